@@ -1,6 +1,8 @@
 use crate::row::Value;
 use std::collections::{HashMap};
+use std::fmt;
 use crate::constraint_state::{ConstraintKind, Constraint};
+use crate::parser::Expression;
 
 // ==============================================================================
 // ENUMS
@@ -9,9 +11,24 @@ use crate::constraint_state::{ConstraintKind, Constraint};
 pub enum DataType {
     String,
     Integer,
+    Float,
+    Boolean,
     Null,
 }
 
+impl fmt::Display for DataType {
+    // Rendered as the SQL type keyword the parser accepts.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataType::String => write!(f, "STRING"),
+            DataType::Integer => write!(f, "INTEGER"),
+            DataType::Float => write!(f, "FLOAT"),
+            DataType::Boolean => write!(f, "BOOLEAN"),
+            DataType::Null => write!(f, "NULL"),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum ColumnError {
     DefaultValueTypeMismatch,
@@ -40,7 +57,25 @@ pub struct ColumnBuilder {
 // ==============================================================================
 // IMPLEMENTATIONS
 // ==============================================================================
+impl DataType {
+    #[must_use]
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, DataType::Integer | DataType::Float)
+    }
+
+    /// Whether values of the two types can be compared: numeric types with each
+    /// other, any other type only with itself, and anything with `Null`.
+    #[must_use]
+    pub fn is_comparable_with(&self, other: &DataType) -> bool {
+        self == other
+            || *self == DataType::Null
+            || *other == DataType::Null
+            || (self.is_numeric() && other.is_numeric())
+    }
+}
+
 impl ColumnBuilder {
+    #[must_use]
     pub fn new(name: &str, data_type: DataType) -> Self {
         Self {
             name: name.to_string(),
@@ -49,20 +84,28 @@ impl ColumnBuilder {
         }
     }
 
+    #[must_use]
     pub fn not_null(mut self) -> Self {
         self.constraints.insert(ConstraintKind::NotNull, Constraint::Unit(ConstraintKind::NotNull));
         self
     }
 
+    #[must_use]
     pub fn unique(mut self) -> Self {
         self.constraints.insert(ConstraintKind::Unique, Constraint::Unit(ConstraintKind::Unique));
         self
     }
 
-    pub fn default(mut self, value: Value) -> Result<Self, ColumnError> {
-        if value.get_data_type() != self.data_type {
+    /// Sets the column's DEFAULT, widened to the column's type.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DefaultValueTypeMismatch` if the value doesn't fit the column.
+    pub fn default(mut self, mut value: Value) -> Result<Self, ColumnError> {
+        if !value.is_compatible_with(&self.data_type) {
             return Err(ColumnError::DefaultValueTypeMismatch);
         }
+        value.widen_to(&self.data_type);
 
         self.constraints.insert(
             ConstraintKind::Default,
@@ -72,11 +115,29 @@ impl ColumnBuilder {
         Ok(self)
     }
 
+    #[must_use]
     pub fn index(mut self) -> Self {
         self.constraints.insert(ConstraintKind::Index, Constraint::Unit(ConstraintKind::Index));
         self
     }
 
+    /// Fills an omitted (NULL) value with the next number in a per-column sequence,
+    /// starting at 1. Explicit values are kept, and the sequence skips past them.
+    #[must_use]
+    pub fn auto_increment(mut self) -> Self {
+        self.constraints.insert(ConstraintKind::AutoIncrement, Constraint::Unit(ConstraintKind::AutoIncrement));
+        self
+    }
+
+    /// Requires `predicate` not to be false for any row. Columns are referenced by
+    /// their exact names, and may include columns other than this one.
+    #[must_use]
+    pub fn check(mut self, predicate: Expression) -> Self {
+        self.constraints.insert(ConstraintKind::Check, Constraint::Check(predicate));
+        self
+    }
+
+    #[must_use]
     pub fn build(self) -> Column {
         Column {
             name: self.name,
@@ -167,4 +228,45 @@ mod tests {
             Some(&Constraint::Unit(ConstraintKind::Index))
         );
     }
+
+    #[test]
+    fn test_is_numeric() {
+        assert!(DataType::Integer.is_numeric());
+        assert!(DataType::Float.is_numeric());
+        assert!(!DataType::String.is_numeric());
+        assert!(!DataType::Boolean.is_numeric());
+        assert!(!DataType::Null.is_numeric());
+    }
+
+    #[test]
+    fn test_is_comparable_with_matrix() {
+        use DataType::{Boolean, Float, Integer, Null, String};
+        let types = [String, Integer, Float, Boolean, Null];
+        let comparable = [
+            //  String Integer Float  Boolean Null
+            [true,  false, false, false, true], // String
+            [false, true,  true,  false, true], // Integer
+            [false, true,  true,  false, true], // Float
+            [false, false, false, true,  true], // Boolean
+            [true,  true,  true,  true,  true], // Null
+        ];
+
+        for (left, row) in types.iter().zip(comparable) {
+            for (right, expected) in types.iter().zip(row) {
+                assert_eq!(left.is_comparable_with(right), expected, "{left} vs {right}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_integer_default_is_widened_for_float_column() {
+        let column = ColumnBuilder::new("price", DataType::Float)
+            .default(Value::Integer(5)).unwrap()
+            .build();
+
+        assert_eq!(
+            column.constraints.get(&ConstraintKind::Default),
+            Some(&Constraint::WithValue(ConstraintKind::Default, Value::Float(5.0)))
+        );
+    }
 }
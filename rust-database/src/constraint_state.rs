@@ -1,4 +1,5 @@
-use std::collections::{HashMap, HashSet, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use crate::parser::Expression;
 use crate::row::Value;
 use crate::schema::Schema;
 
@@ -13,45 +14,91 @@ pub enum ConstraintKind {
     Unique,
     Default,
     Index,
+    Check,
+    AutoIncrement,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Constraint {
     Unit(ConstraintKind),         
     WithValue(ConstraintKind, Value), 
+    /// A predicate every row must not make false; unknown (NULL) passes, as in SQL.
+    Check(Expression),
 }
 
 // ========================================================================================
 // STRUCT
 // ========================================================================================
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct ConstraintState {
     pub unique_values: HashMap<String, HashSet<Value>>,
     pub not_null_columns: HashSet<String>,
     pub default_values: HashMap<String, Value>,
-    pub indexes: HashMap<String, BTreeSet<Value>>,
+    /// Indexed column → value → ids of the rows holding it. Maintained by `Table`,
+    /// since row ids aren't known while a row is being validated.
+    pub indexes: HashMap<String, BTreeMap<Value, BTreeSet<u64>>>,
 
-    // Composite unique: column group → seen combinations
-    // pub composite_uniques: HashMap<Vec<String>, HashSet<Vec<Value>>>,
+    /// Auto-increment column → the value the next row omitting it will get.
+    pub next_auto_increment: HashMap<String, i64>,
+    /// Composite unique: column group → combinations held by stored rows.
+    pub composite_uniques: HashMap<Vec<String>, HashSet<Vec<Value>>>,
 
-    // Foreign key enforcement: (referenced table, column) → allowed values
-    // pub foreign_keys: HashMap<(String, String), HashSet<Value>>,
+    // Foreign keys span tables, so `Database` enforces them (see `Database::add_foreign_key`).
+
+    /// Changes made through the claim/release methods while a snapshot is open,
+    /// oldest first, so `restore` can undo them.
+    journal: Vec<Change>,
+    open_snapshots: usize,
+}
+
+/// One change to the unique sets or indexes, as recorded in the journal.
+#[derive(Clone, Debug)]
+enum Change {
+    UniqueClaimed(String, Value),
+    UniqueReleased(String, Value),
+    CompositeClaimed(Vec<String>, Vec<Value>),
+    CompositeReleased(Vec<String>, Vec<Value>),
+    Indexed(String, Value, u64),
+    Unindexed(String, Value, u64),
+}
+
+/// A point to roll the unique sets (single and composite) and indexes back to.
+/// Only a position in the journal, so taking one costs nothing; NOT NULL columns,
+/// defaults and sequences aren't covered. Each snapshot must be ended by `restore`
+/// or `commit`, innermost first.
+#[derive(Debug)]
+#[must_use]
+pub struct ConstraintSnapshot {
+    journal_len: usize,
 }
 
 // ========================================================================================
 // IMPLEMENTATIOn
 // ========================================================================================
 
+impl Constraint {
+    #[must_use]
+    pub fn kind(&self) -> ConstraintKind {
+        match self {
+            Constraint::Unit(kind) | Constraint::WithValue(kind, _) => *kind,
+            Constraint::Check(_) => ConstraintKind::Check,
+        }
+    }
+}
+
 impl ConstraintState {
-    pub fn new(_schema: &Schema) -> Self { 
-        Self::from_schema(_schema)
+    #[must_use]
+    pub fn new(schema: &Schema) -> Self {
+        Self::from_schema(schema)
     }
 
+    #[must_use]
     pub fn from_schema(schema: &Schema) -> Self {
         let mut unique_values = HashMap::new();
         let mut default_values = HashMap::new();
         let mut not_null_columns = HashSet::new();
         let mut indexes = HashMap::new();
+        let mut next_auto_increment = HashMap::new();
 
         for col in &schema.columns {
             for constraint in col.constraints.values() {
@@ -63,7 +110,10 @@ impl ConstraintState {
                         unique_values.insert(col.name.clone(), HashSet::new());
                     }
                     Constraint::Unit(ConstraintKind::Index) => {
-                        indexes.insert(col.name.clone(), BTreeSet::new());
+                        indexes.insert(col.name.clone(), BTreeMap::new());
+                    }
+                    Constraint::Unit(ConstraintKind::AutoIncrement) => {
+                        next_auto_increment.insert(col.name.clone(), 1);
                     }
                     Constraint::WithValue(ConstraintKind::Default, val) => {
                         default_values.insert(col.name.clone(), val.clone());
@@ -75,13 +125,195 @@ impl ConstraintState {
             }
         }
 
-        return ConstraintState {
+        let composite_uniques = schema.composite_uniques.iter().map(|group| (group.clone(), HashSet::new())).collect();
+
+        ConstraintState {
             unique_values,
-            default_values,
             not_null_columns,
+            default_values,
             indexes,
+            next_auto_increment,
+            composite_uniques,
+            journal: Vec::new(),
+            open_snapshots: 0,
+        }
+    }
+
+    /// Starts recording changes made through the claim/release methods, so they
+    /// can be undone with `restore`. Changes made directly to the public maps
+    /// aren't recorded.
+    pub fn snapshot(&mut self) -> ConstraintSnapshot {
+        self.open_snapshots += 1;
+        ConstraintSnapshot { journal_len: self.journal.len() }
+    }
+
+    /// Undoes every recorded change made since `snapshot` was taken, newest first.
+    // Taken by value so a snapshot can't be ended twice.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn restore(&mut self, snapshot: ConstraintSnapshot) {
+        while self.journal.len() > snapshot.journal_len {
+            let Some(change) = self.journal.pop() else { break };
+            match change {
+                Change::UniqueClaimed(column, value) => {
+                    if let Some(seen) = self.unique_values.get_mut(&column) {
+                        seen.remove(&value);
+                    }
+                }
+                Change::UniqueReleased(column, value) => {
+                    if let Some(seen) = self.unique_values.get_mut(&column) {
+                        seen.insert(value);
+                    }
+                }
+                Change::CompositeClaimed(group, key) => {
+                    if let Some(seen) = self.composite_uniques.get_mut(&group) {
+                        seen.remove(&key);
+                    }
+                }
+                Change::CompositeReleased(group, key) => {
+                    if let Some(seen) = self.composite_uniques.get_mut(&group) {
+                        seen.insert(key);
+                    }
+                }
+                Change::Indexed(column, value, row_id) => Self::remove_from_index(&mut self.indexes, &column, &value, row_id),
+                Change::Unindexed(column, value, row_id) => {
+                    if let Some(index) = self.indexes.get_mut(&column) {
+                        index.entry(value).or_default().insert(row_id);
+                    }
+                }
+            }
+        }
+        self.end_snapshot();
+    }
+
+    /// Keeps the changes made since `snapshot` was taken.
+    // Taken by value so a snapshot can't be ended twice.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn commit(&mut self, snapshot: ConstraintSnapshot) {
+        debug_assert!(snapshot.journal_len <= self.journal.len());
+        self.end_snapshot();
+    }
+
+    fn end_snapshot(&mut self) {
+        self.open_snapshots = self.open_snapshots.saturating_sub(1);
+        if self.open_snapshots == 0 {
+            self.journal.clear();
+        }
+    }
+
+    fn record(&mut self, change: Change) {
+        if self.open_snapshots > 0 {
+            self.journal.push(change);
+        }
+    }
+
+    /// Marks `value` as taken in a UNIQUE column. `Null` and unconstrained columns are ignored.
+    pub fn claim_unique(&mut self, column: &str, value: &Value) {
+        if *value != Value::Null
+            && let Some(seen) = self.unique_values.get_mut(column)
+            && seen.insert(value.clone())
+        {
+            self.record(Change::UniqueClaimed(column.to_string(), value.clone()));
+        }
+    }
+
+    pub fn release_unique(&mut self, column: &str, value: &Value) {
+        if let Some(seen) = self.unique_values.get_mut(column)
+            && seen.remove(value)
+        {
+            self.record(Change::UniqueReleased(column.to_string(), value.clone()));
         }
     }
+
+    pub fn claim_composite(&mut self, group: &[String], key: Vec<Value>) {
+        if let Some(seen) = self.composite_uniques.get_mut(group)
+            && seen.insert(key.clone())
+        {
+            self.record(Change::CompositeClaimed(group.to_vec(), key));
+        }
+    }
+
+    pub fn release_composite(&mut self, group: &[String], key: &[Value]) {
+        if let Some(seen) = self.composite_uniques.get_mut(group)
+            && seen.remove(key)
+        {
+            self.record(Change::CompositeReleased(group.to_vec(), key.to_vec()));
+        }
+    }
+
+    /// Adds `row_id` under `value` in the column's index, if it has one.
+    pub fn index_value(&mut self, column: &str, value: &Value, row_id: u64) {
+        if let Some(index) = self.indexes.get_mut(column)
+            && index.entry(value.clone()).or_default().insert(row_id)
+        {
+            self.record(Change::Indexed(column.to_string(), value.clone(), row_id));
+        }
+    }
+
+    pub fn unindex_value(&mut self, column: &str, value: &Value, row_id: u64) {
+        if self.indexes.get(column).and_then(|index| index.get(value)).is_some_and(|row_ids| row_ids.contains(&row_id)) {
+            Self::remove_from_index(&mut self.indexes, column, value, row_id);
+            self.record(Change::Unindexed(column.to_string(), value.clone(), row_id));
+        }
+    }
+
+    /// Removes one row id from an index, dropping the value's entry once it's empty.
+    fn remove_from_index(indexes: &mut HashMap<String, BTreeMap<Value, BTreeSet<u64>>>, column: &str, value: &Value, row_id: u64) {
+        if let Some(index) = indexes.get_mut(column)
+            && let Some(row_ids) = index.get_mut(value)
+        {
+            row_ids.remove(&row_id);
+            if row_ids.is_empty() {
+                index.remove(value);
+            }
+        }
+    }
+
+    /// Checks that the state tracks exactly the constraints `schema` declares: each
+    /// column it holds UNIQUE, NOT NULL, DEFAULT, INDEX or auto-increment state for
+    /// exists with that constraint, and the other way round. Defaults must match;
+    /// tracked values, index entries and sequences aren't inspected. The error
+    /// describes the first mismatch found.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first mismatch.
+    pub fn validate_against_schema(&self, schema: &Schema) -> Result<(), String> {
+        fn names<'a>(keys: impl Iterator<Item = &'a String>) -> BTreeSet<&'a str> {
+            keys.map(String::as_str).collect()
+        }
+
+        let expected = Self::from_schema(schema);
+        let tracked = [
+            ("UNIQUE", names(self.unique_values.keys()), names(expected.unique_values.keys())),
+            ("NOT NULL", names(self.not_null_columns.iter()), names(expected.not_null_columns.iter())),
+            ("DEFAULT", names(self.default_values.keys()), names(expected.default_values.keys())),
+            ("INDEX", names(self.indexes.keys()), names(expected.indexes.keys())),
+            ("AUTO_INCREMENT", names(self.next_auto_increment.keys()), names(expected.next_auto_increment.keys())),
+        ];
+        for (constraint, actual, declared) in tracked {
+            if let Some(column) = actual.difference(&declared).next() {
+                return Err(if schema.has_column(column) {
+                    format!("state tracks {constraint} for column '{column}', which the schema doesn't declare")
+                } else {
+                    format!("state tracks {constraint} for column '{column}', which isn't in the schema")
+                });
+            }
+            if let Some(column) = declared.difference(&actual).next() {
+                return Err(format!("schema declares {constraint} on column '{column}', but the state doesn't track it"));
+            }
+        }
+
+        if let Some((column, value)) = self.default_values.iter().find(|(column, value)| expected.default_values[*column] != **value) {
+            return Err(format!("state's DEFAULT {value:?} for column '{column}' differs from the schema's"));
+        }
+        if let Some(group) = self.composite_uniques.keys().find(|group| !expected.composite_uniques.contains_key(*group)) {
+            return Err(format!("state tracks UNIQUE ({}), which the schema doesn't declare", group.join(", ")));
+        }
+        if let Some(group) = expected.composite_uniques.keys().find(|group| !self.composite_uniques.contains_key(*group)) {
+            return Err(format!("schema declares UNIQUE ({}), but the state doesn't track it", group.join(", ")));
+        }
+        Ok(())
+    }
 }
 
 
@@ -99,10 +331,7 @@ mod tests {
     fn make_column(name: &str, data_type: DataType, constraints: Vec<Constraint>) -> Column {
         let mut map: HashMap<ConstraintKind, Constraint> = std::collections::HashMap::new();
         for c in constraints {
-            match &c {
-                Constraint::Unit(kind) => map.insert(*kind, c.clone()),
-                Constraint::WithValue(kind, _) => map.insert(*kind, c.clone()),
-            };
+            map.insert(c.kind(), c.clone());
         }
         Column {
             name: name.to_string(),
@@ -112,7 +341,7 @@ mod tests {
     }
  
     fn make_schema(columns: Vec<Column>) -> Schema {
-        return Schema::new(columns).unwrap();
+        Schema::new(columns).unwrap()
     }
 
     #[test]
@@ -230,4 +459,78 @@ mod tests {
         assert!(state.unique_values.contains_key("login"));
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_restore_undoes_changes_after_snapshot() {
+        let col = make_column(
+            "email",
+            DataType::String,
+            vec![
+                Constraint::Unit(ConstraintKind::Unique),
+                Constraint::Unit(ConstraintKind::Index),
+            ],
+        );
+        let schema = make_schema(vec![col]);
+        let mut state = ConstraintState::from_schema(&schema);
+        let kept = Value::String("kept@example.com".to_string());
+        let added = Value::String("added@example.com".to_string());
+        state.unique_values.get_mut("email").unwrap().insert(kept.clone());
+        state.indexes.get_mut("email").unwrap().insert(kept.clone(), BTreeSet::from([0]));
+
+        let snapshot = state.snapshot();
+        state.release_unique("email", &kept);
+        state.unindex_value("email", &kept, 0);
+        state.claim_unique("email", &added);
+        state.index_value("email", &added, 1);
+        state.index_value("email", &added, 2);
+
+        // A nested snapshot only undoes its own changes.
+        let inner = state.snapshot();
+        state.unindex_value("email", &added, 2);
+        state.restore(inner);
+        assert_eq!(state.indexes["email"], BTreeMap::from([(added.clone(), BTreeSet::from([1, 2]))]));
+
+        state.restore(snapshot);
+
+        assert_eq!(state.unique_values["email"], HashSet::from([kept.clone()]));
+        assert_eq!(state.indexes["email"], BTreeMap::from([(kept.clone(), BTreeSet::from([0]))]));
+        assert!(state.not_null_columns.is_empty());
+        assert!(state.journal.is_empty());
+
+        // Committed changes stay, and nothing is recorded once no snapshot is open.
+        let snapshot = state.snapshot();
+        state.claim_unique("email", &added);
+        state.commit(snapshot);
+        state.release_unique("email", &kept);
+        assert_eq!(state.unique_values["email"], HashSet::from([added]));
+        assert!(state.journal.is_empty());
+    }
+
+    #[test]
+    fn test_validate_against_schema_detects_drift() {
+        let mut schema = make_schema(vec![
+            make_column("id", DataType::Integer, vec![Constraint::Unit(ConstraintKind::Unique)]),
+            make_column("email", DataType::String, vec![Constraint::Unit(ConstraintKind::Unique)]),
+            make_column("age", DataType::Integer, vec![Constraint::WithValue(ConstraintKind::Default, Value::Integer(18))]),
+        ]);
+        let mut state = ConstraintState::from_schema(&schema);
+        assert_eq!(state.validate_against_schema(&schema), Ok(()));
+
+        // Dropping the column from the schema alone leaves its unique set behind.
+        schema.drop_column("email").unwrap();
+        let err = state.validate_against_schema(&schema).unwrap_err();
+        assert!(err.contains("UNIQUE") && err.contains("'email'") && err.contains("isn't in the schema"), "{err}");
+        state.unique_values.remove("email");
+        assert_eq!(state.validate_against_schema(&schema), Ok(()));
+
+        state.not_null_columns.insert("id".to_string());
+        assert!(state.validate_against_schema(&schema).unwrap_err().contains("doesn't declare"));
+        state.not_null_columns.clear();
+
+        state.unique_values.remove("id");
+        assert!(state.validate_against_schema(&schema).unwrap_err().contains("doesn't track"));
+        state.unique_values.insert("id".to_string(), HashSet::new());
+
+        state.default_values.insert("age".to_string(), Value::Integer(21));
+        assert!(state.validate_against_schema(&schema).unwrap_err().contains("DEFAULT"));
+    }
+}
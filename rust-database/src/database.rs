@@ -3,9 +3,16 @@
 // Our Database and subsequent tests. We store the Database and the Tables.
 // The tables are passed in as we'll have a seperate persistence layer to use.
 // ================================
-use std::collections::HashMap;
-use crate::table::{Table};
-use crate::schema::{Schema};
+use std::collections::{HashMap, HashSet};
+use std::time::SystemTime;
+use crate::table::{Table, TableErrors};
+use crate::column::DataType;
+use crate::schema::{NameResolution, Schema};
+use crate::row::Value;
+use crate::executor::{Executor, ExecutorConfig, QueryResult};
+use crate::parser::{Parser, Statements, TruncateIdentity};
+use crate::query::{FromRow, QueryError, RowView};
+use crate::tokenizer::Tokenizer;
 
 
 // ========================================================================================
@@ -16,6 +23,58 @@ use crate::schema::{Schema};
 pub enum DatabaseError {
     DuplicateTableName(String),
     TableNotFound { name: String },
+    SchemaMismatch { name: String },
+    Table(TableErrors),
+}
+
+impl From<TableErrors> for DatabaseError {
+    fn from(err: TableErrors) -> Self {
+        DatabaseError::Table(err)
+    }
+}
+
+// ========================================================================================
+// AUDIT LOG
+// ========================================================================================
+// Human-oriented record of what was done to the database. Unlike a replay log
+// this isn't meant to rebuild state, just to answer "who touched what, when".
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AuditAction {
+    CreateTable,
+    RenameTable { new_name: String },
+    DropTable,
+    Insert { row_id: u64 },
+    /// A DELETE, with the number of rows it removed.
+    Delete { rows: usize },
+    /// An UPDATE, with the number of rows it rewrote.
+    Update { rows: usize },
+    Truncate { rows: usize },
+    DropColumn { column: String },
+    AlterColumnType { column: String, data_type: DataType },
+}
+
+/// `table.column` may only hold values present in `references_table.references_column`
+/// (or NULL). Names are stored as the tables and columns were declared.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForeignKey {
+    pub table: String,
+    pub column: String,
+    pub references_table: String,
+    pub references_column: String,
+}
+
+impl std::fmt::Display for ForeignKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{} -> {}.{}", self.table, self.column, self.references_table, self.references_column)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditEntry {
+    pub timestamp: SystemTime,
+    pub table: String,
+    pub action: AuditAction,
 }
 
 // ========================================================================================
@@ -23,63 +82,627 @@ pub enum DatabaseError {
 // ========================================================================================
 pub struct Database {
     tables: HashMap<String, Table>,
+    normalized_table_names: HashMap<String, String>, // normalized → stored name
+    name_resolution: NameResolution,
+    executor_config: ExecutorConfig,
+    foreign_keys: Vec<ForeignKey>,
+    audit_enabled: bool,
+    audit_log: Vec<AuditEntry>,
 }
 
 // ========================================================================================
 // IMPLEMENTATION
 // ========================================================================================
 impl Database {
+    #[must_use]
     pub fn new() -> Self {
         Database { 
             tables: HashMap::new(),
+            normalized_table_names: HashMap::new(),
+            name_resolution: NameResolution::default(),
+            executor_config: ExecutorConfig::default(),
+            foreign_keys: Vec::new(),
+            audit_enabled: false,
+            audit_log: Vec::new(),
         }
     }
 
+    /// Creates an empty database that resolves table and column names using `resolution`.
+    #[must_use]
+    pub fn with_name_resolution(resolution: NameResolution) -> Self {
+        Database {
+            name_resolution: resolution,
+            ..Self::new()
+        }
+    }
+
+    #[must_use]
+    pub fn name_resolution(&self) -> NameResolution {
+        self.name_resolution
+    }
+
+    #[must_use]
+    pub fn executor_config(&self) -> &ExecutorConfig {
+        &self.executor_config
+    }
+
+    /// Replaces the limits and defaults statements run under, from the next statement on.
+    pub fn set_executor_config(&mut self, config: ExecutorConfig) {
+        self.executor_config = config;
+    }
+
+    /// Adds an empty table with `schema`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DuplicateTableName` if a table by that name already exists.
     pub fn create_table(&mut self, name: String, schema: Schema) -> Result<(), DatabaseError> {
-        if self.tables.contains_key(&name) {
+        if self.resolve_table_name(&name).is_some() {
             return Err(DatabaseError::DuplicateTableName(name));
         }
 
+        self.normalized_table_names.insert(NameResolution::normalize(&name), name.clone());
+        self.record_audit(&name, AuditAction::CreateTable);
         self.tables.insert(name, Table::new(schema));
         Ok(())
     }
 
+    /// Returns the named table, creating it with `schema` first if it doesn't exist.
+    /// An existing table must have exactly `schema`, otherwise `SchemaMismatch`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SchemaMismatch` if the table exists with a different schema.
+    pub fn get_or_create_table(&mut self, name: &str, schema: Schema) -> Result<&mut Table, DatabaseError> {
+        match self.resolve_table_name(name) {
+            Some(stored_name) if self.tables.get(&stored_name).is_some_and(|table| table.schema != schema) => {
+                Err(DatabaseError::SchemaMismatch { name: name.to_string() })
+            }
+            Some(_) => self.get_table_mut(name.to_string()),
+            None => {
+                self.create_table(name.to_string(), schema)?;
+                self.get_table_mut(name.to_string())
+            }
+        }
+    }
+
+    /// Renames a table, keeping its foreign keys pointing at it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` if `name` doesn't exist, or `DuplicateTableName` if
+    /// `new_name` is taken.
     pub fn update_table_name(&mut self, name: String, new_name: String) -> Result<(), DatabaseError> {
-        if !self.tables.contains_key(&name) {
+        let Some(stored_name) = self.resolve_table_name(&name) else {
             return Err(DatabaseError::TableNotFound { name });
-        }
+        };
 
-        if self.tables.contains_key(&new_name) {
+        // A case-only rename of the same table is allowed under case-insensitive resolution.
+        if self.resolve_table_name(&new_name).is_some_and(|existing| existing != stored_name || existing == new_name) {
             return Err(DatabaseError::DuplicateTableName(new_name));
         }
 
-        if let Some(table) = self.tables.remove(&name) {
+        if let Some(table) = self.tables.remove(&stored_name) {
+            for key in &mut self.foreign_keys {
+                for name in [&mut key.table, &mut key.references_table] {
+                    if *name == stored_name {
+                        name.clone_from(&new_name);
+                    }
+                }
+            }
+            self.normalized_table_names.remove(&NameResolution::normalize(&stored_name));
+            self.normalized_table_names.insert(NameResolution::normalize(&new_name), new_name.clone());
+            self.record_audit(&stored_name, AuditAction::RenameTable { new_name: new_name.clone() });
             self.tables.insert(new_name, table);
         }
 
         Ok(())
     }
 
+    /// Drops a table along with any foreign key to or from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` if the table doesn't exist.
     pub fn delete_table(&mut self, name: String) -> Result<(), DatabaseError> {
-        if self.tables.remove(&name).is_none() {
+        let Some(stored_name) = self.resolve_table_name(&name) else {
             return Err(DatabaseError::TableNotFound { name });
-        }
+        };
+        self.normalized_table_names.remove(&NameResolution::normalize(&stored_name));
+        self.tables.remove(&stored_name);
+        self.foreign_keys.retain(|key| key.table != stored_name && key.references_table != stored_name);
+        self.record_audit(&stored_name, AuditAction::DropTable);
         Ok(())
     }
 
+    /// Names of every table, sorted.
+    pub fn list_tables(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.tables.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[must_use]
+    pub fn table_count(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// Drops every table, along with the foreign keys between them. Settings and
+    /// the audit log are kept; each drop is audited like `delete_table`.
+    pub fn drop_all_tables(&mut self) {
+        let mut names: Vec<String> = self.tables.keys().cloned().collect();
+        names.sort_unstable();
+        self.tables.clear();
+        self.normalized_table_names.clear();
+        self.foreign_keys.clear();
+        for name in names {
+            self.record_audit(&name, AuditAction::DropTable);
+        }
+    }
+
+    /// Alias for `drop_all_tables`: leaves the database empty.
+    pub fn clear(&mut self) {
+        self.drop_all_tables();
+    }
+
     /// Gets an immutable reference to a table.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` if the table doesn't exist.
     pub fn get_table(&self, name: String) -> Result<&Table, DatabaseError> {
-        self.tables
-            .get(&name)
+        self.resolve_table_name(&name)
+            .and_then(|stored_name| self.tables.get(&stored_name))
             .ok_or(DatabaseError::TableNotFound { name })
     }
 
     /// Mutable version if needed:
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` if the table doesn't exist.
     pub fn get_table_mut(&mut self, name: String) -> Result<&mut Table, DatabaseError> {
-        self.tables
-            .get_mut(&name)
+        self.resolve_table_name(&name)
+            .and_then(|stored_name| self.tables.get_mut(&stored_name))
             .ok_or(DatabaseError::TableNotFound { name })
     }
+
+    /// Runs `f` against the named table with a single lookup.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` if the table doesn't exist.
+    pub fn with_table<R>(&self, name: String, f: impl FnOnce(&Table) -> R) -> Result<R, DatabaseError> {
+        self.get_table(name).map(f)
+    }
+
+    /// Runs `f` against the named table mutably with a single lookup. Changes made
+    /// through the table directly are not audited.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` if the table doesn't exist.
+    pub fn with_table_mut<R>(&mut self, name: String, f: impl FnOnce(&mut Table) -> R) -> Result<R, DatabaseError> {
+        self.get_table_mut(name).map(f)
+    }
+
+    /// Runs a SELECT and converts each result row into a `T`, matching columns to
+    /// fields by name. Other statement kinds are rejected, since this only reads.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotASelect` for any other statement, the tokenizer, parser or executor
+    /// error if the query fails, or the first conversion error from `T::from_row`.
+    pub fn query_into<T: FromRow>(&self, sql: &str) -> Result<Vec<T>, QueryError> {
+        let result = self.query(sql)?;
+
+        result
+            .rows
+            .iter()
+            .map(|row| T::from_row(&RowView::new(&result.columns, &row.values)))
+            .collect()
+    }
+
+    /// Runs a SELECT of one column, such as `SELECT COUNT(*) ...`, and returns the
+    /// first row's value, or `None` if there are no rows.
+    ///
+    /// # Errors
+    ///
+    /// Fails as `query_into` does, or with `NotAScalar` if the query returns other than one
+    /// column.
+    pub fn query_scalar(&self, sql: &str) -> Result<Option<Value>, QueryError> {
+        let result = self.query(sql)?;
+        if result.columns.len() != 1 {
+            return Err(QueryError::NotAScalar(result.columns.len()));
+        }
+        Ok(result.rows.into_iter().next().and_then(|row| row.values.into_iter().next()))
+    }
+
+    fn query(&self, sql: &str) -> Result<QueryResult, QueryError> {
+        let tokens = Tokenizer::new(sql).tokenize()?;
+        let statement = Parser::new(tokens).parse_statement()?;
+        let Statements::Select(stmt) = &statement else {
+            return Err(QueryError::NotASelect);
+        };
+        Executor {}.validate(&statement, self).map_err(QueryError::Execution)?;
+        Executor::execute_select(stmt, self).map_err(QueryError::Execution)
+    }
+
+    /// Inserts a row into the named table, recording it in the audit log. The row's
+    /// foreign keys are checked first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` if the table doesn't exist, or `Table` wrapping a foreign
+    /// key violation or a row the table rejects.
+    pub fn insert_row(&mut self, name: String, values: Vec<Value>) -> Result<u64, DatabaseError> {
+        let stored_name = self.resolve_table_name(&name).ok_or(DatabaseError::TableNotFound { name })?;
+        self.check_foreign_keys(&stored_name, &values)?;
+        let row_id = self.get_table_mut(stored_name.clone())?.add_row(values)?;
+        self.record_audit(&stored_name, AuditAction::Insert { row_id });
+        Ok(row_id)
+    }
+
+    /// Rewrites rows of the named table, recording it in the audit log. Each edit is
+    /// `(row id, new values)`. The new values' foreign keys are checked, as on insert,
+    /// and so are rows elsewhere that reference a value the edits take away.
+    /// Nothing changes if any check fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` if the table doesn't exist, or `Table` wrapping a foreign
+    /// key violation, `RowStillReferenced`, or the error of a row the table rejects.
+    pub fn update_rows(&mut self, name: &str, edits: Vec<(u64, Vec<Value>)>) -> Result<usize, DatabaseError> {
+        let stored_name = self.resolve_table_name(name).ok_or_else(|| DatabaseError::TableNotFound { name: name.to_string() })?;
+        for (_, values) in &edits {
+            self.check_foreign_keys(&stored_name, values)?;
+        }
+        let edited: HashMap<u64, &[Value]> = edits.iter().map(|(id, values)| (*id, values.as_slice())).collect();
+        let rows_after: Vec<&[Value]> = self.tables[&stored_name]
+            .rows
+            .iter()
+            .map(|(id, row)| edited.get(id).copied().unwrap_or(&row.values))
+            .collect();
+        self.check_referencing_rows(&stored_name, &rows_after)?;
+
+        let rows = edits.len();
+        self.get_table_mut(stored_name.clone())?.edit_rows(edits)?;
+        self.record_audit(&stored_name, AuditAction::Update { rows });
+        Ok(rows)
+    }
+
+    /// Deletes rows of the named table by id, recording it in the audit log. Rows
+    /// elsewhere must not reference a value only the deleted rows hold. Nothing
+    /// changes if that check fails or an id is missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` if the table doesn't exist, `Table(RowNotFound)` for an
+    /// unknown id, or `Table(RowStillReferenced)` if a deletion would orphan a row.
+    pub fn delete_rows(&mut self, name: &str, ids: &[u64]) -> Result<usize, DatabaseError> {
+        let stored_name = self.resolve_table_name(name).ok_or_else(|| DatabaseError::TableNotFound { name: name.to_string() })?;
+        let table = &self.tables[&stored_name];
+        if let Some(&missing) = ids.iter().find(|id| !table.rows.contains_key(id)) {
+            return Err(DatabaseError::Table(TableErrors::RowNotFound(missing)));
+        }
+        let deleted: HashSet<u64> = ids.iter().copied().collect();
+        let rows_after: Vec<&[Value]> = table
+            .rows
+            .iter()
+            .filter(|(id, _)| !deleted.contains(id))
+            .map(|(_, row)| row.values.as_slice())
+            .collect();
+        self.check_referencing_rows(&stored_name, &rows_after)?;
+
+        let table = self.get_table_mut(stored_name.clone())?;
+        for &id in &deleted {
+            // Every id was checked above, so the row is always present.
+            let _ = table.delete_row(id);
+        }
+        self.record_audit(&stored_name, AuditAction::Delete { rows: deleted.len() });
+        Ok(deleted.len())
+    }
+
+    /// Removes every row of the named table, recording it in the audit log, and
+    /// returns how many there were. Refused while another table's foreign key still
+    /// references one of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` if the table doesn't exist, or `Table(RowStillReferenced)`
+    /// if a row elsewhere references it.
+    pub fn truncate_table(&mut self, name: &str, identity: TruncateIdentity) -> Result<usize, DatabaseError> {
+        let stored_name = self.resolve_table_name(name).ok_or_else(|| DatabaseError::TableNotFound { name: name.to_string() })?;
+        self.check_referencing_rows(&stored_name, &[])?;
+
+        let table = self.get_table_mut(stored_name.clone())?;
+        let rows = table.row_count();
+        table.truncate(identity);
+        self.record_audit(&stored_name, AuditAction::Truncate { rows });
+        Ok(rows)
+    }
+
+    /// Drops a column from the named table, recording it in the audit log. A column
+    /// at either end of a foreign key can't be dropped, since inserts could no
+    /// longer be checked against the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` or `Table(ColumnNotFound)` if the column doesn't exist,
+    /// or `Table(ColumnInForeignKey)` if a foreign key uses it.
+    pub fn drop_column(&mut self, table: &str, column: &str) -> Result<(), DatabaseError> {
+        let (table, column) = self.resolve_column(table, column)?;
+        self.check_not_in_foreign_key(&table, &column)?;
+        self.get_table_mut(table.clone())?.drop_column(&column)?;
+        self.record_audit(&table, AuditAction::DropColumn { column });
+        Ok(())
+    }
+
+    /// Changes a column's type with `Table::change_column_type`, recording it in the
+    /// audit log. A column at either end of a foreign key keeps its type, since its
+    /// converted values would no longer match the other side.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` or `Table(ColumnNotFound)` if the column doesn't exist,
+    /// `Table(ColumnInForeignKey)` if a foreign key uses it, or the table's error if
+    /// the values can't be converted.
+    pub fn change_column_type(&mut self, table: &str, column: &str, data_type: DataType) -> Result<(), DatabaseError> {
+        let (table, column) = self.resolve_column(table, column)?;
+        self.check_not_in_foreign_key(&table, &column)?;
+        self.get_table_mut(table.clone())?.change_column_type(&column, data_type.clone())?;
+        self.record_audit(&table, AuditAction::AlterColumnType { column, data_type });
+        Ok(())
+    }
+
+    fn check_not_in_foreign_key(&self, table: &str, column: &str) -> Result<(), DatabaseError> {
+        let in_key = |key: &&ForeignKey| {
+            (key.table == table && key.column == column)
+                || (key.references_table == table && key.references_column == column)
+        };
+        match self.foreign_keys.iter().find(in_key) {
+            Some(key) => Err(DatabaseError::Table(TableErrors::ColumnInForeignKey {
+                column: column.to_string(),
+                foreign_key: key.to_string(),
+            })),
+            None => Ok(()),
+        }
+    }
+
+    /// Declares that `table.column` references `references_table.references_column`.
+    /// Rows already stored must satisfy it. From then on `insert_row` and `update_rows`
+    /// (and so SQL INSERT and UPDATE) reject a non-NULL value with no match, and
+    /// `update_rows`, `delete_rows` and `truncate_table` on the referenced table refuse
+    /// to leave a referencing row without one. Changes made through a `Table`
+    /// directly are not checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound` or `Table(ColumnNotFound)` if either side doesn't exist, or
+    /// `Table(ForeignKeyViolation)` for the first stored row without a match.
+    pub fn add_foreign_key(
+        &mut self,
+        table: &str,
+        column: &str,
+        references_table: &str,
+        references_column: &str,
+    ) -> Result<(), DatabaseError> {
+        let (table, column) = self.resolve_column(table, column)?;
+        let (references_table, references_column) = self.resolve_column(references_table, references_column)?;
+        let key = ForeignKey { table, column, references_table, references_column };
+
+        let child = &self.tables[&key.table];
+        let position = child.schema.get_column_index(&key.column).unwrap_or_default();
+        for row in child.rows.values() {
+            self.check_foreign_key(&key, &row.values[position])?;
+        }
+
+        if !self.foreign_keys.contains(&key) {
+            self.foreign_keys.push(key);
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn foreign_keys(&self) -> &[ForeignKey] {
+        &self.foreign_keys
+    }
+
+    /// Resolves a table and column name to the names they were declared with.
+    fn resolve_column(&self, table: &str, column: &str) -> Result<(String, String), DatabaseError> {
+        let stored_table = self.resolve_table_name(table).ok_or_else(|| DatabaseError::TableNotFound { name: table.to_string() })?;
+        let schema = &self.tables[&stored_table].schema;
+        let index = schema
+            .resolve_column_index(column, self.name_resolution)
+            .ok_or_else(|| DatabaseError::Table(TableErrors::ColumnNotFound(column.to_string())))?;
+        Ok((stored_table, schema.columns[index].name.clone()))
+    }
+
+    /// Checks a row bound for `table` against each of its foreign keys. A key whose
+    /// column has gone (say, dropped through `Table::drop_column` directly) fails
+    /// rather than being skipped, so no insert goes unchecked.
+    fn check_foreign_keys(&self, table: &str, values: &[Value]) -> Result<(), DatabaseError> {
+        let schema = &self.tables[table].schema;
+        for key in self.foreign_keys.iter().filter(|key| key.table == table) {
+            let position = schema.get_column_index(&key.column);
+            let referenced = self.tables.get(&key.references_table);
+            let (Some(position), true) = (position, referenced.is_some_and(|table| table.schema.has_column(&key.references_column))) else {
+                return Err(DatabaseError::Table(TableErrors::UnresolvedForeignKey { foreign_key: key.to_string() }));
+            };
+            // A row of the wrong length is left for `add_row` to reject.
+            if let Some(value) = values.get(position) {
+                self.check_foreign_key(key, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that every row referencing `table` still finds its value once the table
+    /// holds just `rows_after`. A key from the table to itself reads the referencing
+    /// values from `rows_after` too.
+    fn check_referencing_rows(&self, table: &str, rows_after: &[&[Value]]) -> Result<(), DatabaseError> {
+        let schema = &self.tables[table].schema;
+        for key in self.foreign_keys.iter().filter(|key| key.references_table == table) {
+            let unresolved = || DatabaseError::Table(TableErrors::UnresolvedForeignKey { foreign_key: key.to_string() });
+            let referenced = schema.get_column_index(&key.references_column).ok_or_else(unresolved)?;
+            let child = self.tables.get(&key.table).ok_or_else(unresolved)?;
+            let position = child.schema.get_column_index(&key.column).ok_or_else(unresolved)?;
+
+            // A row of the wrong length is left for the table to reject.
+            let remaining: HashSet<&Value> = rows_after.iter().filter_map(|row| row.get(referenced)).collect();
+            let referencing: Vec<&Value> = if key.table == table {
+                rows_after.iter().filter_map(|row| row.get(position)).collect()
+            } else {
+                child.rows.values().map(|row| &row.values[position]).collect()
+            };
+            if let Some(orphan) = referencing.into_iter().find(|value| **value != Value::Null && !remaining.contains(value)) {
+                return Err(DatabaseError::Table(TableErrors::RowStillReferenced {
+                    value: orphan.clone(),
+                    foreign_key: key.to_string(),
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_foreign_key(&self, key: &ForeignKey, value: &Value) -> Result<(), DatabaseError> {
+        let referenced = self.tables.get(&key.references_table);
+        if *value == Value::Null || referenced.is_some_and(|table| table.contains_value(&key.references_column, value)) {
+            return Ok(());
+        }
+        Err(DatabaseError::Table(TableErrors::ForeignKeyViolation {
+            column: key.column.clone(),
+            value: value.clone(),
+            references: format!("{}.{}", key.references_table, key.references_column),
+        }))
+    }
+
+    /// Turns audit logging on or off. Entries already recorded are kept.
+    pub fn set_audit_enabled(&mut self, enabled: bool) {
+        self.audit_enabled = enabled;
+    }
+
+    #[must_use]
+    pub fn audit_log(&self) -> &[AuditEntry] {
+        &self.audit_log
+    }
+
+    /// Appends an entry if auditing is on. `table` is recorded under its stored
+    /// name when it still exists.
+    pub(crate) fn record_audit(&mut self, table: &str, action: AuditAction) {
+        if self.audit_enabled {
+            self.audit_log.push(AuditEntry {
+                timestamp: SystemTime::now(),
+                table: self.resolve_table_name(table).unwrap_or_else(|| table.to_string()),
+                action,
+            });
+        }
+    }
+
+    /// Maps a table name from a query to the name it is stored under, honouring the
+    /// database's `NameResolution` mode.
+    fn resolve_table_name(&self, name: &str) -> Option<String> {
+        if self.tables.contains_key(name) {
+            return Some(name.to_string());
+        }
+        match self.name_resolution {
+            NameResolution::CaseSensitive => None,
+            NameResolution::CaseInsensitive => self
+                .normalized_table_names
+                .get(&NameResolution::normalize(name))
+                .cloned(),
+        }
+    }
+}
+
+impl Default for Database {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ========================================================================================
+// BUILDER
+// ========================================================================================
+
+/// Builds a populated database in one chain, mostly for tests and fixtures:
+///
+/// ```
+/// use rust_database::column::{ColumnBuilder, DataType};
+/// use rust_database::database::DatabaseBuilder;
+/// use rust_database::row::Value;
+/// use rust_database::schema::Schema;
+///
+/// let schema = Schema::new(vec![ColumnBuilder::new("id", DataType::Integer).build()]).unwrap();
+/// let db = DatabaseBuilder::new()
+///     .table("users", schema)
+///     .rows(vec![vec![Value::Integer(1)], vec![Value::Integer(2)]])
+///     .build()
+///     .unwrap();
+/// assert_eq!(db.get_table("users".to_string()).unwrap().row_count(), 2);
+/// ```
+///
+/// `rows` adds to the table most recently named by `table`. The first error is
+/// kept and returned by `build`; later steps are skipped.
+#[derive(Default)]
+pub struct DatabaseBuilder {
+    db: Database,
+    current_table: Option<String>,
+    error: Option<DatabaseError>,
+}
+
+impl DatabaseBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts from an empty database using `resolution` for table and column names.
+    #[must_use]
+    pub fn with_name_resolution(resolution: NameResolution) -> Self {
+        Self { db: Database::with_name_resolution(resolution), ..Self::default() }
+    }
+
+    #[must_use]
+    pub fn table(mut self, name: &str, schema: Schema) -> Self {
+        if self.error.is_none() {
+            match self.db.create_table(name.to_string(), schema) {
+                Ok(()) => self.current_table = Some(name.to_string()),
+                Err(err) => self.error = Some(err),
+            }
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn rows(mut self, rows: Vec<Vec<Value>>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        let Some(table) = self.current_table.clone() else {
+            self.error = Some(DatabaseError::TableNotFound { name: String::new() });
+            return self;
+        };
+        for values in rows {
+            if let Err(err) = self.db.insert_row(table.clone(), values) {
+                self.error = Some(err);
+                break;
+            }
+        }
+        self
+    }
+
+    /// Returns the database, or the first error any step hit.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from creating a table or inserting a row.
+    pub fn build(self) -> Result<Database, DatabaseError> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.db),
+        }
+    }
 }
 
 
@@ -88,9 +711,15 @@ impl Database {
 // ========================================================================================
 #[cfg(test)]
 mod table_crud_tests {
-    use crate::schema::{Schema};
+    use crate::schema::{NameResolution, Schema};
     use crate::column::{Column, DataType};
-    use crate::database::{Database, DatabaseError}; 
+    use crate::database::{AuditAction, Database, DatabaseBuilder, DatabaseError, ForeignKey}; 
+    use crate::table::{Table, TableErrors};
+    use crate::query::{FromRow, QueryError, RowView};
+    use crate::executor::{ExecutionError, Executor, QueryResult};
+    use crate::parser::Parser;
+    use crate::tokenizer::Tokenizer;
+    use crate::row::Value;
     use std::collections::HashMap;
 
     fn test_schema() -> Schema {
@@ -136,6 +765,43 @@ mod table_crud_tests {
         assert!(db.get_table("name".to_string()).is_err());
     }
 
+    #[test]
+    fn test_list_tables_and_count() {
+        let mut db = Database::new();
+        assert!(db.list_tables().is_empty());
+        assert_eq!(db.table_count(), 0);
+
+        for name in ["users", "orders", "accounts"] {
+            db.create_table(name.to_string(), test_schema()).unwrap();
+        }
+        assert_eq!(db.list_tables(), vec!["accounts", "orders", "users"]);
+        assert_eq!(db.table_count(), 3);
+
+        db.update_table_name("orders".to_string(), "purchases".to_string()).unwrap();
+        db.delete_table("accounts".to_string()).unwrap();
+        assert_eq!(db.list_tables(), vec!["purchases", "users"]);
+        assert_eq!(db.table_count(), 2);
+    }
+
+    #[test]
+    fn test_drop_all_tables_empties_the_database() {
+        let mut db = Database::new();
+        for name in ["users", "orders"] {
+            db.create_table(name.to_string(), test_schema()).unwrap();
+        }
+        db.drop_all_tables();
+        assert_eq!(db.table_count(), 0);
+        assert!(db.get_table("users".to_string()).is_err());
+
+        // The old names are free again.
+        db.create_table("users".to_string(), test_schema()).unwrap();
+        db.insert_row("users".to_string(), vec![Value::String("Alice".to_string()), Value::Integer(30)]).unwrap();
+        db.clear();
+        assert!(db.list_tables().is_empty());
+        db.create_table("users".to_string(), test_schema()).unwrap();
+        assert!(db.get_table("users".to_string()).unwrap().is_empty());
+    }
+
     #[test]
     fn test_create_table_duplicate_fails() {
         let mut db = Database::new();
@@ -157,4 +823,433 @@ mod table_crud_tests {
         let result = db.delete_table("nonexistent".to_string());
         assert_eq!(result, Err(DatabaseError::TableNotFound { name: "nonexistent".to_string() }));
     }
+
+    #[test]
+    fn test_table_lookup_is_case_sensitive_by_default() {
+        let mut db = Database::new();
+        db.create_table("users".to_string(), test_schema()).unwrap();
+        assert!(db.get_table("Users".to_string()).is_err());
+        assert!(db.create_table("USERS".to_string(), test_schema()).is_ok());
+    }
+
+    #[test]
+    fn test_case_insensitive_table_resolution() {
+        let mut db = Database::with_name_resolution(NameResolution::CaseInsensitive);
+        db.create_table("users".to_string(), test_schema()).unwrap();
+
+        assert!(db.get_table("Users".to_string()).is_ok());
+        assert!(db.get_table_mut("USERS".to_string()).is_ok());
+        assert_eq!(
+            db.create_table("USERS".to_string(), test_schema()),
+            Err(DatabaseError::DuplicateTableName("USERS".to_string()))
+        );
+
+        db.update_table_name("Users".to_string(), "Members".to_string()).unwrap();
+        assert!(db.get_table("members".to_string()).is_ok());
+        assert!(db.get_table("users".to_string()).is_err());
+
+        db.delete_table("MEMBERS".to_string()).unwrap();
+        assert!(db.get_table("Members".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_audit_log_is_disabled_by_default() {
+        let mut db = Database::new();
+        db.create_table("users".to_string(), test_schema()).unwrap();
+        assert!(db.audit_log().is_empty());
+    }
+
+    #[test]
+    fn test_audit_log_records_create_insert_drop() {
+        let mut db = Database::new();
+        db.set_audit_enabled(true);
+
+        db.create_table("users".to_string(), test_schema()).unwrap();
+        db.insert_row("users".to_string(), vec![Value::String("Alice".into()), Value::Integer(30)]).unwrap();
+        db.insert_row("users".to_string(), vec![Value::String("Bob".into()), Value::Integer(25)]).unwrap();
+        db.delete_table("users".to_string()).unwrap();
+
+        let actions: Vec<(&str, &AuditAction)> = db
+            .audit_log()
+            .iter()
+            .map(|entry| (entry.table.as_str(), &entry.action))
+            .collect();
+        assert_eq!(actions, vec![
+            ("users", &AuditAction::CreateTable),
+            ("users", &AuditAction::Insert { row_id: 0 }),
+            ("users", &AuditAction::Insert { row_id: 1 }),
+            ("users", &AuditAction::DropTable),
+        ]);
+        assert!(db.audit_log().windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+    }
+
+    #[test]
+    fn test_audit_log_records_sql_statements() {
+        let mut db = Database::with_name_resolution(NameResolution::CaseInsensitive);
+        db.set_audit_enabled(true);
+        db.create_table("users".to_string(), test_schema()).unwrap();
+        let executor = Executor {};
+        let parse = |sql: &str| Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement().unwrap();
+        for sql in [
+            "INSERT INTO users VALUES ('Alice', 30);",
+            "INSERT INTO users VALUES ('Bob', 25);",
+            "UPDATE users SET age = 31 WHERE name = 'Alice';",
+            "DELETE FROM Users WHERE age < 30;",
+            "ALTER TABLE users ALTER COLUMN age TYPE STRING;",
+            "ALTER TABLE users DROP COLUMN age;",
+            "TRUNCATE TABLE users;",
+        ] {
+            executor.execute(&parse(sql), &mut db).unwrap();
+        }
+        // Rejected statements leave no entry.
+        assert!(executor.execute(&parse("UPDATE users SET missing = 1;"), &mut db).is_err());
+
+        let actions: Vec<(&str, &AuditAction)> = db.audit_log().iter().map(|entry| (entry.table.as_str(), &entry.action)).collect();
+        assert_eq!(actions, vec![
+            ("users", &AuditAction::CreateTable),
+            ("users", &AuditAction::Insert { row_id: 0 }),
+            ("users", &AuditAction::Insert { row_id: 1 }),
+            ("users", &AuditAction::Update { rows: 1 }),
+            ("users", &AuditAction::Delete { rows: 1 }),
+            ("users", &AuditAction::AlterColumnType { column: "age".to_string(), data_type: DataType::String }),
+            ("users", &AuditAction::DropColumn { column: "age".to_string() }),
+            ("users", &AuditAction::Truncate { rows: 1 }),
+        ]);
+    }
+
+    #[test]
+    fn test_failed_operations_are_not_audited() {
+        let mut db = Database::new();
+        db.set_audit_enabled(true);
+
+        assert!(db.insert_row("missing".to_string(), vec![]).is_err());
+        assert!(db.delete_table("missing".to_string()).is_err());
+        assert!(db.audit_log().is_empty());
+    }
+
+    #[test]
+    fn test_with_table_mut_inserts_rows() {
+        let mut db = Database::new();
+        db.create_table("users".to_string(), test_schema()).unwrap();
+
+        let inserted = db.with_table_mut("users".to_string(), |table| {
+            [("Alice", 30), ("Bob", 25), ("Charlie", 35)]
+                .into_iter()
+                .map(|(name, age)| table.add_row(vec![Value::String(name.to_string()), Value::Integer(age)]))
+                .collect::<Result<Vec<u64>, _>>()
+        });
+
+        assert_eq!(inserted, Ok(Ok(vec![0, 1, 2])));
+        assert_eq!(db.with_table("users".to_string(), Table::row_count), Ok(3));
+        assert_eq!(
+            db.with_table_mut("missing".to_string(), |table| table.row_count()),
+            Err(DatabaseError::TableNotFound { name: "missing".to_string() })
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Person {
+        name: String,
+        age: i64,
+    }
+
+    impl FromRow for Person {
+        fn from_row(row: &RowView<'_>) -> Result<Self, QueryError> {
+            Ok(Person { name: row.get("name")?, age: row.get("age")? })
+        }
+    }
+
+    #[test]
+    fn test_query_into_struct() {
+        let mut db = Database::new();
+        db.create_table("users".to_string(), test_schema()).unwrap();
+        db.insert_row("users".to_string(), vec![Value::String("Alice".to_string()), Value::Integer(30)]).unwrap();
+        db.insert_row("users".to_string(), vec![Value::String("Bob".to_string()), Value::Integer(25)]).unwrap();
+
+        let people: Vec<Person> = db.query_into("SELECT age, name FROM users WHERE age > 26;").unwrap();
+
+        assert_eq!(people, vec![Person { name: "Alice".to_string(), age: 30 }]);
+    }
+
+    #[test]
+    fn test_query_into_reports_missing_columns_and_non_selects() {
+        let mut db = Database::new();
+        db.create_table("users".to_string(), test_schema()).unwrap();
+        db.insert_row("users".to_string(), vec![Value::String("Alice".to_string()), Value::Integer(30)]).unwrap();
+
+        let missing = db.query_into::<Person>("SELECT name FROM users;");
+        assert!(matches!(missing, Err(QueryError::MissingColumn(column)) if column == "age"));
+        let not_a_select = db.query_into::<Person>("DELETE FROM users;");
+        assert!(matches!(not_a_select, Err(QueryError::NotASelect)));
+    }
+
+    #[test]
+    fn test_query_scalar() {
+        let mut db = Database::new();
+        db.create_table("users".to_string(), test_schema()).unwrap();
+        db.insert_row("users".to_string(), vec![Value::String("Alice".to_string()), Value::Integer(30)]).unwrap();
+        db.insert_row("users".to_string(), vec![Value::String("Bob".to_string()), Value::Integer(25)]).unwrap();
+
+        assert_eq!(db.query_scalar("SELECT COUNT(*) FROM users;").unwrap(), Some(Value::Integer(2)));
+        assert_eq!(db.query_scalar("SELECT MAX(age) FROM users;").unwrap(), Some(Value::Integer(30)));
+        assert_eq!(db.query_scalar("SELECT name FROM users WHERE age > 100;").unwrap(), None);
+        assert!(matches!(db.query_scalar("SELECT name, age FROM users;"), Err(QueryError::NotAScalar(2))));
+        assert!(matches!(db.query_scalar("SELECT * FROM users;"), Err(QueryError::NotAScalar(2))));
+    }
+
+    #[test]
+    fn test_query_validates_columns() {
+        let mut db = Database::new();
+        db.create_table("users".to_string(), test_schema()).unwrap();
+
+        // The table is empty, so only validation can catch the unknown column.
+        let bad_where = db.query_scalar("SELECT name FROM users WHERE no_such_col = 1;");
+        assert!(matches!(bad_where, Err(QueryError::Execution(ExecutionError::ColumnNotFound(column))) if column == "no_such_col"));
+        let bad_select = db.query_into::<Person>("SELECT no_such_col FROM users;");
+        assert!(matches!(bad_select, Err(QueryError::Execution(ExecutionError::ColumnNotFound(column))) if column == "no_such_col"));
+    }
+
+    #[test]
+    fn test_builder_creates_tables_and_rows() {
+        let orders = Schema::new(vec![
+            Column { name: "id".into(), data_type: DataType::Integer, constraints: HashMap::new() },
+            Column { name: "owner".into(), data_type: DataType::String, constraints: HashMap::new() },
+        ])
+        .unwrap();
+        let db = DatabaseBuilder::new()
+            .table("users", test_schema())
+            .rows(vec![
+                vec![Value::String("Alice".to_string()), Value::Integer(30)],
+                vec![Value::String("Bob".to_string()), Value::Integer(25)],
+            ])
+            .table("orders", orders)
+            .rows(vec![vec![Value::Integer(1), Value::String("Bob".to_string())]])
+            .build()
+            .unwrap();
+
+        let people: Vec<Person> = db.query_into("SELECT name, age FROM users WHERE age < 26;").unwrap();
+        assert_eq!(people, vec![Person { name: "Bob".to_string(), age: 25 }]);
+        let owner = db.with_table("orders".to_string(), |orders| orders.first_row().map(|(_, row)| row.values[1].clone()));
+        assert_eq!(owner, Ok(Some(Value::String("Bob".to_string()))));
+    }
+
+    #[test]
+    fn test_builder_reports_first_error() {
+        let result = DatabaseBuilder::new()
+            .table("users", test_schema())
+            .rows(vec![vec![Value::Integer(1)]])
+            .table("users", test_schema())
+            .build();
+        assert!(matches!(result, Err(DatabaseError::Table(_))));
+
+        let result = DatabaseBuilder::new().rows(vec![vec![Value::Integer(1)]]).build();
+        assert!(matches!(result, Err(DatabaseError::TableNotFound { .. })));
+    }
+
+    #[test]
+    fn test_get_or_create_table_creates_then_gets() {
+        let mut db = Database::new();
+        db.set_audit_enabled(true);
+
+        let table = db.get_or_create_table("users", test_schema()).unwrap();
+        table.add_row(vec![Value::String("Alice".to_string()), Value::Integer(30)]).unwrap();
+
+        let table = db.get_or_create_table("users", test_schema()).unwrap();
+        assert_eq!(table.row_count(), 1);
+        assert_eq!(db.audit_log().len(), 1);
+    }
+
+    #[test]
+    fn test_get_or_create_table_rejects_schema_mismatch() {
+        let mut db = Database::new();
+        db.create_table("users".to_string(), test_schema()).unwrap();
+        let other = Schema::new(vec![
+            Column { name: "name".into(), data_type: DataType::String, constraints: HashMap::new() },
+        ])
+        .unwrap();
+
+        assert_eq!(
+            db.get_or_create_table("users", other).err(),
+            Some(DatabaseError::SchemaMismatch { name: "users".to_string() })
+        );
+    }
+
+    fn create_users_and_orders(db: &mut Database) {
+        let orders = Schema::new(vec![
+            Column { name: "id".into(), data_type: DataType::Integer, constraints: HashMap::new() },
+            Column { name: "user_name".into(), data_type: DataType::String, constraints: HashMap::new() },
+        ])
+        .unwrap();
+        db.create_table("users".to_string(), test_schema()).unwrap();
+        db.create_table("orders".to_string(), orders).unwrap();
+        db.insert_row("users".to_string(), vec![Value::String("Alice".to_string()), Value::Integer(30)]).unwrap();
+    }
+
+    #[test]
+    fn test_foreign_key_accepts_valid_and_null_references() {
+        let mut db = Database::new();
+        create_users_and_orders(&mut db);
+        db.add_foreign_key("orders", "user_name", "users", "name").unwrap();
+
+        db.insert_row("orders".to_string(), vec![Value::Integer(1), Value::String("Alice".to_string())]).unwrap();
+        db.insert_row("orders".to_string(), vec![Value::Integer(2), Value::Null]).unwrap();
+        assert_eq!(db.with_table("orders".to_string(), Table::row_count), Ok(2));
+    }
+
+    #[test]
+    fn test_foreign_key_rejects_dangling_reference() {
+        let mut db = Database::new();
+        create_users_and_orders(&mut db);
+        db.add_foreign_key("orders", "user_name", "users", "name").unwrap();
+
+        assert_eq!(
+            db.insert_row("orders".to_string(), vec![Value::Integer(1), Value::String("Bob".to_string())]),
+            Err(DatabaseError::Table(TableErrors::ForeignKeyViolation {
+                column: "user_name".to_string(),
+                value: Value::String("Bob".to_string()),
+                references: "users.name".to_string(),
+            }))
+        );
+        assert_eq!(db.with_table("orders".to_string(), Table::row_count), Ok(0));
+    }
+
+    #[test]
+    fn test_add_foreign_key_checks_existing_rows_and_follows_renames() {
+        let mut db = Database::new();
+        create_users_and_orders(&mut db);
+        db.insert_row("orders".to_string(), vec![Value::Integer(1), Value::String("Bob".to_string())]).unwrap();
+
+        assert!(matches!(
+            db.add_foreign_key("orders", "user_name", "users", "name"),
+            Err(DatabaseError::Table(TableErrors::ForeignKeyViolation { .. }))
+        ));
+        assert!(matches!(db.add_foreign_key("orders", "missing", "users", "name"), Err(DatabaseError::Table(TableErrors::ColumnNotFound(_)))));
+        assert!(db.foreign_keys().is_empty());
+
+        db.insert_row("users".to_string(), vec![Value::String("Bob".to_string()), Value::Integer(25)]).unwrap();
+        db.add_foreign_key("orders", "user_name", "users", "name").unwrap();
+        db.update_table_name("users".to_string(), "members".to_string()).unwrap();
+        assert_eq!(db.foreign_keys(), &[ForeignKey {
+            table: "orders".to_string(),
+            column: "user_name".to_string(),
+            references_table: "members".to_string(),
+            references_column: "name".to_string(),
+        }]);
+        assert!(db.insert_row("orders".to_string(), vec![Value::Integer(2), Value::String("Carol".to_string())]).is_err());
+    }
+
+    #[test]
+    fn test_foreign_key_columns_cannot_be_dropped() {
+        let mut db = Database::new();
+        create_users_and_orders(&mut db);
+        db.add_foreign_key("orders", "user_name", "users", "name").unwrap();
+
+        assert_eq!(
+            db.drop_column("users", "name"),
+            Err(DatabaseError::Table(TableErrors::ColumnInForeignKey {
+                column: "name".to_string(),
+                foreign_key: "orders.user_name -> users.name".to_string(),
+            }))
+        );
+        assert!(db.drop_column("orders", "user_name").is_err());
+        let drop_sql = Parser::new(Tokenizer::new("ALTER TABLE users DROP COLUMN name;").tokenize().unwrap()).parse_statement().unwrap();
+        assert!(matches!(
+            Executor {}.execute(&drop_sql, &mut db),
+            Err(ExecutionError::InvalidRow(TableErrors::ColumnInForeignKey { .. }))
+        ));
+        db.drop_column("users", "age").unwrap();
+        assert_eq!(db.with_table("users".to_string(), |table| table.schema.column_count()), Ok(1));
+
+        // Dropped behind the database's back: inserts fail instead of going unchecked.
+        db.with_table_mut("users".to_string(), |table| table.drop_column("name")).unwrap().unwrap();
+        assert_eq!(
+            db.insert_row("orders".to_string(), vec![Value::Integer(1), Value::String("Bob".to_string())]),
+            Err(DatabaseError::Table(TableErrors::UnresolvedForeignKey {
+                foreign_key: "orders.user_name -> users.name".to_string(),
+            }))
+        );
+    }
+    fn run(db: &mut Database, sql: &str) -> Result<QueryResult, ExecutionError> {
+        let ast = Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement().unwrap();
+        Executor {}.execute(&ast, db)
+    }
+
+    fn orders_referencing_alice() -> Database {
+        let mut db = Database::new();
+        create_users_and_orders(&mut db);
+        db.insert_row("users".to_string(), vec![Value::String("Bob".to_string()), Value::Integer(25)]).unwrap();
+        db.add_foreign_key("orders", "user_name", "users", "name").unwrap();
+        db.insert_row("orders".to_string(), vec![Value::Integer(1), Value::String("Alice".to_string())]).unwrap();
+        db
+    }
+
+    fn still_referenced(value: &str) -> TableErrors {
+        TableErrors::RowStillReferenced {
+            value: Value::String(value.to_string()),
+            foreign_key: "orders.user_name -> users.name".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_update_checks_foreign_keys_on_both_sides() {
+        let mut db = orders_referencing_alice();
+
+        assert!(matches!(
+            run(&mut db, "UPDATE orders SET user_name = 'Carol';"),
+            Err(ExecutionError::InvalidRow(TableErrors::ForeignKeyViolation { .. }))
+        ));
+        assert!(matches!(
+            run(&mut db, "UPDATE users SET name = 'Alicia' WHERE name = 'Alice';"),
+            Err(ExecutionError::InvalidRow(err)) if err == still_referenced("Alice")
+        ));
+        assert_eq!(run(&mut db, "UPDATE orders SET user_name = 'Bob';").unwrap().rows_affected, 1);
+        assert_eq!(run(&mut db, "UPDATE users SET name = 'Alicia' WHERE name = 'Alice';").unwrap().rows_affected, 1);
+    }
+
+    #[test]
+    fn test_delete_cannot_orphan_referencing_rows() {
+        let mut db = orders_referencing_alice();
+
+        assert!(matches!(
+            run(&mut db, "DELETE FROM users WHERE name = 'Alice';"),
+            Err(ExecutionError::InvalidRow(err)) if err == still_referenced("Alice")
+        ));
+        assert_eq!(db.with_table("users".to_string(), Table::row_count), Ok(2));
+        assert_eq!(run(&mut db, "DELETE FROM users WHERE name = 'Bob';").unwrap().rows_affected, 1);
+
+        run(&mut db, "DELETE FROM orders;").unwrap();
+        assert_eq!(run(&mut db, "DELETE FROM users;").unwrap().rows_affected, 1);
+    }
+
+    #[test]
+    fn test_truncate_cannot_orphan_referencing_rows() {
+        let mut db = orders_referencing_alice();
+
+        assert!(matches!(
+            run(&mut db, "TRUNCATE TABLE users;"),
+            Err(ExecutionError::InvalidRow(err)) if err == still_referenced("Alice")
+        ));
+        assert_eq!(db.with_table("users".to_string(), Table::row_count), Ok(2));
+
+        assert_eq!(run(&mut db, "TRUNCATE TABLE orders;").unwrap().rows_affected, 1);
+        assert_eq!(run(&mut db, "TRUNCATE TABLE users;").unwrap().rows_affected, 2);
+    }
+
+    #[test]
+    fn test_foreign_key_columns_keep_their_type() {
+        let mut db = orders_referencing_alice();
+
+        assert_eq!(
+            db.change_column_type("users", "name", DataType::String),
+            Err(DatabaseError::Table(TableErrors::ColumnInForeignKey {
+                column: "name".to_string(),
+                foreign_key: "orders.user_name -> users.name".to_string(),
+            }))
+        );
+        assert!(matches!(
+            run(&mut db, "ALTER TABLE orders ALTER COLUMN user_name TYPE STRING;"),
+            Err(ExecutionError::InvalidRow(TableErrors::ColumnInForeignKey { .. }))
+        ));
+        run(&mut db, "ALTER TABLE users ALTER COLUMN age TYPE FLOAT;").unwrap();
+    }
 }
@@ -1,23 +1,116 @@
 // executor.rs
 
-use crate::parser::{Statements, SelectStatement, Expression, BinaryOperator, SelectColumn, Literal};
-use crate::row::{Row, Value};
-use crate::schema::{Schema};
-use crate::database::{Database};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ops::Bound;
+use thiserror::Error;
+
+use crate::parser::{Statements, SelectStatement, CreateTableStatement, DeleteStatement, DropTableStatement, AlterTableStatement, AlterTableAction, SetOperationStatement, DescribeStatement, UpdateStatement, InsertStatement, TruncateStatement, Expression, BinaryOperator, SelectColumn, Literal, SortDirection, NullsOrder, AggregateFunc};
+use crate::row::{Row, RowErrors, Value};
+use crate::schema::{NameResolution, Schema, SchemaError};
+use crate::column::{ColumnBuilder, DataType};
+use crate::constraint_state::{Constraint, ConstraintKind};
+use crate::database::{Database, DatabaseError};
+use crate::table::{Table, TableErrors};
+use crate::tokenizer::Span;
 
 #[derive(Debug, PartialEq)] // Added for testing
 pub struct QueryResult {
+    /// Output column names, all distinct: a repeated name gets a `_1`, `_2`, ...
+    /// suffix, as in `SELECT name, name` giving `name` and `name_1`.
+    pub columns: Vec<String>,
     pub rows: Vec<Row>,
+    /// Rows changed by a write statement; zero for reads and DDL.
+    pub rows_affected: usize,
+}
+
+impl QueryResult {
+    #[must_use]
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column == name)
+    }
+
+    /// Looks up a value by row position and column name.
+    #[must_use]
+    pub fn get(&self, row: usize, column: &str) -> Option<&Value> {
+        let index = self.column_index(column)?;
+        self.rows.get(row)?.values.get(index)
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Error)]
 pub enum ExecutionError {
+    #[error("Table not found")]
     TableNotFound,
+    #[error("Column '{0}' not found")]
     ColumnNotFound(String),
+    #[error("Invalid expression")]
     InvalidExpression,
+    #[error("Type mismatch")]
     TypeMismatch,
+    #[error("Table '{0}' already exists")]
+    TableAlreadyExists(String),
+    #[error("Invalid schema: {0}")]
+    InvalidSchema(SchemaError),
+    #[error("Expression nesting exceeds the maximum depth of {0}")]
+    ExpressionTooDeep(usize),
+    #[error("Invalid row: {0}")]
+    InvalidRow(TableErrors),
+    #[error("Numeric overflow")]
+    NumericOverflow,
+    /// Statement `statement` (zero-based) of a `replay` failed; earlier ones stay applied.
+    #[error("Replay failed at statement {statement}: {source}")]
+    ReplayFailed { statement: usize, source: Box<ExecutionError> },
+    #[error("SELECT collected more than {max_rows} rows; add a LIMIT or a narrower WHERE clause")]
+    TooManyRows { max_rows: usize },
+    #[error("Statement exceeded its budget of {max_steps} steps")]
+    BudgetExceeded { max_steps: usize },
+    #[error("UNION sides select {left} and {right} columns")]
+    UnionColumnCount { left: usize, right: usize },
+    #[error("Column '{0}' is named more than once in the INSERT column list")]
+    DuplicateInsertColumn(String),
+}
+
+/// Limits and defaults applied while executing statements, held by the `Database`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutorConfig {
+    /// Most rows a SELECT may collect before sorting, projecting and paging;
+    /// `None` for no limit. A query stopping early on its LIMIT (no ORDER BY,
+    /// DISTINCT or aggregate) only collects the rows it returns.
+    pub max_rows: Option<usize>,
+    /// Most rows a single statement may examine while scanning, whether or not
+    /// they match; `None` for no limit. Bounds a runaway query's work even when
+    /// it returns little.
+    pub max_steps: Option<usize>,
+    /// Where NULLs sort for an `ORDER BY` key that doesn't say `NULLS FIRST`
+    /// or `NULLS LAST`.
+    pub nulls_order: NullsOrder,
+}
+
+/// Counts the rows a statement examines against `ExecutorConfig::max_steps`.
+struct StepBudget {
+    max_steps: Option<usize>,
+    steps: usize,
+}
+
+impl StepBudget {
+    fn new(config: &ExecutorConfig) -> Self {
+        StepBudget { max_steps: config.max_steps, steps: 0 }
+    }
+
+    fn step(&mut self) -> Result<(), ExecutionError> {
+        self.steps += 1;
+        match self.max_steps {
+            Some(max_steps) if self.steps > max_steps => Err(ExecutionError::BudgetExceeded { max_steps }),
+            _ => Ok(()),
+        }
+    }
 }
 
+/// Deepest AND/OR nesting `evaluate_expression` will recurse into.
+pub const MAX_EXPRESSION_DEPTH: usize = 256;
+
 
 // ==============================================================================
 // EXECUTOR IMPLEMENTATION
@@ -25,193 +118,1427 @@ pub enum ExecutionError {
 pub struct Executor {}
 
 impl Executor {
-    pub fn execute(&self, ast: &Statements, db: &Database) -> Result<QueryResult, ExecutionError> {
+    /// Validates a statement, then runs it against `db`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `ExecutionError` from validation or from running the statement.
+    pub fn execute(&self, ast: &Statements, db: &mut Database) -> Result<QueryResult, ExecutionError> {
+        self.validate(ast, db)?;
+        match ast {
+            Statements::Select(stmt) => Self::execute_select(stmt, db),
+            Statements::CreateTable(stmt) => Self::execute_create_table(stmt, db),
+            Statements::Insert(stmt) => Self::execute_insert(stmt, db),
+            Statements::Delete(stmt) => Self::execute_delete(stmt, db),
+            Statements::Update(stmt) => Self::execute_update(stmt, db),
+            Statements::DropTable(stmt) => Self::execute_drop_table(stmt, db),
+            Statements::AlterTable(stmt) => Self::execute_alter_table(stmt, db),
+            Statements::SetOperation(stmt) => Self::execute_set_operation(stmt, db),
+            Statements::ShowTables => Ok(Self::execute_show_tables(db)),
+            Statements::Describe(stmt) => Self::execute_describe(stmt, db),
+            Statements::Truncate(stmt) => Self::execute_truncate(stmt, db),
+        }
+    }
+
+    /// Executes `statements` in order, as when replaying a log or a migration onto
+    /// `db`. Each statement is checked against the schema as it stands when the
+    /// statement runs, so one written for an older schema (say, an INSERT naming a
+    /// since-dropped column) stops the replay with `ReplayFailed` before it touches
+    /// the table.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ReplayFailed` with the index of the first statement that fails.
+    pub fn replay(&self, statements: &[Statements], db: &mut Database) -> Result<Vec<QueryResult>, ExecutionError> {
+        statements
+            .iter()
+            .enumerate()
+            .map(|(statement, ast)| {
+                self.execute(ast, db)
+                    .map_err(|source| ExecutionError::ReplayFailed { statement, source: Box::new(source) })
+            })
+            .collect()
+    }
+
+    /// Checks that a statement fits the current schema: its table exists (or, for
+    /// CREATE TABLE, doesn't), every column it names exists, and an INSERT without
+    /// a column list supplies one value per column. Doesn't look at any rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TableNotFound`, `ColumnNotFound` or the error for the first mismatch.
+    pub fn validate(&self, ast: &Statements, db: &Database) -> Result<(), ExecutionError> {
+        let resolution = db.name_resolution();
+        let schema = |table: &str| {
+            db.get_table(table.to_string()).map(|table| &table.schema).map_err(|_| ExecutionError::TableNotFound)
+        };
+        let column = |schema: &Schema, name: &str| {
+            schema
+                .resolve_column_index(name, resolution)
+                .map(|_| ())
+                .ok_or_else(|| ExecutionError::ColumnNotFound(name.to_string()))
+        };
+        let select = |stmt: &SelectStatement| {
+            let schema = schema(&stmt.from_table)?;
+            for selected in &stmt.columns {
+                match selected {
+                    SelectColumn::Identifier(name) | SelectColumn::Aggregate { arg: Some(name), .. } => column(schema, name)?,
+                    SelectColumn::Wildcard | SelectColumn::Aggregate { arg: None, .. } => {}
+                }
+            }
+            for (name, ..) in &stmt.order_by {
+                column(schema, name)?;
+            }
+            Self::validate_expression_columns(stmt.where_clause.as_ref(), schema, resolution)
+        };
+
         match ast {
-            Statements::Select(stmt) => self.execute_select(stmt, db),
-            _ => unimplemented!(),
+            Statements::Select(stmt) => select(stmt),
+            Statements::SetOperation(stmt) => {
+                select(&stmt.left)?;
+                select(&stmt.right)
+            }
+            Statements::Insert(stmt) => {
+                let schema = schema(&stmt.table_name)?;
+                if stmt.columns.is_empty() && stmt.values.len() != schema.column_count() {
+                    return Err(ExecutionError::InvalidRow(TableErrors::RowConstructionError(RowErrors::WrongValueCount {
+                        expected: schema.column_count(),
+                        got: stmt.values.len(),
+                    })));
+                }
+                stmt.columns.iter().try_for_each(|name| column(schema, name))
+            }
+            Statements::Update(stmt) => {
+                let schema = schema(&stmt.table_name)?;
+                for (name, _) in &stmt.assignments {
+                    column(schema, name)?;
+                }
+                Self::validate_expression_columns(stmt.where_clause.as_ref(), schema, resolution)
+            }
+            Statements::Delete(stmt) => {
+                let schema = schema(&stmt.table_name)?;
+                Self::validate_expression_columns(stmt.where_clause.as_ref(), schema, resolution)
+            }
+            Statements::Truncate(stmt) => schema(&stmt.table_name).map(|_| ()),
+            Statements::ShowTables => Ok(()),
+            Statements::Describe(stmt) => schema(&stmt.table_name).map(|_| ()),
+            Statements::DropTable(stmt) if stmt.if_exists => Ok(()),
+            Statements::DropTable(stmt) => schema(&stmt.table_name).map(|_| ()),
+            Statements::AlterTable(stmt) => {
+                let schema = schema(&stmt.table_name)?;
+                match &stmt.action {
+                    AlterTableAction::DropColumn(name) | AlterTableAction::AlterColumnType { column: name, .. } => column(schema, name),
+                    AlterTableAction::RenameTo(_) => Ok(()),
+                }
+            }
+            Statements::CreateTable(stmt) => match db.get_table(stmt.table_name.clone()) {
+                Ok(_) => Err(ExecutionError::TableAlreadyExists(stmt.table_name.clone())),
+                Err(_) => Ok(()),
+            },
+        }
+    }
+
+    /// Checks every column a WHERE clause names, even in branches no row would reach.
+    fn validate_expression_columns(
+        where_clause: Option<&Expression>,
+        schema: &Schema,
+        resolution: NameResolution,
+    ) -> Result<(), ExecutionError> {
+        for name in where_clause.map(Expression::column_references).unwrap_or_default() {
+            schema
+                .resolve_column_index(name, resolution)
+                .ok_or_else(|| ExecutionError::ColumnNotFound(name.to_string()))?;
+        }
+        Ok(())
+    }
+
+    fn execute_create_table(
+        stmt: &CreateTableStatement,
+        db: &mut Database,
+    ) -> Result<QueryResult, ExecutionError> {
+        let columns = stmt
+            .columns
+            .iter()
+            .map(|def| {
+                let mut builder = ColumnBuilder::new(&def.name, def.data_type.clone());
+                if let Some(literal) = &def.default {
+                    builder = builder.default(Self::value_from_literal(literal)).map_err(|_| {
+                        ExecutionError::InvalidSchema(SchemaError::DefaultValueTypeMismatch { column_name: def.name.clone() })
+                    })?;
+                }
+                if let Some(predicate) = &def.check {
+                    builder = builder.check(predicate.clone());
+                }
+                let column = def.constraints
+                    .iter()
+                    .fold(builder, |builder, kind| match kind {
+                        ConstraintKind::NotNull => builder.not_null(),
+                        ConstraintKind::Unique => builder.unique(),
+                        ConstraintKind::Index => builder.index(),
+                        ConstraintKind::AutoIncrement => builder.auto_increment(),
+                        // DEFAULT and CHECK are carried in `def.default` and `def.check`.
+                        ConstraintKind::Default | ConstraintKind::Check => builder,
+                    })
+                    .build();
+                Ok(column)
+            })
+            .collect::<Result<Vec<_>, ExecutionError>>()?;
+        let schema = Schema::new(columns).map_err(ExecutionError::InvalidSchema)?;
+
+        db.create_table(stmt.table_name.clone(), schema).map_err(|err| match err {
+            DatabaseError::DuplicateTableName(name) => ExecutionError::TableAlreadyExists(name),
+            _ => ExecutionError::TableNotFound,
+        })?;
+        Ok(QueryResult { columns: Vec::new(), rows: Vec::new(), rows_affected: 0 })
+    }
+
+    fn execute_drop_table(
+        stmt: &DropTableStatement,
+        db: &mut Database,
+    ) -> Result<QueryResult, ExecutionError> {
+        match db.delete_table(stmt.table_name.clone()) {
+            Ok(()) => {}
+            Err(DatabaseError::TableNotFound { .. }) if stmt.if_exists => {}
+            Err(_) => return Err(ExecutionError::TableNotFound),
+        }
+        Ok(QueryResult { columns: Vec::new(), rows: Vec::new(), rows_affected: 0 })
+    }
+
+    fn execute_alter_table(
+        stmt: &AlterTableStatement,
+        db: &mut Database,
+    ) -> Result<QueryResult, ExecutionError> {
+        match &stmt.action {
+            AlterTableAction::DropColumn(name) => {
+                db.drop_column(&stmt.table_name, name).map_err(Self::alter_column_error)?;
+            }
+            AlterTableAction::AlterColumnType { column, data_type } => {
+                db.change_column_type(&stmt.table_name, column, data_type.clone()).map_err(Self::alter_column_error)?;
+            }
+            AlterTableAction::RenameTo(new_name) => {
+                db.update_table_name(stmt.table_name.clone(), new_name.clone()).map_err(|err| match err {
+                    DatabaseError::DuplicateTableName(name) => ExecutionError::TableAlreadyExists(name),
+                    _ => ExecutionError::TableNotFound,
+                })?;
+            }
+        }
+        Ok(QueryResult { columns: Vec::new(), rows: Vec::new(), rows_affected: 0 })
+    }
+
+    /// Inserts one row. With a column list, values are placed by column name, so the
+    /// list may be in any order; columns it leaves out get `Null` (and so their
+    /// default, if any). Without one, values are taken in schema order.
+    fn execute_insert(
+        stmt: &InsertStatement,
+        db: &mut Database,
+    ) -> Result<QueryResult, ExecutionError> {
+        let resolution = db.name_resolution();
+        let schema = &db.get_table(stmt.table_name.clone()).map_err(|_| ExecutionError::TableNotFound)?.schema;
+
+        let values = if stmt.columns.is_empty() {
+            stmt.values.iter().map(Self::value_from_literal).collect()
+        } else {
+            let mut values = vec![Value::Null; schema.columns.len()];
+            let mut assigned = vec![false; schema.columns.len()];
+            for (column, literal) in stmt.columns.iter().zip(&stmt.values) {
+                let index = schema.resolve_column_index(column, resolution)
+                    .ok_or_else(|| ExecutionError::ColumnNotFound(column.clone()))?;
+                if std::mem::replace(&mut assigned[index], true) {
+                    return Err(ExecutionError::DuplicateInsertColumn(column.clone()));
+                }
+                values[index] = Self::value_from_literal(literal);
+            }
+            values
+        };
+
+        db.insert_row(stmt.table_name.clone(), values).map_err(Self::row_change_error)?;
+        Ok(QueryResult { columns: Vec::new(), rows: Vec::new(), rows_affected: 1 })
+    }
+
+    /// Deletes every row matching the WHERE clause (all rows without one). The
+    /// predicate is checked against every row before anything is removed, so an
+    /// invalid predicate deletes nothing; nor does a delete that would orphan a row
+    /// referencing this table through a foreign key.
+    fn execute_delete(
+        stmt: &DeleteStatement,
+        db: &mut Database,
+    ) -> Result<QueryResult, ExecutionError> {
+        let resolution = db.name_resolution();
+        let mut budget = StepBudget::new(db.executor_config());
+        let table = db.get_table(stmt.table_name.clone()).map_err(|_| ExecutionError::TableNotFound)?;
+
+        Self::check_predicate_types(stmt.where_clause.as_ref(), &table.schema, resolution)?;
+        let mut matching_ids = Vec::new();
+        let where_clause = Self::fold_where_clause(stmt.where_clause.as_ref());
+        for (&id, row) in &table.rows {
+            budget.step()?;
+            let matches = match &where_clause {
+                Some(expression) => Self::evaluate_expression(expression, row, &table.schema, resolution)?,
+                None => true,
+            };
+            if matches {
+                matching_ids.push(id);
+            }
+        }
+
+        let rows_affected = db.delete_rows(&stmt.table_name, &matching_ids).map_err(Self::row_change_error)?;
+        Ok(QueryResult { columns: Vec::new(), rows: Vec::new(), rows_affected })
+    }
+
+    /// Removes every row of the table; `rows_affected` is how many there were.
+    /// Refused while a foreign key elsewhere references the table's rows.
+    fn execute_truncate(
+        stmt: &TruncateStatement,
+        db: &mut Database,
+    ) -> Result<QueryResult, ExecutionError> {
+        let rows_affected = db.truncate_table(&stmt.table_name, stmt.identity).map_err(Self::row_change_error)?;
+        Ok(QueryResult { columns: Vec::new(), rows: Vec::new(), rows_affected })
+    }
+
+    fn alter_column_error(err: DatabaseError) -> ExecutionError {
+        match err {
+            DatabaseError::Table(TableErrors::ColumnNotFound(name)) => ExecutionError::ColumnNotFound(name),
+            err => Self::row_change_error(err),
+        }
+    }
+
+    fn row_change_error(err: DatabaseError) -> ExecutionError {
+        match err {
+            DatabaseError::Table(err) => ExecutionError::InvalidRow(err),
+            _ => ExecutionError::TableNotFound,
+        }
+    }
+
+    /// Rewrites the assigned columns of every row matching the WHERE clause, leaving
+    /// other columns as they were; unassigned columns keep their stored value rather
+    /// than falling back to a default. Each new row is revalidated, foreign keys
+    /// included, through `Database::update_rows`; if any row is rejected the whole
+    /// table is restored.
+    fn execute_update(
+        stmt: &UpdateStatement,
+        db: &mut Database,
+    ) -> Result<QueryResult, ExecutionError> {
+        let resolution = db.name_resolution();
+        let mut budget = StepBudget::new(db.executor_config());
+        let table = db.get_table(stmt.table_name.clone()).map_err(|_| ExecutionError::TableNotFound)?;
+
+        let mut assignments = Vec::with_capacity(stmt.assignments.len());
+        for (column, literal) in &stmt.assignments {
+            let index = table.schema.resolve_column_index(column, resolution)
+                .ok_or_else(|| ExecutionError::ColumnNotFound(column.clone()))?;
+            assignments.push((index, Self::value_from_literal(literal)));
+        }
+
+        Self::check_predicate_types(stmt.where_clause.as_ref(), &table.schema, resolution)?;
+        let mut updates = Vec::new();
+        let where_clause = Self::fold_where_clause(stmt.where_clause.as_ref());
+        for (&id, row) in &table.rows {
+            budget.step()?;
+            let matches = match &where_clause {
+                Some(expression) => Self::evaluate_expression(expression, row, &table.schema, resolution)?,
+                None => true,
+            };
+            if matches {
+                let mut values = row.values.clone();
+                for (index, value) in &assignments {
+                    values[*index] = value.clone();
+                }
+                updates.push((id, values));
+            }
         }
+
+        let rows_affected = db.update_rows(&stmt.table_name, updates).map_err(Self::row_change_error)?;
+        Ok(QueryResult { columns: Vec::new(), rows: Vec::new(), rows_affected })
     }
 
-    fn execute_select(
-        &self,
+    pub(crate) fn execute_select(
         stmt: &SelectStatement,
         db: &Database,
     ) -> Result<QueryResult, ExecutionError> {
         let table = db.get_table(stmt.from_table.clone()).map_err(|_| ExecutionError::TableNotFound)?;
+        let resolution = db.name_resolution();
 
-        let filtered_rows: Vec<Row> = table
-            .rows
-            .values()
-            .filter_map(|row| {
-                let should_include = match &stmt.where_clause {
-                    Some(expression) => self.evaluate_expression(expression, row, &table.schema).ok(),
-                    None => Some(true),
+        Self::check_predicate_types(stmt.where_clause.as_ref(), &table.schema, resolution)?;
+        let where_clause = Self::fold_where_clause(stmt.where_clause.as_ref());
+        let indexed_extremes = match where_clause {
+            None => Self::indexed_extremes(&stmt.columns, table, resolution),
+            Some(_) => None,
+        };
+        let candidates: Vec<&Row> = match &where_clause {
+            _ if indexed_extremes.is_some() => Vec::new(),
+            Some(Expression::Literal(Literal::Boolean(false) | Literal::Null, _)) => Vec::new(),
+            Some(expression) => match Self::index_range_candidates(expression, table, resolution) {
+                Some(row_ids) => row_ids.iter().filter_map(|id| table.rows.get(id)).collect(),
+                None => table.rows.values().collect(),
+            },
+            None => table.rows.values().collect(),
+        };
+
+        let is_aggregate = stmt.columns.iter().any(|col| matches!(col, SelectColumn::Aggregate { .. }));
+        // Without anything needing every match, a LIMIT lets the scan stop early.
+        let enough_rows = if is_aggregate || stmt.distinct || !stmt.order_by.is_empty() {
+            None
+        } else {
+            stmt.limit.map(|limit| limit.saturating_add(stmt.offset.unwrap_or(0)))
+        };
+        let max_rows = db.executor_config().max_rows;
+        let mut budget = StepBudget::new(db.executor_config());
+
+        let mut filtered_rows: Vec<Row> = Vec::new();
+        for row in candidates {
+            if enough_rows == Some(filtered_rows.len()) {
+                break;
+            }
+            budget.step()?;
+            let should_include = match &where_clause {
+                Some(expression) => Self::evaluate_expression(expression, row, &table.schema, resolution)?,
+                None => true,
+            };
+            if should_include {
+                if let Some(max_rows) = max_rows
+                    && filtered_rows.len() >= max_rows
+                {
+                    return Err(ExecutionError::TooManyRows { max_rows });
+                }
+                filtered_rows.push(row.clone());
+            }
+        }
+
+        let (columns, mut final_rows) = if let Some(extremes) = indexed_extremes {
+            extremes
+        } else if is_aggregate {
+            Self::aggregate_columns(&filtered_rows, &stmt.columns, &table.schema, resolution)?
+        } else {
+            let nulls_order = db.executor_config().nulls_order;
+            Self::sort_rows(&mut filtered_rows, &stmt.order_by, nulls_order, &table.schema, resolution)?;
+            Self::project_columns(&filtered_rows, &stmt.columns, &table.schema, resolution)?
+        };
+
+        if stmt.distinct {
+            let mut seen = HashSet::new();
+            final_rows.retain(|row| seen.insert(row.values.clone()));
+        }
+
+        let offset = stmt.offset.unwrap_or(0).min(final_rows.len());
+        final_rows.drain(..offset);
+        if let Some(limit) = stmt.limit {
+            final_rows.truncate(limit);
+        }
+
+        Ok(QueryResult { columns: Self::unique_column_names(columns), rows: final_rows, rows_affected: 0 })
+    }
+
+    /// Suffixes repeated output names with the lowest `_n` that clashes with no other
+    /// output name, so the first occurrence keeps its name.
+    fn unique_column_names(names: Vec<String>) -> Vec<String> {
+        let taken: HashSet<String> = names.iter().cloned().collect();
+        if taken.len() == names.len() {
+            return names;
+        }
+
+        // Only this many candidates can clash, so one in range is always free.
+        let max_suffix = taken.len() + names.len();
+        let mut emitted = HashSet::new();
+        names
+            .into_iter()
+            .map(|name| {
+                let unique = if emitted.contains(&name) {
+                    (1..=max_suffix)
+                        .map(|n| format!("{name}_{n}"))
+                        .find(|candidate| !taken.contains(candidate) && !emitted.contains(candidate))
+                        .unwrap_or(name)
+                } else {
+                    name
                 };
+                emitted.insert(unique.clone());
+                unique
+            })
+            .collect()
+    }
+
+    /// Rejects a WHERE clause comparing a column with a literal it can never equal,
+    /// such as `age = 'abc'` on an INTEGER column, before any row is scanned. Without
+    /// this, SELECT would quietly return no rows, even on an empty table.
+    fn check_predicate_types(
+        where_clause: Option<&Expression>,
+        schema: &Schema,
+        resolution: NameResolution,
+    ) -> Result<(), ExecutionError> {
+        // Explicit stack, so deep trees can't overflow here.
+        let mut pending: Vec<&Expression> = where_clause.into_iter().collect();
+        while let Some(expr) = pending.pop() {
+            match expr {
+                Expression::Binary(left, BinaryOperator::And | BinaryOperator::Or, right) => {
+                    pending.push(left);
+                    pending.push(right);
+                }
+                Expression::Not(operand) => pending.push(operand),
+                Expression::IsDistinctFrom(left, right, _) => Self::check_operand_types(left, right, schema, resolution)?,
+                Expression::Between { expr, low, high } => {
+                    Self::check_operand_types(expr, low, schema, resolution)?;
+                    Self::check_operand_types(expr, high, schema, resolution)?;
+                }
+                Expression::Binary(left, op, right) => {
+                    if let Some((name, op, literal)) = Self::column_comparison(left, op, right)
+                        && let Some(index) = schema.resolve_column_index(name, resolution)
+                    {
+                        let column_type = &schema.columns[index].data_type;
+                        let literal_type = Self::value_from_literal(literal).get_data_type();
+                        let like_on_non_string = matches!(op, BinaryOperator::Like | BinaryOperator::NotLike)
+                            && (*column_type != DataType::String || !matches!(literal_type, DataType::String | DataType::Null));
+                        if like_on_non_string || !column_type.is_comparable_with(&literal_type) {
+                            return Err(ExecutionError::TypeMismatch);
+                        }
+                    }
+                }
+                Expression::InList(operand, list, _) => {
+                    let entries: Vec<Value> = list.iter().map(Self::value_from_literal).collect();
+                    let list_type = Self::list_type(&entries)?;
+                    if let Expression::Identifier(name, _) = &**operand
+                        && let Some(index) = schema.resolve_column_index(name, resolution)
+                        && !schema.columns[index].data_type.is_comparable_with(&list_type)
+                    {
+                        return Err(ExecutionError::TypeMismatch);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// `TypeMismatch` if one side is a column and the other a literal it can't be
+    /// compared with.
+    fn check_operand_types(
+        left: &Expression,
+        right: &Expression,
+        schema: &Schema,
+        resolution: NameResolution,
+    ) -> Result<(), ExecutionError> {
+        if let Some((name, _, literal)) = Self::column_comparison(left, &BinaryOperator::Equals, right)
+            && let Some(index) = schema.resolve_column_index(name, resolution)
+            && !schema.columns[index]
+                .data_type
+                .is_comparable_with(&Self::value_from_literal(literal).get_data_type())
+        {
+            return Err(ExecutionError::TypeMismatch);
+        }
+        Ok(())
+    }
+
+    /// Folds the WHERE clause's constant parts. A clause that folds to TRUE is
+    /// dropped; one that folds to FALSE or NULL (unknown) is kept as that literal.
+    fn fold_where_clause(where_clause: Option<&Expression>) -> Option<Expression> {
+        match where_clause.map(Expression::fold_constants) {
+            Some(Expression::Literal(Literal::Boolean(true), _)) => None,
+            folded => folded,
+        }
+    }
+
+    /// Narrows the rows to scan with a range lookup on one indexed column. Every
+    /// top-level AND conjunct comparing an indexed column with a literal of the
+    /// column's type tightens that column's range; other conjuncts are left to the
+    /// full predicate. When several indexed columns are constrained, the one with
+    /// the fewest estimated matches is used. Returns the candidate row ids in id
+    /// order, or `None` to fall back to a full scan. Candidates still go through
+    /// the full predicate, so this never changes which rows match.
+    fn index_range_candidates(
+        expr: &Expression,
+        table: &Table,
+        resolution: NameResolution,
+    ) -> Option<BTreeSet<u64>> {
+        let mut ranges: BTreeMap<usize, (Bound<Value>, Bound<Value>)> = BTreeMap::new();
+
+        // Walk the AND tree with an explicit stack, so deep trees can't overflow here.
+        let mut pending = vec![expr];
+        while let Some(expr) = pending.pop() {
+            let Expression::Binary(left, op, right) = expr else { continue };
+            if *op == BinaryOperator::And {
+                pending.push(left);
+                pending.push(right);
+                continue;
+            }
+
+            let Some((name, op, literal)) = Self::column_comparison(left, op, right) else { continue };
+            let Some(index) = table.schema.resolve_column_index(name, resolution) else { continue };
+            let column = &table.schema.columns[index];
+            let value = Self::value_from_literal(literal);
+            if !table.constraint_state.indexes.contains_key(&column.name) || value.get_data_type() != column.data_type {
+                continue;
+            }
+
+            let (mut lower, mut upper) = ranges.remove(&index).unwrap_or((Bound::Unbounded, Bound::Unbounded));
+            match op {
+                BinaryOperator::Equals => {
+                    lower = Self::tighter_bound(lower, Bound::Included(value.clone()), Ordering::Greater);
+                    upper = Self::tighter_bound(upper, Bound::Included(value), Ordering::Less);
+                }
+                BinaryOperator::GreaterThan => lower = Self::tighter_bound(lower, Bound::Excluded(value), Ordering::Greater),
+                BinaryOperator::GreaterThanOrEquals => lower = Self::tighter_bound(lower, Bound::Included(value), Ordering::Greater),
+                BinaryOperator::LessThan => upper = Self::tighter_bound(upper, Bound::Excluded(value), Ordering::Less),
+                BinaryOperator::LessThanOrEquals => upper = Self::tighter_bound(upper, Bound::Included(value), Ordering::Less),
+                _ => {}
+            }
+            if lower != Bound::Unbounded || upper != Bound::Unbounded {
+                ranges.insert(index, (lower, upper));
+            }
+        }
+
+        let (column, (lower, upper)) = ranges
+            .into_iter()
+            .min_by_key(|(index, (lower, upper))| Self::estimated_matches(table, &table.schema.columns[*index].name, lower, upper))?;
+        let index = table.constraint_state.indexes.get(&table.schema.columns[column].name)?;
+
+        // `BTreeMap::range` panics on an inverted range, which here just means no rows match.
+        let is_empty = match (&lower, &upper) {
+            (Bound::Included(l), Bound::Included(u)) => l > u,
+            (Bound::Included(l) | Bound::Excluded(l), Bound::Included(u) | Bound::Excluded(u)) => l >= u,
+            _ => false,
+        };
+        if is_empty {
+            return Some(BTreeSet::new());
+        }
+
+        Some(index.range((lower, upper)).flat_map(|(_, row_ids)| row_ids.iter().copied()).collect())
+    }
+
+    /// Rough number of rows an indexed range will return. An equality lookup
+    /// assumes values are evenly spread over the column's distinct values; without
+    /// a histogram, a one-sided range is guessed at a third of the rows and a
+    /// two-sided one at a ninth.
+    fn estimated_matches(table: &Table, column: &str, lower: &Bound<Value>, upper: &Bound<Value>) -> usize {
+        let rows = table.row_count();
+        match (lower, upper) {
+            (Bound::Included(l), Bound::Included(u)) if l == u => {
+                rows / table.index_cardinality(column).unwrap_or(1).max(1)
+            }
+            (Bound::Unbounded, _) | (_, Bound::Unbounded) => rows / 3,
+            _ => rows / 9,
+        }
+    }
 
-                if should_include.unwrap_or(false) {
-                    Some(row.clone())
+    /// Picks the narrower of two bounds on the same side of a range: the one whose
+    /// value compares as `narrower` to the other, or the exclusive one on a tie.
+    fn tighter_bound(current: Bound<Value>, new: Bound<Value>, narrower: Ordering) -> Bound<Value> {
+        match (&current, &new) {
+            (Bound::Unbounded, _) => new,
+            (_, Bound::Unbounded) => current,
+            (Bound::Included(c) | Bound::Excluded(c), Bound::Included(n) | Bound::Excluded(n)) => {
+                let ordering = n.cmp(c);
+                if ordering == narrower || (ordering.is_eq() && matches!(new, Bound::Excluded(_))) {
+                    new
                 } else {
-                    None
+                    current
                 }
+            }
+        }
+    }
+
+    /// Sorts rows by the `ORDER BY` keys, falling through to later keys on ties.
+    /// NULLs go first or last in either direction, as the key says or else as
+    /// `default_nulls` says; the sort is stable, so rows equal on every key keep
+    /// their insertion order.
+    fn sort_rows(
+        rows: &mut [Row],
+        order_by: &[(String, SortDirection, Option<NullsOrder>)],
+        default_nulls: NullsOrder,
+        schema: &Schema,
+        resolution: NameResolution,
+    ) -> Result<(), ExecutionError> {
+        let keys = order_by
+            .iter()
+            .map(|(name, direction, nulls)| {
+                schema.resolve_column_index(name, resolution)
+                    .map(|index| (index, *direction, nulls.unwrap_or(default_nulls)))
+                    .ok_or_else(|| ExecutionError::ColumnNotFound(name.clone()))
             })
-            .collect();
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::sort_by_keys(rows, &keys);
+        Ok(())
+    }
+
+    /// Stable sort on `(value index, direction, NULL placement)` keys, in priority order.
+    fn sort_by_keys(rows: &mut [Row], keys: &[(usize, SortDirection, NullsOrder)]) {
+        rows.sort_by(|a, b| {
+            keys.iter()
+                .map(|&(index, direction, nulls)| Self::compare_for_sort(&a.values[index], &b.values[index], direction, nulls))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+    }
 
-        let final_rows = self.project_columns(&filtered_rows, &stmt.columns, &table.schema)?;
-        Ok(QueryResult { rows: final_rows })
+    fn compare_for_sort(left: &Value, right: &Value, direction: SortDirection, nulls: NullsOrder) -> Ordering {
+        let null_first = match nulls {
+            NullsOrder::First => Ordering::Less,
+            NullsOrder::Last => Ordering::Greater,
+        };
+        match (left, right) {
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Null, _) => null_first,
+            (_, Value::Null) => null_first.reverse(),
+            _ if direction == SortDirection::Desc => right.cmp(left),
+            _ => left.cmp(right),
+        }
     }
 
+    /// Evaluates a predicate for row filtering. An unknown result, from comparing
+    /// against NULL, excludes the row just like false.
     fn evaluate_expression(
-        &self,
         expr: &Expression,
         row: &Row,
         schema: &Schema,
+        resolution: NameResolution,
     ) -> Result<bool, ExecutionError> {
+        Ok(Self::evaluate_expression_at_depth(expr, row, schema, resolution, 0)? == Some(true))
+    }
+
+    /// Whether a row satisfies a CHECK predicate: anything but false passes, so a
+    /// NULL operand does not reject the row. A predicate that can't be evaluated
+    /// (say, comparing mismatched types) fails.
+    pub(crate) fn satisfies_check(predicate: &Expression, row: &Row, schema: &Schema) -> bool {
+        matches!(
+            Self::evaluate_expression_at_depth(predicate, row, schema, NameResolution::CaseSensitive, 0),
+            Ok(Some(true) | None)
+        )
+    }
+
+    /// Recursive body of `evaluate_expression`, using SQL three-valued logic: `None`
+    /// is unknown. `AND`/`OR` recurse into their operands as predicates; every other
+    /// binary operator compares two values. ASTs can be built without going
+    /// through the parser, so nesting is bounded here too rather than trusting
+    /// the parser's limit.
+    fn evaluate_expression_at_depth(
+        expr: &Expression,
+        row: &Row,
+        schema: &Schema,
+        resolution: NameResolution,
+        depth: usize,
+    ) -> Result<Option<bool>, ExecutionError> {
+        if depth >= MAX_EXPRESSION_DEPTH {
+            return Err(ExecutionError::ExpressionTooDeep(MAX_EXPRESSION_DEPTH));
+        }
+
         match expr {
+            Expression::Binary(left, BinaryOperator::And, right) => {
+                let left = Self::evaluate_expression_at_depth(left, row, schema, resolution, depth + 1)?;
+                if left == Some(false) {
+                    return Ok(Some(false));
+                }
+                let right = Self::evaluate_expression_at_depth(right, row, schema, resolution, depth + 1)?;
+                Ok(match (left, right) {
+                    (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                })
+            }
+            Expression::Binary(left, BinaryOperator::Or, right) => {
+                let left = Self::evaluate_expression_at_depth(left, row, schema, resolution, depth + 1)?;
+                if left == Some(true) {
+                    return Ok(Some(true));
+                }
+                let right = Self::evaluate_expression_at_depth(right, row, schema, resolution, depth + 1)?;
+                Ok(match (left, right) {
+                    (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                })
+            }
             Expression::Binary(left, op, right) => {
-                let left_val = self.resolve_value(left, row, schema)?;
-                let right_val = self.resolve_value_from_literal(right)?; 
+                let left_val = Self::resolve_value(left, row, schema, resolution)?;
+                let right_val = Self::resolve_value(right, row, schema, resolution)?;
+                Self::compare(&left_val, op, &right_val)
+            }
+            Expression::IsNull(operand, negated) => {
+                let value = Self::resolve_value(operand, row, schema, resolution)?;
+                Ok(Some((*value == Value::Null) != *negated))
+            }
+            Expression::InList(operand, list, negated) => {
+                let value = Self::resolve_value(operand, row, schema, resolution)?;
+                Ok(Self::in_list(&value, list)?.map(|found| found != *negated))
+            }
+            Expression::IsDistinctFrom(left, right, negated) => {
+                let left_val = Self::resolve_value(left, row, schema, resolution)?;
+                let right_val = Self::resolve_value(right, row, schema, resolution)?;
+                Ok(Some(Self::is_distinct(&left_val, &right_val)? != *negated))
+            }
+            Expression::Not(operand) => {
+                Ok(Self::evaluate_expression_at_depth(operand, row, schema, resolution, depth + 1)?.map(|b| !b))
+            }
+            Expression::Between { expr, low, high } => {
+                let value = Self::resolve_value(expr, row, schema, resolution)?;
+                let low = Self::resolve_value(low, row, schema, resolution)?;
+                let high = Self::resolve_value(high, row, schema, resolution)?;
+                // Both comparisons run first so a mismatched bound errors even when the
+                // other already rules the row out.
+                let above_low = Self::compare(&value, &BinaryOperator::GreaterThanOrEquals, &low)?;
+                let below_high = Self::compare(&value, &BinaryOperator::LessThanOrEquals, &high)?;
+                Ok(match (above_low, below_high) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                })
+            }
+            // What a folded constant predicate leaves behind.
+            Expression::Literal(Literal::Boolean(b), _) => Ok(Some(*b)),
+            Expression::Literal(Literal::Null, _) => Ok(None),
+            // A bare identifier or other literal produces a value, not a boolean.
+            _ => Err(ExecutionError::InvalidExpression),
+        }
+    }
+
+    /// NULL-safe inequality: NULL is distinct from every value but itself.
+    fn is_distinct(left: &Value, right: &Value) -> Result<bool, ExecutionError> {
+        match (left, right) {
+            (Value::Null, Value::Null) => Ok(false),
+            (Value::Null, _) | (_, Value::Null) => Ok(true),
+            _ => Ok(Self::compare(left, &BinaryOperator::NotEquals, right)? == Some(true)),
+        }
+    }
+
+    /// `value IN (list)`: true on any match; otherwise unknown if `value` or an entry
+    /// is NULL, else false. So an empty list is always false. The list's non-NULL
+    /// entries must be mutually comparable.
+    fn in_list(value: &Value, list: &[Literal]) -> Result<Option<bool>, ExecutionError> {
+        let entries: Vec<Value> = list.iter().map(Self::value_from_literal).collect();
+        Self::list_type(&entries)?;
+
+        let mut result = Some(false);
+        for entry in &entries {
+            match Self::compare(value, &BinaryOperator::Equals, entry)? {
+                Some(true) => return Ok(Some(true)),
+                Some(false) => {}
+                None => result = None,
+            }
+        }
+        Ok(result)
+    }
+
+    /// The type of an IN list's non-NULL entries (`Null` if there are none), or
+    /// `TypeMismatch` if they can't be compared with each other.
+    fn list_type(entries: &[Value]) -> Result<DataType, ExecutionError> {
+        let mut list_type = DataType::Null;
+        for entry in entries {
+            let entry_type = entry.get_data_type();
+            if !list_type.is_comparable_with(&entry_type) {
+                return Err(ExecutionError::TypeMismatch);
+            }
+            if list_type == DataType::Null {
+                list_type = entry_type;
+            }
+        }
+        Ok(list_type)
+    }
+
+    /// Compares two values, returning `None` (unknown) when either is NULL.
+    fn compare(left: &Value, op: &BinaryOperator, right: &Value) -> Result<Option<bool>, ExecutionError> {
+        if !left.get_data_type().is_comparable_with(&right.get_data_type()) {
+            return Err(ExecutionError::TypeMismatch);
+        }
+        if *left == Value::Null || *right == Value::Null {
+            return Ok(None);
+        }
+        if matches!(op, BinaryOperator::Like | BinaryOperator::NotLike) {
+            return match (left, right) {
+                (Value::String(text), Value::String(pattern)) => {
+                    Ok(Some(Self::like(text, pattern) != (*op == BinaryOperator::NotLike)))
+                }
+                _ => Err(ExecutionError::TypeMismatch),
+            };
+        }
+
+        let ordering = match (Self::numeric_value(left), Self::numeric_value(right)) {
+            // Integer against Float: compare numerically rather than by variant.
+            (Some(l), Some(r)) if left.get_data_type() != right.get_data_type() => l.total_cmp(&r),
+            _ => left.cmp(right),
+        };
+
+        match op {
+            BinaryOperator::Equals => Ok(Some(ordering.is_eq())),
+            BinaryOperator::NotEquals => Ok(Some(ordering.is_ne())),
+            BinaryOperator::GreaterThan => Ok(Some(ordering.is_gt())),
+            BinaryOperator::LessThan => Ok(Some(ordering.is_lt())),
+            BinaryOperator::GreaterThanOrEquals => Ok(Some(ordering.is_ge())),
+            BinaryOperator::LessThanOrEquals => Ok(Some(ordering.is_le())),
+            BinaryOperator::And | BinaryOperator::Or | BinaryOperator::Like | BinaryOperator::NotLike => {
+                Err(ExecutionError::InvalidExpression)
+            }
+        }
+    }
+
+    /// Matches `text` against a LIKE pattern: `%` matches any run of characters
+    /// (including none) and `_` exactly one. A backslash makes the next character
+    /// literal, so `\%` and `\_` match a percent sign and an underscore; a trailing
+    /// backslash matches itself. Matching is case-sensitive.
+    fn like(text: &str, pattern: &str) -> bool {
+        #[derive(PartialEq)]
+        enum Part {
+            AnyRun,
+            AnyChar,
+            Char(char),
+        }
 
-                match op {
-                    BinaryOperator::Equals => Ok(left_val == &right_val),
-                    _ => unimplemented!("Operator not supported yet"),
+        let mut parts = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            parts.push(match c {
+                '%' => Part::AnyRun,
+                '_' => Part::AnyChar,
+                '\\' => Part::Char(chars.next().unwrap_or('\\')),
+                c => Part::Char(c),
+            });
+        }
+
+        // Greedy scan that backtracks to the last `%` on a mismatch, letting it
+        // swallow one more character each time.
+        let text: Vec<char> = text.chars().collect();
+        let (mut t, mut p) = (0, 0);
+        let mut last_run: Option<(usize, usize)> = None; // (pattern index after `%`, text index it resumes from)
+        while t < text.len() {
+            match parts.get(p) {
+                Some(Part::AnyChar) => {
+                    t += 1;
+                    p += 1;
+                }
+                Some(Part::Char(c)) if *c == text[t] => {
+                    t += 1;
+                    p += 1;
+                }
+                Some(Part::AnyRun) => {
+                    p += 1;
+                    last_run = Some((p, t));
                 }
+                _ => match last_run {
+                    Some((after_run, resume)) => {
+                        p = after_run;
+                        t = resume + 1;
+                        last_run = Some((after_run, resume + 1));
+                    }
+                    None => return false,
+                },
             }
-            _ => unimplemented!("Expression type not supported yet"),
         }
+        parts[p..].iter().all(|part| *part == Part::AnyRun)
     }
 
+    #[allow(clippy::cast_precision_loss)]
+    fn numeric_value(value: &Value) -> Option<f64> {
+        match value {
+            Value::Integer(i) => Some(*i as f64),
+            Value::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// The value an operand stands for: a column of `row`, or a literal.
     fn resolve_value<'a>(
-        &self,
         expr: &'a Expression,
         row: &'a Row,
         schema: &Schema,
-    ) -> Result<&'a Value, ExecutionError> {
+        resolution: NameResolution,
+    ) -> Result<Cow<'a, Value>, ExecutionError> {
         match expr {
-            Expression::Identifier(col_name) => {
-                let col_index = schema.get_column_index(col_name)
+            Expression::Identifier(col_name, _) => {
+                let col_index = schema.resolve_column_index(col_name, resolution)
                     .ok_or_else(|| ExecutionError::ColumnNotFound(col_name.clone()))?;
-                Ok(&row.values[col_index])
+                Ok(Cow::Borrowed(&row.values[col_index]))
             }
+            Expression::Literal(lit, _) => Ok(Cow::Owned(Self::value_from_literal(lit))),
             _ => Err(ExecutionError::InvalidExpression),
         }
     }
-    
-    // Helper specifically for the right-hand side of a simple binary expression
-    fn resolve_value_from_literal(&self, expr: &Expression) -> Result<Value, ExecutionError> {
-        match expr {
-            Expression::Literal(lit) => match lit {
-                Literal::Integer(i) => Ok(Value::Integer(*i)),
-                Literal::String(s) => Ok(Value::String(s.clone())),
-                _ => unimplemented!(),
-            },
-            _ => Err(ExecutionError::InvalidExpression),
+
+    /// Splits a comparison between a column and a literal into `(column, op, literal)`,
+    /// turning `30 < age` around into `age > 30`. `None` for any other shape.
+    fn column_comparison<'a>(
+        left: &'a Expression,
+        op: &BinaryOperator,
+        right: &'a Expression,
+    ) -> Option<(&'a str, BinaryOperator, &'a Literal)> {
+        match (left, right) {
+            (Expression::Identifier(name, _), Expression::Literal(literal, _)) => Some((name, op.clone(), literal)),
+            (Expression::Literal(literal, _), Expression::Identifier(name, _)) => {
+                let flipped = match op {
+                    BinaryOperator::GreaterThan => BinaryOperator::LessThan,
+                    BinaryOperator::LessThan => BinaryOperator::GreaterThan,
+                    BinaryOperator::GreaterThanOrEquals => BinaryOperator::LessThanOrEquals,
+                    BinaryOperator::LessThanOrEquals => BinaryOperator::GreaterThanOrEquals,
+                    other => other.clone(),
+                };
+                Some((name, flipped, literal))
+            }
+            _ => None,
+        }
+    }
+
+    fn value_from_literal(literal: &Literal) -> Value {
+        match literal {
+            Literal::Integer(i) => Value::Integer(*i),
+            Literal::Float(f) => Value::Float(*f),
+            Literal::String(s) => Value::String(s.clone()),
+            Literal::Boolean(b) => Value::Boolean(*b),
+            Literal::Null => Value::Null,
         }
     }
     
-    fn project_columns(&self, rows: &[Row], columns: &[SelectColumn], schema: &Schema) -> Result<Vec<Row>, ExecutionError> {
-        if columns.len() == 1 && columns[0] == SelectColumn::Wildcard {
-            return Ok(rows.to_vec()); // Return all columns
+    /// Lists the tables as rows of a single `table_name` column, sorted by name.
+    fn execute_show_tables(db: &Database) -> QueryResult {
+        let rows = db
+            .list_tables()
+            .into_iter()
+            .map(|name| Row { values: vec![Value::String(name.to_string())] })
+            .collect();
+        QueryResult { columns: vec!["table_name".to_string()], rows, rows_affected: 0 }
+    }
+
+    /// One row per column, in schema order: its name, its type, and its
+    /// constraints rendered as SQL in a fixed order (empty when it has none).
+    fn execute_describe(stmt: &DescribeStatement, db: &Database) -> Result<QueryResult, ExecutionError> {
+        let schema = &db.get_table(stmt.table_name.clone()).map_err(|_| ExecutionError::TableNotFound)?.schema;
+        let rows = schema
+            .columns
+            .iter()
+            .map(|column| {
+                let kinds = [
+                    ConstraintKind::NotNull,
+                    ConstraintKind::Unique,
+                    ConstraintKind::Default,
+                    ConstraintKind::Index,
+                    ConstraintKind::AutoIncrement,
+                    ConstraintKind::Check,
+                ];
+                let constraints: Vec<String> = kinds
+                    .into_iter()
+                    .filter_map(|kind| {
+                        let constraint = column.constraints.get(&kind)?;
+                        Some(match (kind, constraint) {
+                            (ConstraintKind::Default, Constraint::WithValue(_, value)) => format!("DEFAULT {}", value.to_sql_literal()),
+                            (ConstraintKind::NotNull, _) => "NOT NULL".to_string(),
+                            (ConstraintKind::Unique, _) => "UNIQUE".to_string(),
+                            (ConstraintKind::Default, _) => "DEFAULT".to_string(),
+                            (ConstraintKind::Index, _) => "INDEX".to_string(),
+                            (ConstraintKind::AutoIncrement, _) => "AUTO_INCREMENT".to_string(),
+                            (ConstraintKind::Check, _) => "CHECK".to_string(),
+                        })
+                    })
+                    .collect();
+                Row {
+                    values: vec![
+                        Value::String(column.name.clone()),
+                        Value::String(column.data_type.to_string()),
+                        Value::String(constraints.join(" ")),
+                    ],
+                }
+            })
+            .collect();
+
+        Ok(QueryResult {
+            columns: vec!["name".to_string(), "data_type".to_string(), "constraints".to_string()],
+            rows,
+            rows_affected: 0,
+        })
+    }
+
+    /// Runs both sides of a UNION and combines their rows under the left side's
+    /// column names. The sides must select the same number of columns, pairwise
+    /// comparable in type; plain UNION then drops duplicate rows.
+    fn execute_set_operation(stmt: &SetOperationStatement, db: &Database) -> Result<QueryResult, ExecutionError> {
+        let left_types = Self::select_column_types(&stmt.left, db)?;
+        let right_types = Self::select_column_types(&stmt.right, db)?;
+        if left_types.len() != right_types.len() {
+            return Err(ExecutionError::UnionColumnCount { left: left_types.len(), right: right_types.len() });
+        }
+        if !left_types.iter().zip(&right_types).all(|(left, right)| left.is_comparable_with(right)) {
+            return Err(ExecutionError::TypeMismatch);
         }
 
-        let mut projected_rows = Vec::new();
-        let mut col_indices = Vec::new();
+        let mut result = Self::execute_select(&stmt.left, db)?;
+        result.rows.extend(Self::execute_select(&stmt.right, db)?.rows);
+        if !stmt.all {
+            let mut seen = HashSet::new();
+            result.rows.retain(|row| seen.insert(row.values.clone()));
+        }
 
-        for col in columns {
-            if let SelectColumn::Identifier(name) = col {
-                let index = schema.get_column_index(name)
-                    .ok_or_else(|| ExecutionError::ColumnNotFound(name.clone()))?;
-                col_indices.push(index);
-            }
+        // ORDER BY names the combined result's columns, which take the left side's names.
+        let resolution = db.name_resolution();
+        let default_nulls = db.executor_config().nulls_order;
+        let keys = stmt
+            .order_by
+            .iter()
+            .map(|(name, direction, nulls)| {
+                result.columns.iter()
+                    .position(|column| match resolution {
+                        NameResolution::CaseSensitive => column == name,
+                        NameResolution::CaseInsensitive => NameResolution::normalize(column) == NameResolution::normalize(name),
+                    })
+                    .map(|index| (index, *direction, nulls.unwrap_or(default_nulls)))
+                    .ok_or_else(|| ExecutionError::ColumnNotFound(name.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::sort_by_keys(&mut result.rows, &keys);
+
+        let offset = stmt.offset.unwrap_or(0).min(result.rows.len());
+        result.rows.drain(..offset);
+        if let Some(limit) = stmt.limit {
+            result.rows.truncate(limit);
         }
+        Ok(result)
+    }
 
-        for row in rows {
-            let projected_values = col_indices.iter().map(|&i| row.values[i].clone()).collect();
-            projected_rows.push(Row { values: projected_values });
+    /// The type of each column a SELECT produces, worked out from its table's schema.
+    fn select_column_types(stmt: &SelectStatement, db: &Database) -> Result<Vec<DataType>, ExecutionError> {
+        let schema = &db.get_table(stmt.from_table.clone()).map_err(|_| ExecutionError::TableNotFound)?.schema;
+        let resolution = db.name_resolution();
+        let column_type = |name: &String| {
+            schema
+                .resolve_column_index(name, resolution)
+                .map(|index| schema.columns[index].data_type.clone())
+                .ok_or_else(|| ExecutionError::ColumnNotFound(name.clone()))
+        };
+
+        let mut types = Vec::new();
+        for col in &stmt.columns {
+            match col {
+                SelectColumn::Wildcard => types.extend(schema.columns.iter().map(|column| column.data_type.clone())),
+                SelectColumn::Aggregate { func: AggregateFunc::Count, .. } => types.push(DataType::Integer),
+                SelectColumn::Aggregate { func: AggregateFunc::Avg, .. } => types.push(DataType::Float),
+                SelectColumn::Identifier(name) | SelectColumn::Aggregate { arg: Some(name), .. } => types.push(column_type(name)?),
+                SelectColumn::Aggregate { arg: None, .. } => return Err(ExecutionError::InvalidExpression),
+            }
         }
-        
-        Ok(projected_rows)
+        Ok(types)
     }
-}
 
+    /// Folds the rows into a single result row, one value per aggregate. Without
+    /// GROUP BY, plain columns can't be mixed with aggregates.
+    fn aggregate_columns(
+        rows: &[Row],
+        columns: &[SelectColumn],
+        schema: &Schema,
+        resolution: NameResolution,
+    ) -> Result<(Vec<String>, Vec<Row>), ExecutionError> {
+        let mut names = Vec::new();
+        let mut values = Vec::new();
 
-// ==============================================================================
+        for col in columns {
+            let SelectColumn::Aggregate { func, arg } = col else {
+                return Err(ExecutionError::InvalidExpression);
+            };
+            let Some(arg) = arg else {
+                names.push(format!("{func}(*)"));
+                values.push(Value::Integer(i64::try_from(rows.len()).unwrap_or(i64::MAX)));
+                continue;
+            };
+
+            let index = schema.resolve_column_index(arg, resolution)
+                .ok_or_else(|| ExecutionError::ColumnNotFound(arg.clone()))?;
+            let column = &schema.columns[index];
+            names.push(format!("{func}({})", column.name));
+
+            let non_null = rows.iter().map(|row| &row.values[index]).filter(|value| **value != Value::Null);
+            values.push(Self::aggregate(*func, &column.data_type, non_null)?);
+        }
+
+        Ok((names, vec![Row { values }]))
+    }
+
+    /// Answers a select list made only of `MIN`/`MAX` over indexed columns from the
+    /// ends of their indexes, without touching the rows. Only valid with no WHERE
+    /// clause; `None` if any column needs a scan.
+    fn indexed_extremes(
+        columns: &[SelectColumn],
+        table: &Table,
+        resolution: NameResolution,
+    ) -> Option<(Vec<String>, Vec<Row>)> {
+        let mut names = Vec::new();
+        let mut values = Vec::new();
+
+        for col in columns {
+            let SelectColumn::Aggregate { func: func @ (AggregateFunc::Min | AggregateFunc::Max), arg: Some(arg) } = col else {
+                return None;
+            };
+            let column = &table.schema.columns[table.schema.resolve_column_index(arg, resolution)?];
+            let stats = table.column_stats(&column.name)?;
+            names.push(format!("{func}({})", column.name));
+            values.push(match func {
+                AggregateFunc::Min => stats.min,
+                _ => stats.max,
+            }.unwrap_or(Value::Null));
+        }
+
+        (!columns.is_empty()).then(|| (names, vec![Row { values }]))
+    }
+
+    /// Applies one aggregate to a column's non-null values. Apart from `COUNT`, an
+    /// empty input gives `Null`.
+    #[allow(clippy::cast_precision_loss)]
+    fn aggregate<'a>(
+        func: AggregateFunc,
+        data_type: &DataType,
+        values: impl Iterator<Item = &'a Value>,
+    ) -> Result<Value, ExecutionError> {
+        if matches!(func, AggregateFunc::Sum | AggregateFunc::Avg) && !data_type.is_numeric() {
+            return Err(ExecutionError::TypeMismatch);
+        }
+
+        match func {
+            AggregateFunc::Count => Ok(Value::Integer(i64::try_from(values.count()).unwrap_or(i64::MAX))),
+            AggregateFunc::Min => Ok(values.min().cloned().unwrap_or(Value::Null)),
+            AggregateFunc::Max => Ok(values.max().cloned().unwrap_or(Value::Null)),
+            AggregateFunc::Sum if *data_type == DataType::Integer => {
+                let mut sum: Option<i64> = None;
+                for value in values {
+                    if let Value::Integer(i) = value {
+                        sum = Some(sum.unwrap_or(0).checked_add(*i).ok_or(ExecutionError::NumericOverflow)?);
+                    }
+                }
+                Ok(sum.map_or(Value::Null, Value::Integer))
+            }
+            AggregateFunc::Sum | AggregateFunc::Avg => {
+                let numbers: Vec<f64> = values.filter_map(Self::numeric_value).collect();
+                if numbers.is_empty() {
+                    return Ok(Value::Null);
+                }
+                let sum: f64 = numbers.iter().sum();
+                if func == AggregateFunc::Sum {
+                    Ok(Value::Float(sum))
+                } else {
+                    Ok(Value::Float(sum / numbers.len() as f64))
+                }
+            }
+        }
+    }
+
+    fn project_columns(
+        rows: &[Row],
+        columns: &[SelectColumn],
+        schema: &Schema,
+        resolution: NameResolution,
+    ) -> Result<(Vec<String>, Vec<Row>), ExecutionError> {
+        if columns.len() == 1 && columns[0] == SelectColumn::Wildcard {
+            let names = schema.columns.iter().map(|col| col.name.clone()).collect();
+            return Ok((names, rows.to_vec())); // Return all columns
+        }
+
+        let mut projected_rows = Vec::new();
+        let mut names = Vec::new();
+        let mut col_indices = Vec::new();
+
+        for col in columns {
+            if let SelectColumn::Identifier(name) = col {
+                let index = schema.resolve_column_index(name, resolution)
+                    .ok_or_else(|| ExecutionError::ColumnNotFound(name.clone()))?;
+                names.push(schema.columns[index].name.clone());
+                col_indices.push(index);
+            }
+        }
+
+        for row in rows {
+            let projected_values = col_indices.iter().map(|&i| row.values[i].clone()).collect();
+            projected_rows.push(Row { values: projected_values });
+        }
+        
+        Ok((names, projected_rows))
+    }
+}
+
+impl Expression {
+    /// Simplifies the parts of a predicate that don't depend on the row: a comparison
+    /// between two literals becomes a boolean literal, and `AND`/`OR` with a boolean
+    /// literal operand collapse (`TRUE AND x` is `x`, `FALSE AND x` is `FALSE`).
+    /// Comparisons that would fail, e.g. between mismatched types, are left for
+    /// evaluation to report. Nesting past `MAX_EXPRESSION_DEPTH` is left unfolded.
+    #[must_use]
+    pub fn fold_constants(&self) -> Expression {
+        self.fold_constants_at_depth(0)
+    }
+
+    fn fold_constants_at_depth(&self, depth: usize) -> Expression {
+        if depth >= MAX_EXPRESSION_DEPTH {
+            return self.clone();
+        }
+        if let Expression::Not(operand) = self {
+            return match operand.fold_constants_at_depth(depth + 1) {
+                Expression::Literal(Literal::Boolean(b), _) => Expression::Literal(Literal::Boolean(!b), self.span()),
+                Expression::Literal(Literal::Null, _) => Expression::Literal(Literal::Null, self.span()),
+                folded => Expression::Not(Box::new(folded)),
+            };
+        }
+        let Expression::Binary(left, op, right) = self else { return self.clone() };
+
+        let left = left.fold_constants_at_depth(depth + 1);
+        let right = right.fold_constants_at_depth(depth + 1);
+        let boolean = |expr: &Expression| match expr {
+            Expression::Literal(Literal::Boolean(b), _) => Some(*b),
+            _ => None,
+        };
+
+        match (op, boolean(&left), boolean(&right)) {
+            (BinaryOperator::And, Some(false), _) | (BinaryOperator::And, _, Some(false)) => {
+                Expression::Literal(Literal::Boolean(false), self.span())
+            }
+            (BinaryOperator::Or, Some(true), _) | (BinaryOperator::Or, _, Some(true)) => {
+                Expression::Literal(Literal::Boolean(true), self.span())
+            }
+            (BinaryOperator::And, Some(true), _) | (BinaryOperator::Or, Some(false), _) => right,
+            (BinaryOperator::And, _, Some(true)) | (BinaryOperator::Or, _, Some(false)) => left,
+            _ => {
+                if let (Expression::Literal(l, _), Expression::Literal(r, _)) = (&left, &right)
+                    && let Ok(result) = Executor::compare(&Executor::value_from_literal(l), op, &Executor::value_from_literal(r))
+                {
+                    return Expression::Literal(result.map_or(Literal::Null, Literal::Boolean), self.span());
+                }
+                Expression::Binary(Box::new(left), op.clone(), Box::new(right))
+            }
+        }
+    }
+
+    /// Whether any part of the expression names the column `name`, matched exactly.
+    #[must_use]
+    pub fn references_column(&self, name: &str) -> bool {
+        self.column_references().contains(&name)
+    }
+
+    /// Every column name the expression mentions, in any branch.
+    #[must_use]
+    pub fn referenced_columns(&self) -> HashSet<String> {
+        self.column_references().into_iter().map(str::to_string).collect()
+    }
+
+    /// Source range of the expression, from its first column or literal to its
+    /// last. `None` unless it was parsed with `Parser::from_spanned`.
+    #[must_use]
+    pub fn span(&self) -> Option<Span> {
+        let spans: Vec<Span> = self.leaves().into_iter().filter_map(|leaf| match leaf {
+            Expression::Identifier(_, span) | Expression::Literal(_, span) => *span,
+            _ => None,
+        }).collect();
+        let (first, last) = (spans.first()?, spans.last()?);
+        Some(Span { start: first.start, end: last.end })
+    }
+
+    /// Column names in the order they appear, repeats included.
+    pub(crate) fn column_references(&self) -> Vec<&str> {
+        self.leaves().into_iter().filter_map(|leaf| match leaf {
+            Expression::Identifier(name, _) => Some(name.as_str()),
+            _ => None,
+        }).collect()
+    }
+
+    /// The identifiers and literals of the expression, in source order.
+    fn leaves(&self) -> Vec<&Expression> {
+        let mut leaves = Vec::new();
+        // Explicit stack, so deep trees can't overflow here. Children are pushed
+        // right to left so they pop in source order.
+        let mut pending = vec![self];
+        while let Some(expr) = pending.pop() {
+            match expr {
+                Expression::Binary(left, _, right) | Expression::IsDistinctFrom(left, right, _) => {
+                    pending.extend([&**right, &**left]);
+                }
+                Expression::IsNull(operand, _) | Expression::InList(operand, ..) | Expression::Not(operand) => {
+                    pending.push(operand);
+                }
+                Expression::Between { expr, low, high } => pending.extend([&**high, &**low, &**expr]),
+                Expression::Identifier(..) | Expression::Literal(..) => leaves.push(expr),
+            }
+        }
+        leaves
+    }
+}
+
+// ==============================================================================
+// EXPORT
+// ==============================================================================
+#[cfg(feature = "serde")]
+impl QueryResult {
+    /// Renders the result as JSON lines: one object per row, keyed by column name,
+    /// with the keys in column order.
+    pub fn to_json_lines(&self) -> String {
+        let mut output = String::new();
+        for row in &self.rows {
+            let object: serde_json::Map<String, serde_json::Value> = self
+                .columns
+                .iter()
+                .cloned()
+                .zip(row.values.iter().map(Value::to_json_value))
+                .collect();
+            output.push_str(&serde_json::Value::Object(object).to_string());
+            output.push('\n');
+        }
+        output
+    }
+}
+
+
+// ==============================================================================
 // TESTS
 // ==============================================================================
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::ColumnDefinition;
     use crate::column::{ColumnBuilder, DataType};
-    use crate::database::Database;
+    use crate::database::{Database, DatabaseBuilder};
     use crate::row::{Value};
     use crate::schema::Schema;
 
     // ===== Test Setup =====
-    fn create_mock_db() -> Database {
-        let schema = Schema::new(vec![
+    fn users_schema() -> Schema {
+        Schema::new(vec![
             ColumnBuilder::new("id", DataType::Integer).build(),
             ColumnBuilder::new("name", DataType::String).build(),
             ColumnBuilder::new("age", DataType::Integer).build(),
         ])
-        .unwrap();
-
-        let mut db = Database::new();
-        db.create_table("users".to_string(), schema).unwrap();
-        let table = db.get_table_mut("users".to_string()).unwrap();
+        .unwrap()
+    }
 
-        table
-            .add_row(vec![
-                Value::Integer(1),
-                Value::String("Alice".to_string()),
-                Value::Integer(30),
-            ])
-            .unwrap();
-        table
-            .add_row(vec![
-                Value::Integer(2),
-                Value::String("Bob".to_string()),
-                Value::Integer(25),
-            ])
-            .unwrap();
-        table
-            .add_row(vec![
-                Value::Integer(3),
-                Value::String("Charlie".to_string()),
-                Value::Integer(30),
-            ])
-            .unwrap();
-        db
+    fn create_mock_db() -> Database {
+        DatabaseBuilder::new()
+            .table("users", users_schema())
+            .rows(vec![user(1, "Alice", 30), user(2, "Bob", 25), user(3, "Charlie", 30)])
+            .build()
+            .unwrap()
     }
     
     #[test]
     fn test_select_all_no_where() {
-        let db = create_mock_db();
+        let mut db = create_mock_db();
         let executor = Executor {};
         let ast = Statements::Select(SelectStatement {
             from_table: "users".to_string(),
             columns: vec![SelectColumn::Wildcard],
             where_clause: None,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
         });
 
-        let result = executor.execute(&ast, &db).unwrap();
+        let result = executor.execute(&ast, &mut db).unwrap();
         // Should return all 3 rows
         assert_eq!(result.rows.len(), 3);
     }
     
     #[test]
     fn test_select_with_integer_where_clause() {
-        let db = create_mock_db();
+        let mut db = create_mock_db();
         let executor = Executor {};
         let ast = Statements::Select(SelectStatement {
             from_table: "users".to_string(),
             columns: vec![SelectColumn::Wildcard],
             where_clause: Some(Expression::Binary(
-                Box::new(Expression::Identifier("id".to_string())),
+                Box::new(Expression::Identifier("id".to_string(), None)),
                 BinaryOperator::Equals,
-                Box::new(Expression::Literal(Literal::Integer(2))),
+                Box::new(Expression::Literal(Literal::Integer(2), None)),
             )),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
         });
         
-        let result = executor.execute(&ast, &db).unwrap();
+        let result = executor.execute(&ast, &mut db).unwrap();
 
         assert_eq!(result.rows.len(), 1);
         assert_eq!(result.rows[0].values[1], Value::String("Bob".to_string()));
@@ -219,19 +1546,23 @@ mod tests {
     
     #[test]
     fn test_select_with_string_where_clause() {
-        let db = create_mock_db();
+        let mut db = create_mock_db();
         let executor = Executor {};
         let ast = Statements::Select(SelectStatement {
             from_table: "users".to_string(),
             columns: vec![SelectColumn::Wildcard],
             where_clause: Some(Expression::Binary(
-                Box::new(Expression::Identifier("name".to_string())),
+                Box::new(Expression::Identifier("name".to_string(), None)),
                 BinaryOperator::Equals,
-                Box::new(Expression::Literal(Literal::String("Charlie".to_string()))),
+                Box::new(Expression::Literal(Literal::String("Charlie".to_string()), None)),
             )),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
         });
         
-        let result = executor.execute(&ast, &db).unwrap();
+        let result = executor.execute(&ast, &mut db).unwrap();
         
         // Should return only Charlie's row
         assert_eq!(result.rows.len(), 1);
@@ -240,7 +1571,7 @@ mod tests {
     
     #[test]
     fn test_select_with_projection() { // Projection is selecting a subset of rows.
-        let db = create_mock_db();
+        let mut db = create_mock_db();
         let executor = Executor {};
 
         let ast = Statements::Select(SelectStatement {
@@ -250,13 +1581,17 @@ mod tests {
                 SelectColumn::Identifier("age".to_string()),
             ],
             where_clause: Some(Expression::Binary(
-                Box::new(Expression::Identifier("age".to_string())),
+                Box::new(Expression::Identifier("age".to_string(), None)),
                 BinaryOperator::Equals,
-                Box::new(Expression::Literal(Literal::Integer(30))),
+                Box::new(Expression::Literal(Literal::Integer(30), None)),
             )),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
         });
 
-        let result = executor.execute(&ast, &db).unwrap();
+        let result = executor.execute(&ast, &mut db).unwrap();
         
         // Should return 2 rows (Alice and Charlie)
         assert_eq!(result.rows.len(), 2);
@@ -267,4 +1602,1792 @@ mod tests {
         assert_eq!(result.rows[0].values, vec![Value::String("Alice".to_string()), Value::Integer(30)]);
         assert_eq!(result.rows[1].values, vec![Value::String("Charlie".to_string()), Value::Integer(30)]);
     }
+
+    fn comparison(column: &str, op: BinaryOperator, literal: Literal) -> Expression {
+        Expression::Binary(
+            Box::new(Expression::Identifier(column.to_string(), None)),
+            op,
+            Box::new(Expression::Literal(literal, None)),
+        )
+    }
+
+    fn select_ids(db: &mut Database, where_clause: Expression) -> Vec<Value> {
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![SelectColumn::Identifier("id".to_string())],
+            where_clause: Some(where_clause),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+        let mut ids: Vec<Value> = Executor {}
+            .execute(&ast, db)
+            .unwrap()
+            .rows
+            .into_iter()
+            .map(|row| row.values[0].clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_select_with_and_clause() {
+        let mut db = create_mock_db();
+        let where_clause = Expression::Binary(
+            Box::new(comparison("age", BinaryOperator::GreaterThan, Literal::Integer(20))),
+            BinaryOperator::And,
+            Box::new(comparison("name", BinaryOperator::Equals, Literal::String("Bob".to_string()))),
+        );
+
+        assert_eq!(select_ids(&mut db, where_clause), vec![Value::Integer(2)]);
+    }
+
+    #[test]
+    fn test_select_with_or_clause() {
+        let mut db = create_mock_db();
+        let where_clause = Expression::Binary(
+            Box::new(comparison("age", BinaryOperator::LessThan, Literal::Integer(30))),
+            BinaryOperator::Or,
+            Box::new(comparison("name", BinaryOperator::Equals, Literal::String("Charlie".to_string()))),
+        );
+
+        assert_eq!(select_ids(&mut db, where_clause), vec![Value::Integer(2), Value::Integer(3)]);
+    }
+
+    #[test]
+    fn test_select_with_nested_and_or_clause() {
+        let mut db = create_mock_db();
+        // (age = 30 AND id != 1) OR name = 'Bob'
+        let where_clause = Expression::Binary(
+            Box::new(Expression::Binary(
+                Box::new(comparison("age", BinaryOperator::Equals, Literal::Integer(30))),
+                BinaryOperator::And,
+                Box::new(comparison("id", BinaryOperator::NotEquals, Literal::Integer(1))),
+            )),
+            BinaryOperator::Or,
+            Box::new(comparison("name", BinaryOperator::Equals, Literal::String("Bob".to_string()))),
+        );
+
+        assert_eq!(select_ids(&mut db, where_clause), vec![Value::Integer(2), Value::Integer(3)]);
+    }
+
+    #[test]
+    fn test_select_returns_rows_in_insertion_order() {
+        let mut db = Database::new();
+        db.create_table("users".to_string(), users_schema()).unwrap();
+        let table = db.get_table_mut("users".to_string()).unwrap();
+        for (id, name) in [(5, "Eve"), (1, "Alice"), (4, "Dan"), (2, "Bob"), (3, "Charlie")] {
+            table.add_row(vec![Value::Integer(id), Value::String(name.to_string()), Value::Integer(20)]).unwrap();
+        }
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![SelectColumn::Identifier("id".to_string())],
+            where_clause: None,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        for _ in 0..3 {
+            let ids: Vec<Value> = Executor {}
+                .execute(&ast, &mut db)
+                .unwrap()
+                .rows
+                .into_iter()
+                .map(|row| row.values[0].clone())
+                .collect();
+            assert_eq!(ids, [5, 1, 4, 2, 3].map(Value::Integer).to_vec());
+        }
+    }
+
+    #[test]
+    fn test_create_table() {
+        let mut db = Database::new();
+        let ast = Statements::CreateTable(CreateTableStatement {
+            table_name: "users".to_string(),
+            columns: vec![
+                ColumnDefinition { name: "id".to_string(), data_type: DataType::Integer, constraints: vec![], default: None, check: None },
+                ColumnDefinition { name: "name".to_string(), data_type: DataType::String, constraints: vec![], default: None, check: None },
+                ColumnDefinition { name: "age".to_string(), data_type: DataType::Integer, constraints: vec![], default: None, check: None },
+            ],
+        });
+
+        Executor {}.execute(&ast, &mut db).unwrap();
+        assert_eq!(db.get_table("users".to_string()).unwrap().schema, users_schema());
+
+        assert!(matches!(
+            Executor {}.execute(&ast, &mut db),
+            Err(ExecutionError::TableAlreadyExists(name)) if name == "users"
+        ));
+    }
+
+    #[test]
+    fn test_select_with_float_where_clause() {
+        let mut db = Database::new();
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("name", DataType::String).build(),
+            ColumnBuilder::new("price", DataType::Float).build(),
+        ])
+        .unwrap();
+        db.create_table("products".to_string(), schema).unwrap();
+        let table = db.get_table_mut("products".to_string()).unwrap();
+        for (name, price) in [("pen", 1.25), ("book", 12.5), ("mug", 7.0)] {
+            table.add_row(vec![Value::String(name.to_string()), Value::Float(price)]).unwrap();
+        }
+        let ast = Statements::Select(SelectStatement {
+            from_table: "products".to_string(),
+            columns: vec![SelectColumn::Identifier("name".to_string())],
+            where_clause: Some(comparison("price", BinaryOperator::LessThan, Literal::Float(10.0))),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        let result = Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(result.rows, vec![
+            Row { values: vec![Value::String("pen".to_string())] },
+            Row { values: vec![Value::String("mug".to_string())] },
+        ]);
+    }
+
+    #[test]
+    fn test_select_with_boolean_where_clause() {
+        let mut db = Database::new();
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("name", DataType::String).build(),
+            ColumnBuilder::new("active", DataType::Boolean).build(),
+        ])
+        .unwrap();
+        db.create_table("users".to_string(), schema).unwrap();
+        let table = db.get_table_mut("users".to_string()).unwrap();
+        for (name, active) in [("Alice", true), ("Bob", false), ("Charlie", true)] {
+            table.add_row(vec![Value::String(name.to_string()), Value::Boolean(active)]).unwrap();
+        }
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![SelectColumn::Identifier("name".to_string())],
+            where_clause: Some(comparison("active", BinaryOperator::Equals, Literal::Boolean(false))),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        let result = Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(result.rows, vec![Row { values: vec![Value::String("Bob".to_string())] }]);
+    }
+
+    #[test]
+    fn test_query_result_lookup_by_column_name() {
+        let mut db = create_mock_db();
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![
+                SelectColumn::Identifier("name".to_string()),
+                SelectColumn::Identifier("age".to_string()),
+            ],
+            where_clause: None,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        let result = Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(result.column_index("age"), Some(1));
+        assert_eq!(result.column_index("id"), None);
+        assert_eq!(result.get(1, "name"), Some(&Value::String("Bob".to_string())));
+        assert_eq!(result.get(2, "age"), Some(&Value::Integer(30)));
+        assert_eq!(result.get(3, "age"), None);
+        assert_eq!(result.get(0, "missing"), None);
+    }
+
+    #[test]
+    fn test_select_with_mixed_case_names() {
+        let executor = Executor {};
+        let ast = Statements::Select(SelectStatement {
+            from_table: "USERS".to_string(),
+            columns: vec![SelectColumn::Identifier("Name".to_string())],
+            where_clause: Some(Expression::Binary(
+                Box::new(Expression::Identifier("ID".to_string(), None)),
+                BinaryOperator::Equals,
+                Box::new(Expression::Literal(Literal::Integer(2), None)),
+            )),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        // Case-sensitive by default, so the table isn't found.
+        let mut db = create_mock_db();
+        assert!(matches!(executor.execute(&ast, &mut db), Err(ExecutionError::TableNotFound)));
+
+        let mut db = Database::with_name_resolution(NameResolution::CaseInsensitive);
+        db.create_table("users".to_string(), users_schema()).unwrap();
+        db.get_table_mut("Users".to_string())
+            .unwrap()
+            .add_row(vec![Value::Integer(2), Value::String("Bob".to_string()), Value::Integer(25)])
+            .unwrap();
+
+        let result = executor.execute(&ast, &mut db).unwrap();
+        assert_eq!(result.columns, vec!["name".to_string()]);
+        assert_eq!(result.rows, vec![Row { values: vec![Value::String("Bob".to_string())] }]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_query_result_to_json_lines() {
+        let mut db = create_mock_db();
+        db.get_table_mut("users".to_string())
+            .unwrap()
+            .add_row(vec![Value::Integer(4), Value::Null, Value::Integer(41)])
+            .unwrap();
+        let executor = Executor {};
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![
+                SelectColumn::Identifier("name".to_string()),
+                SelectColumn::Identifier("id".to_string()),
+            ],
+            where_clause: Some(Expression::Binary(
+                Box::new(Expression::Identifier("id".to_string(), None)),
+                BinaryOperator::Equals,
+                Box::new(Expression::Literal(Literal::Integer(4), None)),
+            )),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+        let result = executor.execute(&ast, &mut db).unwrap();
+        // Keys follow the selected columns, not alphabetical order.
+        assert_eq!(result.to_json_lines(), "{\"name\":null,\"id\":4}\n");
+
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![SelectColumn::Wildcard],
+            where_clause: Some(Expression::Binary(
+                Box::new(Expression::Identifier("name".to_string(), None)),
+                BinaryOperator::Equals,
+                Box::new(Expression::Literal(Literal::String("Bob".to_string()), None)),
+            )),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+        let result = executor.execute(&ast, &mut db).unwrap();
+        assert_eq!(result.to_json_lines(), "{\"id\":2,\"name\":\"Bob\",\"age\":25}\n");
+    }
+
+    fn delete_from_users(where_clause: Option<Expression>) -> Statements {
+        Statements::Delete(DeleteStatement { table_name: "users".to_string(), where_clause })
+    }
+
+    #[test]
+    fn test_delete_all_rows() {
+        let mut db = create_mock_db();
+
+        let result = Executor {}.execute(&delete_from_users(None), &mut db).unwrap();
+
+        assert_eq!(result.rows_affected, 3);
+        assert!(db.get_table("users".to_string()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delete_with_where_clause() {
+        let mut db = create_mock_db();
+        let ast = delete_from_users(Some(comparison("age", BinaryOperator::Equals, Literal::Integer(30))));
+
+        let result = Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(result.rows_affected, 2);
+        let where_clause = comparison("id", BinaryOperator::GreaterThan, Literal::Integer(0));
+        assert_eq!(select_ids(&mut db, where_clause), vec![Value::Integer(2)]);
+    }
+
+    #[test]
+    fn test_delete_with_invalid_where_clause_deletes_nothing() {
+        let mut db = create_mock_db();
+        let ast = delete_from_users(Some(comparison("age", BinaryOperator::Equals, Literal::String("thirty".to_string()))));
+
+        assert!(matches!(Executor {}.execute(&ast, &mut db), Err(ExecutionError::TypeMismatch)));
+        assert_eq!(db.get_table("users".to_string()).unwrap().row_count(), 3);
+    }
+
+    #[test]
+    fn test_delete_releases_unique_values() {
+        let mut db = Database::new();
+        let schema = Schema::new(vec![ColumnBuilder::new("email", DataType::String).unique().build()]).unwrap();
+        db.create_table("users".to_string(), schema).unwrap();
+        let email = vec![Value::String("a@example.com".to_string())];
+        db.insert_row("users".to_string(), email.clone()).unwrap();
+
+        Executor {}.execute(&delete_from_users(None), &mut db).unwrap();
+
+        assert!(db.insert_row("users".to_string(), email).is_ok());
+    }
+
+    fn nested_and_clause(depth: usize) -> Expression {
+        let mut expression = comparison("id", BinaryOperator::Equals, Literal::Integer(1));
+        for _ in 0..depth {
+            expression = Expression::Binary(
+                Box::new(expression),
+                BinaryOperator::And,
+                Box::new(comparison("age", BinaryOperator::Equals, Literal::Integer(30))),
+            );
+        }
+        expression
+    }
+
+    #[test]
+    fn test_expression_too_deep() {
+        let mut db = create_mock_db();
+        assert_eq!(select_ids(&mut db, nested_and_clause(MAX_EXPRESSION_DEPTH - 1)), vec![Value::Integer(1)]);
+
+        let too_deep = select_users_where(nested_and_clause(MAX_EXPRESSION_DEPTH * 4), None, Vec::new());
+        assert!(matches!(
+            Executor {}.execute(&too_deep, &mut db),
+            Err(ExecutionError::ExpressionTooDeep(MAX_EXPRESSION_DEPTH))
+        ));
+    }
+
+    fn update_users(assignments: Vec<(&str, Literal)>, where_clause: Option<Expression>) -> Statements {
+        Statements::Update(UpdateStatement {
+            table_name: "users".to_string(),
+            assignments: assignments.into_iter().map(|(column, literal)| (column.to_string(), literal)).collect(),
+            where_clause,
+        })
+    }
+
+    fn user_rows(db: &Database) -> Vec<Vec<Value>> {
+        db.get_table("users".to_string()).unwrap().rows.values().map(|row| row.values.clone()).collect()
+    }
+
+    fn user(id: i64, name: &str, age: i64) -> Vec<Value> {
+        vec![Value::Integer(id), Value::String(name.to_string()), Value::Integer(age)]
+    }
+
+    #[test]
+    fn test_update_single_column_with_where_clause() {
+        let mut db = create_mock_db();
+        let ast = update_users(
+            vec![("age", Literal::Integer(26))],
+            Some(comparison("name", BinaryOperator::Equals, Literal::String("Bob".to_string()))),
+        );
+
+        let result = Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(result.rows_affected, 1);
+        assert_eq!(user_rows(&db), vec![user(1, "Alice", 30), user(2, "Bob", 26), user(3, "Charlie", 30)]);
+    }
+
+    #[test]
+    fn test_update_multiple_columns_with_where_clause() {
+        let mut db = create_mock_db();
+        let ast = update_users(
+            vec![("name", Literal::String("Carl".to_string())), ("age", Literal::Integer(31))],
+            Some(comparison("id", BinaryOperator::Equals, Literal::Integer(3))),
+        );
+
+        Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(user_rows(&db), vec![user(1, "Alice", 30), user(2, "Bob", 25), user(3, "Carl", 31)]);
+    }
+
+    #[test]
+    fn test_update_without_where_clause() {
+        let mut db = create_mock_db();
+        let ast = update_users(vec![("age", Literal::Integer(40))], None);
+
+        let result = Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(result.rows_affected, 3);
+        assert_eq!(user_rows(&db), vec![user(1, "Alice", 40), user(2, "Bob", 40), user(3, "Charlie", 40)]);
+    }
+
+    #[test]
+    fn test_update_multiple_columns_without_where_clause() {
+        let mut db = create_mock_db();
+        let ast = update_users(vec![("name", Literal::String("Anon".to_string())), ("age", Literal::Integer(0))], None);
+
+        Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(user_rows(&db), vec![user(1, "Anon", 0), user(2, "Anon", 0), user(3, "Anon", 0)]);
+    }
+
+    #[test]
+    fn test_update_rejected_row_leaves_table_unchanged() {
+        let mut db = Database::new();
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).unique().build(),
+            ColumnBuilder::new("name", DataType::String).build(),
+            ColumnBuilder::new("age", DataType::Integer).build(),
+        ])
+        .unwrap();
+        db.create_table("users".to_string(), schema).unwrap();
+        for row in [user(1, "Alice", 30), user(2, "Bob", 25)] {
+            db.insert_row("users".to_string(), row).unwrap();
+        }
+
+        // The second row can't take id 7 once the first has it.
+        let duplicate = update_users(vec![("id", Literal::Integer(7))], None);
+        assert!(matches!(Executor {}.execute(&duplicate, &mut db), Err(ExecutionError::InvalidRow(_))));
+        let wrong_type = update_users(vec![("age", Literal::String("old".to_string()))], None);
+        assert!(matches!(Executor {}.execute(&wrong_type, &mut db), Err(ExecutionError::InvalidRow(_))));
+        assert_eq!(user_rows(&db), vec![user(1, "Alice", 30), user(2, "Bob", 25)]);
+
+        // The constraint state was restored along with the rows.
+        let swap = update_users(vec![("id", Literal::Integer(7))], Some(comparison("id", BinaryOperator::Equals, Literal::Integer(1))));
+        Executor {}.execute(&swap, &mut db).unwrap();
+        assert_eq!(user_rows(&db), vec![user(7, "Alice", 30), user(2, "Bob", 25)]);
+    }
+
+    #[test]
+    fn test_update_unknown_column() {
+        let mut db = create_mock_db();
+        let ast = update_users(vec![("email", Literal::String("x".to_string()))], None);
+
+        assert!(matches!(Executor {}.execute(&ast, &mut db), Err(ExecutionError::ColumnNotFound(name)) if name == "email"));
+    }
+
+    #[test]
+    fn test_partial_update_keeps_explicit_value_in_defaulted_column() {
+        let mut db = Database::new();
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("name", DataType::String).default(Value::String("anonymous".to_string())).unwrap().build(),
+            ColumnBuilder::new("age", DataType::Integer).build(),
+        ])
+        .unwrap();
+        db.create_table("users".to_string(), schema).unwrap();
+        db.insert_row("users".to_string(), user(1, "Alice", 30)).unwrap();
+        db.insert_row("users".to_string(), vec![Value::Integer(2), Value::Null, Value::Integer(25)]).unwrap();
+
+        let ast = update_users(vec![("age", Literal::Integer(40))], None);
+        Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(user_rows(&db), vec![user(1, "Alice", 40), user(2, "anonymous", 40)]);
+    }
+
+    fn select_names_ordered(db: &mut Database, order_by: &[(&str, SortDirection)]) -> Vec<Value> {
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![SelectColumn::Identifier("name".to_string())],
+            where_clause: None,
+            order_by: order_by.iter().map(|(column, direction)| ((*column).to_string(), *direction, None)).collect(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+        Executor {}
+            .execute(&ast, db)
+            .unwrap()
+            .rows
+            .into_iter()
+            .map(|row| row.values[0].clone())
+            .collect()
+    }
+
+    fn names(names: &[&str]) -> Vec<Value> {
+        names.iter().map(|name| Value::String((*name).to_string())).collect()
+    }
+
+    #[test]
+    fn test_order_by_single_key() {
+        let mut db = create_mock_db();
+
+        assert_eq!(
+            select_names_ordered(&mut db, &[("age", SortDirection::Asc)]),
+            names(&["Bob", "Alice", "Charlie"])
+        );
+        assert_eq!(
+            select_names_ordered(&mut db, &[("name", SortDirection::Desc)]),
+            names(&["Charlie", "Bob", "Alice"])
+        );
+    }
+
+    #[test]
+    fn test_order_by_multiple_keys() {
+        let mut db = create_mock_db();
+
+        assert_eq!(
+            select_names_ordered(&mut db, &[("age", SortDirection::Desc), ("name", SortDirection::Desc)]),
+            names(&["Charlie", "Alice", "Bob"])
+        );
+        assert_eq!(
+            select_names_ordered(&mut db, &[("age", SortDirection::Desc), ("id", SortDirection::Asc)]),
+            names(&["Alice", "Charlie", "Bob"])
+        );
+    }
+
+    #[test]
+    fn test_order_by_puts_nulls_last() {
+        let mut db = create_mock_db();
+        db.insert_row("users".to_string(), vec![Value::Integer(4), Value::String("Dan".to_string()), Value::Null]).unwrap();
+
+        assert_eq!(
+            select_names_ordered(&mut db, &[("age", SortDirection::Asc)]),
+            names(&["Bob", "Alice", "Charlie", "Dan"])
+        );
+        assert_eq!(
+            select_names_ordered(&mut db, &[("age", SortDirection::Desc)]),
+            names(&["Alice", "Charlie", "Bob", "Dan"])
+        );
+    }
+
+    #[test]
+    fn test_order_by_nulls_default_comes_from_config() {
+        let mut db = create_mock_db();
+        db.insert_row("users".to_string(), vec![Value::Integer(4), Value::String("Dan".to_string()), Value::Null]).unwrap();
+        db.set_executor_config(ExecutorConfig { nulls_order: NullsOrder::First, ..ExecutorConfig::default() });
+
+        assert_eq!(
+            select_names_ordered(&mut db, &[("age", SortDirection::Asc)]),
+            names(&["Dan", "Bob", "Alice", "Charlie"])
+        );
+        assert_eq!(
+            select_names_ordered(&mut db, &[("age", SortDirection::Desc)]),
+            names(&["Dan", "Alice", "Charlie", "Bob"])
+        );
+
+        // A key's own NULLS clause wins over the configured default, either way round.
+        let ordered = |db: &mut Database, nulls: NullsOrder| {
+            let ast = select_users_where(
+                Expression::Literal(Literal::Boolean(true), None),
+                None,
+                vec![("age".to_string(), SortDirection::Asc, Some(nulls))],
+            );
+            Executor {}.execute(&ast, db).unwrap().rows.into_iter().map(|row| row.values[0].clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(ordered(&mut db, NullsOrder::Last).last(), Some(&Value::Integer(4)));
+        db.set_executor_config(ExecutorConfig::default());
+        assert_eq!(ordered(&mut db, NullsOrder::First).first(), Some(&Value::Integer(4)));
+    }
+
+    #[test]
+    fn test_order_by_unknown_column() {
+        let mut db = create_mock_db();
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![SelectColumn::Wildcard],
+            where_clause: None,
+            order_by: vec![("email".to_string(), SortDirection::Asc, None)],
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        assert!(matches!(Executor {}.execute(&ast, &mut db), Err(ExecutionError::ColumnNotFound(name)) if name == "email"));
+    }
+
+    #[test]
+    fn test_compare_integer_column_with_float_literal() {
+        let mut db = create_mock_db();
+
+        let where_clause = comparison("age", BinaryOperator::GreaterThan, Literal::Float(27.5));
+        assert_eq!(select_ids(&mut db, where_clause), vec![Value::Integer(1), Value::Integer(3)]);
+        let where_clause = comparison("age", BinaryOperator::Equals, Literal::Float(25.0));
+        assert_eq!(select_ids(&mut db, where_clause), vec![Value::Integer(2)]);
+    }
+
+    #[test]
+    fn test_compare_rejects_incomparable_types() {
+        let result = Executor::compare(&Value::Integer(1), &BinaryOperator::Equals, &Value::String("1".to_string()));
+        assert!(matches!(result, Err(ExecutionError::TypeMismatch)));
+        let result = Executor::compare(&Value::Boolean(true), &BinaryOperator::Equals, &Value::Float(1.0));
+        assert!(matches!(result, Err(ExecutionError::TypeMismatch)));
+    }
+
+    fn select_ids_paged(db: &mut Database, limit: Option<usize>, offset: Option<usize>) -> Vec<Value> {
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![SelectColumn::Identifier("id".to_string())],
+            where_clause: None,
+            order_by: vec![("id".to_string(), SortDirection::Asc, None)],
+            limit,
+            offset,
+            distinct: false,
+        });
+        Executor {}
+            .execute(&ast, db)
+            .unwrap()
+            .rows
+            .into_iter()
+            .map(|row| row.values[0].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_select_with_limit() {
+        let mut db = create_mock_db();
+
+        assert_eq!(select_ids_paged(&mut db, Some(2), None), vec![Value::Integer(1), Value::Integer(2)]);
+        assert_eq!(select_ids_paged(&mut db, Some(10), None).len(), 3);
+        assert!(select_ids_paged(&mut db, Some(0), None).is_empty());
+    }
+
+    #[test]
+    fn test_select_with_offset() {
+        let mut db = create_mock_db();
+
+        assert_eq!(select_ids_paged(&mut db, None, Some(1)), vec![Value::Integer(2), Value::Integer(3)]);
+        assert!(select_ids_paged(&mut db, None, Some(3)).is_empty());
+        assert!(select_ids_paged(&mut db, None, Some(100)).is_empty());
+    }
+
+    #[test]
+    fn test_select_with_limit_and_offset() {
+        let mut db = create_mock_db();
+
+        assert_eq!(select_ids_paged(&mut db, Some(1), Some(1)), vec![Value::Integer(2)]);
+        assert_eq!(select_ids_paged(&mut db, Some(5), Some(2)), vec![Value::Integer(3)]);
+    }
+
+    fn select_aggregates(db: &mut Database, columns: Vec<SelectColumn>, where_clause: Option<Expression>) -> QueryResult {
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns,
+            where_clause,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+        Executor {}.execute(&ast, db).unwrap()
+    }
+
+    fn aggregate(func: AggregateFunc, arg: &str) -> SelectColumn {
+        SelectColumn::Aggregate { func, arg: Some(arg.to_string()) }
+    }
+
+    #[test]
+    fn test_count() {
+        let mut db = create_mock_db();
+        db.insert_row("users".to_string(), vec![Value::Integer(4), Value::String("Dan".to_string()), Value::Null]).unwrap();
+        let count_all = SelectColumn::Aggregate { func: AggregateFunc::Count, arg: None };
+
+        let result = select_aggregates(&mut db, vec![count_all, aggregate(AggregateFunc::Count, "age")], None);
+
+        assert_eq!(result.columns, vec!["COUNT(*)".to_string(), "COUNT(age)".to_string()]);
+        assert_eq!(result.rows, vec![Row { values: vec![Value::Integer(4), Value::Integer(3)] }]);
+    }
+
+    #[test]
+    fn test_sum() {
+        let mut db = create_mock_db();
+        let where_clause = comparison("age", BinaryOperator::Equals, Literal::Integer(30));
+
+        let result = select_aggregates(&mut db, vec![aggregate(AggregateFunc::Sum, "age")], Some(where_clause));
+
+        assert_eq!(result.get(0, "SUM(age)"), Some(&Value::Integer(60)));
+    }
+
+    #[test]
+    fn test_avg() {
+        let mut db = create_mock_db();
+
+        let result = select_aggregates(&mut db, vec![aggregate(AggregateFunc::Avg, "age")], None);
+
+        assert_eq!(result.get(0, "AVG(age)"), Some(&Value::Float(85.0 / 3.0)));
+    }
+
+    #[test]
+    fn test_min_and_max() {
+        let mut db = create_mock_db();
+
+        let result = select_aggregates(
+            &mut db,
+            vec![aggregate(AggregateFunc::Min, "age"), aggregate(AggregateFunc::Max, "name")],
+            None,
+        );
+
+        assert_eq!(result.rows, vec![Row { values: vec![Value::Integer(25), Value::String("Charlie".to_string())] }]);
+    }
+
+    #[test]
+    fn test_aggregates_over_no_rows() {
+        let mut db = create_mock_db();
+        let where_clause = comparison("age", BinaryOperator::GreaterThan, Literal::Integer(99));
+
+        let result = select_aggregates(
+            &mut db,
+            vec![aggregate(AggregateFunc::Count, "age"), aggregate(AggregateFunc::Sum, "age"), aggregate(AggregateFunc::Max, "age")],
+            Some(where_clause),
+        );
+
+        assert_eq!(result.rows, vec![Row { values: vec![Value::Integer(0), Value::Null, Value::Null] }]);
+    }
+
+    #[test]
+    fn test_sum_and_avg_reject_non_numeric_columns() {
+        let mut db = create_mock_db();
+
+        for func in [AggregateFunc::Sum, AggregateFunc::Avg] {
+            let ast = Statements::Select(SelectStatement {
+                from_table: "users".to_string(),
+                columns: vec![aggregate(func, "name")],
+                where_clause: None,
+                order_by: Vec::new(),
+                limit: None,
+                offset: None,
+                distinct: false,
+            });
+            assert!(matches!(Executor {}.execute(&ast, &mut db), Err(ExecutionError::TypeMismatch)));
+        }
+    }
+
+    #[test]
+    fn test_aggregates_cannot_mix_with_plain_columns() {
+        let mut db = create_mock_db();
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![SelectColumn::Identifier("name".to_string()), aggregate(AggregateFunc::Max, "age")],
+            where_clause: None,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        assert!(matches!(Executor {}.execute(&ast, &mut db), Err(ExecutionError::InvalidExpression)));
+    }
+
+    #[test]
+    fn test_select_distinct() {
+        let mut db = create_mock_db();
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![SelectColumn::Identifier("age".to_string())],
+            where_clause: None,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: true,
+        });
+
+        let result = Executor {}.execute(&ast, &mut db).unwrap();
+
+        // Ages 30, 25, 30 collapse to the first-seen order 30, 25.
+        assert_eq!(result.rows, vec![
+            Row { values: vec![Value::Integer(30)] },
+            Row { values: vec![Value::Integer(25)] },
+        ]);
+    }
+
+    fn insert_into_users(columns: &[&str], values: Vec<Literal>) -> Statements {
+        Statements::Insert(InsertStatement {
+            table_name: "users".to_string(),
+            values,
+            columns: columns.iter().map(|column| (*column).to_string()).collect(),
+        })
+    }
+
+    #[test]
+    fn test_insert_in_schema_order() {
+        let mut db = create_mock_db();
+        let ast = insert_into_users(&[], vec![Literal::Integer(4), Literal::String("Dan".to_string()), Literal::Integer(41)]);
+
+        let result = Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(result.rows_affected, 1);
+        assert_eq!(user_rows(&db).last(), Some(&user(4, "Dan", 41)));
+    }
+
+    #[test]
+    fn test_insert_maps_column_list_by_name() {
+        let mut db = create_mock_db();
+        let ast = insert_into_users(
+            &["age", "name", "id"],
+            vec![Literal::Integer(41), Literal::String("Dan".to_string()), Literal::Integer(4)],
+        );
+
+        Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(user_rows(&db).last(), Some(&user(4, "Dan", 41)));
+    }
+
+    #[test]
+    fn test_insert_leaves_unlisted_columns_null() {
+        let mut db = create_mock_db();
+        let ast = insert_into_users(&["name", "id"], vec![Literal::String("Dan".to_string()), Literal::Integer(4)]);
+
+        Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(
+            user_rows(&db).last(),
+            Some(&vec![Value::Integer(4), Value::String("Dan".to_string()), Value::Null])
+        );
+    }
+
+    #[test]
+    fn test_insert_rejects_bad_column_lists() {
+        let mut db = create_mock_db();
+
+        let unknown = insert_into_users(&["email"], vec![Literal::String("x".to_string())]);
+        assert!(matches!(Executor {}.execute(&unknown, &mut db), Err(ExecutionError::ColumnNotFound(name)) if name == "email"));
+        let repeated = insert_into_users(&["id", "id"], vec![Literal::Integer(4), Literal::Integer(5)]);
+        assert!(matches!(Executor {}.execute(&repeated, &mut db), Err(ExecutionError::DuplicateInsertColumn(name)) if name == "id"));
+        let wrong_type = insert_into_users(&["id"], vec![Literal::String("four".to_string())]);
+        assert!(matches!(Executor {}.execute(&wrong_type, &mut db), Err(ExecutionError::InvalidRow(_))));
+        assert_eq!(user_rows(&db).len(), 3);
+    }
+
+    /// Two tables with the same 2,000 rows, one with `age` indexed and one without.
+    fn create_range_fixture() -> Database {
+        let mut db = Database::new();
+        let indexed = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("age", DataType::Integer).index().build(),
+        ])
+        .unwrap();
+        let plain = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("age", DataType::Integer).build(),
+        ])
+        .unwrap();
+        db.create_table("indexed".to_string(), indexed).unwrap();
+        db.create_table("plain".to_string(), plain).unwrap();
+
+        for id in 0..2000 {
+            let age = if id % 97 == 0 { Value::Null } else { Value::Integer((id * 37) % 101) };
+            for table in ["indexed", "plain"] {
+                db.insert_row(table.to_string(), vec![Value::Integer(id), age.clone()]).unwrap();
+            }
+        }
+        db
+    }
+
+    fn select_all_where(db: &mut Database, table: &str, where_clause: Expression) -> Vec<Row> {
+        let ast = Statements::Select(SelectStatement {
+            from_table: table.to_string(),
+            columns: vec![SelectColumn::Wildcard],
+            where_clause: Some(where_clause),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+        Executor {}.execute(&ast, db).unwrap().rows
+    }
+
+    fn and(left: Expression, right: Expression) -> Expression {
+        Expression::Binary(Box::new(left), BinaryOperator::And, Box::new(right))
+    }
+
+    #[test]
+    fn test_index_range_matches_full_scan() {
+        let mut db = create_range_fixture();
+        let age = |op, n| comparison("age", op, Literal::Integer(n));
+        let predicates = [
+            and(age(BinaryOperator::GreaterThanOrEquals, 25), age(BinaryOperator::LessThan, 40)),
+            and(age(BinaryOperator::GreaterThan, 25), age(BinaryOperator::LessThanOrEquals, 40)),
+            age(BinaryOperator::Equals, 50),
+            age(BinaryOperator::GreaterThan, 90),
+            age(BinaryOperator::LessThan, 3),
+            and(and(age(BinaryOperator::GreaterThan, 10), age(BinaryOperator::GreaterThan, 20)), age(BinaryOperator::LessThan, 30)),
+            and(age(BinaryOperator::GreaterThan, 40), age(BinaryOperator::LessThan, 40)),
+            and(age(BinaryOperator::GreaterThanOrEquals, 40), age(BinaryOperator::LessThanOrEquals, 40)),
+            and(age(BinaryOperator::GreaterThan, 60), age(BinaryOperator::LessThan, 20)),
+            and(age(BinaryOperator::GreaterThan, 30), comparison("id", BinaryOperator::LessThan, Literal::Integer(500))),
+        ];
+
+        for predicate in predicates {
+            let indexed = select_all_where(&mut db, "indexed", predicate.clone());
+            let plain = select_all_where(&mut db, "plain", predicate);
+            assert_eq!(indexed, plain);
+        }
+    }
+
+    #[test]
+    fn test_index_range_narrows_candidates() {
+        let db = create_range_fixture();
+        let range = and(
+            comparison("age", BinaryOperator::GreaterThanOrEquals, Literal::Integer(25)),
+            comparison("age", BinaryOperator::LessThan, Literal::Integer(40)),
+        );
+        let indexed = db.get_table("indexed".to_string()).unwrap();
+        let plain = db.get_table("plain".to_string()).unwrap();
+
+        let candidates = Executor::index_range_candidates(&range, indexed, NameResolution::default()).unwrap();
+
+        assert!(!candidates.is_empty() && candidates.len() < indexed.row_count() / 5);
+        assert_eq!(Executor::index_range_candidates(&range, plain, NameResolution::default()), None);
+        let mixed_types = comparison("age", BinaryOperator::GreaterThan, Literal::Float(25.5));
+        assert_eq!(Executor::index_range_candidates(&mixed_types, indexed, NameResolution::default()), None);
+    }
+
+    fn literal_comparison(left: i64, op: BinaryOperator, right: i64) -> Expression {
+        Expression::Binary(
+            Box::new(Expression::Literal(Literal::Integer(left), None)),
+            op,
+            Box::new(Expression::Literal(Literal::Integer(right), None)),
+        )
+    }
+
+    #[test]
+    fn test_fold_constants() {
+        let age_over_30 = comparison("age", BinaryOperator::GreaterThan, Literal::Integer(30));
+
+        let trivially_true = and(literal_comparison(1, BinaryOperator::Equals, 1), age_over_30.clone());
+        assert_eq!(trivially_true.fold_constants(), age_over_30);
+
+        let trivially_false = and(age_over_30.clone(), literal_comparison(1, BinaryOperator::Equals, 2));
+        assert_eq!(trivially_false.fold_constants(), Expression::Literal(Literal::Boolean(false), None));
+
+        let or_true = Expression::Binary(
+            Box::new(age_over_30.clone()),
+            BinaryOperator::Or,
+            Box::new(literal_comparison(2, BinaryOperator::GreaterThan, 1)),
+        );
+        assert_eq!(or_true.fold_constants(), Expression::Literal(Literal::Boolean(true), None));
+
+        // Without constant parts there's nothing to fold.
+        assert_eq!(age_over_30.fold_constants(), age_over_30);
+    }
+
+    #[test]
+    fn test_referenced_columns() {
+        // NOT (age > 30 AND (name IS NULL OR id IN (1, 2))) AND age BETWEEN 1 AND 99
+        let expr = and(
+            Expression::Not(Box::new(and(
+                comparison("age", BinaryOperator::GreaterThan, Literal::Integer(30)),
+                Expression::Binary(
+                    Box::new(is_null("name", false)),
+                    BinaryOperator::Or,
+                    Box::new(in_list("id", vec![Literal::Integer(1), Literal::Integer(2)])),
+                ),
+            ))),
+            Expression::Between {
+                expr: Box::new(Expression::Identifier("age".to_string(), None)),
+                low: Box::new(Expression::Literal(Literal::Integer(1), None)),
+                high: Box::new(Expression::Literal(Literal::Integer(99), None)),
+            },
+        );
+
+        assert_eq!(expr.column_references(), vec!["age", "name", "id", "age"]);
+        assert_eq!(expr.referenced_columns(), HashSet::from(["age".to_string(), "name".to_string(), "id".to_string()]));
+        assert!(expr.references_column("id"));
+        assert!(!expr.references_column("ID"));
+        assert!(!expr.references_column("email"));
+
+        assert!(Expression::Literal(Literal::Integer(1), None).referenced_columns().is_empty());
+    }
+
+    #[test]
+    fn test_select_with_trivially_true_predicate() {
+        let mut db = create_mock_db();
+        let where_clause = and(
+            literal_comparison(1, BinaryOperator::Equals, 1),
+            comparison("age", BinaryOperator::Equals, Literal::Integer(30)),
+        );
+
+        assert_eq!(select_ids(&mut db, where_clause), vec![Value::Integer(1), Value::Integer(3)]);
+        assert_eq!(select_ids(&mut db, literal_comparison(1, BinaryOperator::Equals, 1)).len(), 3);
+    }
+
+    #[test]
+    fn test_select_with_trivially_false_predicate() {
+        let mut db = create_mock_db();
+
+        assert!(select_ids(&mut db, literal_comparison(1, BinaryOperator::Equals, 2)).is_empty());
+
+        let ast = delete_from_users(Some(literal_comparison(1, BinaryOperator::Equals, 2)));
+        assert_eq!(Executor {}.execute(&ast, &mut db).unwrap().rows_affected, 0);
+    }
+
+    fn is_null(column: &str, negated: bool) -> Expression {
+        Expression::IsNull(Box::new(Expression::Identifier(column.to_string(), None)), negated)
+    }
+
+    #[test]
+    fn test_is_null_and_is_not_null() {
+        let mut db = create_mock_db();
+        for (id, name) in [(4, "Dan"), (5, "Eve")] {
+            let ast = insert_into_users(&["id", "name"], vec![Literal::Integer(id), Literal::String(name.to_string())]);
+            Executor {}.execute(&ast, &mut db).unwrap();
+        }
+        let ast = insert_into_users(&[], vec![Literal::Integer(6), Literal::Null, Literal::Integer(50)]);
+        Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert_eq!(select_ids(&mut db, is_null("age", false)), vec![Value::Integer(4), Value::Integer(5)]);
+        assert_eq!(select_ids(&mut db, is_null("name", false)), vec![Value::Integer(6)]);
+        assert_eq!(
+            select_ids(&mut db, is_null("age", true)),
+            vec![Value::Integer(1), Value::Integer(2), Value::Integer(3), Value::Integer(6)]
+        );
+    }
+
+    #[test]
+    fn test_comparisons_with_null_never_match() {
+        let mut db = create_mock_db();
+        let ast = insert_into_users(&["id"], vec![Literal::Integer(4)]);
+        Executor {}.execute(&ast, &mut db).unwrap();
+
+        assert!(select_ids(&mut db, comparison("age", BinaryOperator::Equals, Literal::Null)).is_empty());
+        assert!(select_ids(&mut db, comparison("age", BinaryOperator::NotEquals, Literal::Null)).is_empty());
+        assert_eq!(
+            select_ids(&mut db, comparison("age", BinaryOperator::LessThan, Literal::Integer(100))),
+            vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)]
+        );
+    }
+
+    #[test]
+    fn test_compare_with_null_is_unknown() {
+        let operators = [
+            BinaryOperator::Equals,
+            BinaryOperator::NotEquals,
+            BinaryOperator::GreaterThan,
+            BinaryOperator::LessThan,
+            BinaryOperator::GreaterThanOrEquals,
+            BinaryOperator::LessThanOrEquals,
+        ];
+        for op in &operators {
+            assert_eq!(Executor::compare(&Value::Null, op, &Value::Integer(5)).unwrap(), None);
+            assert_eq!(Executor::compare(&Value::Integer(5), op, &Value::Null).unwrap(), None);
+            assert_eq!(Executor::compare(&Value::Null, op, &Value::Null).unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn test_and_or_three_valued_truth_tables() {
+        let row = Row { values: user(1, "Alice", 30) };
+        let logic = |value: Option<bool>| Expression::Literal(value.map_or(Literal::Null, Literal::Boolean), None);
+        let evaluate = |left, op, right| {
+            let expression = Expression::Binary(Box::new(logic(left)), op, Box::new(logic(right)));
+            Executor::evaluate_expression_at_depth(&expression, &row, &users_schema(), NameResolution::default(), 0).unwrap()
+        };
+
+        let values = [Some(true), Some(false), None];
+        for left in values {
+            for right in values {
+                let expected_and = match (left, right) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                };
+                let expected_or = match (left, right) {
+                    (Some(true), _) | (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                };
+                assert_eq!(evaluate(left, BinaryOperator::And, right), expected_and, "{left:?} AND {right:?}");
+                assert_eq!(evaluate(left, BinaryOperator::Or, right), expected_or, "{left:?} OR {right:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_comparisons_exclude_rows() {
+        let mut db = create_mock_db();
+        let ast = insert_into_users(&["id", "name"], vec![Literal::Integer(4), Literal::String("Dan".to_string())]);
+        Executor {}.execute(&ast, &mut db).unwrap();
+
+        let age_over_5 = comparison("age", BinaryOperator::GreaterThan, Literal::Integer(5));
+        assert_eq!(
+            select_ids(&mut db, age_over_5.clone()),
+            vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)]
+        );
+        // Unknown OR true is true; unknown AND true is unknown.
+        let is_dan = comparison("name", BinaryOperator::Equals, Literal::String("Dan".to_string()));
+        let either = Expression::Binary(Box::new(age_over_5.clone()), BinaryOperator::Or, Box::new(is_dan.clone()));
+        assert_eq!(select_ids(&mut db, either).len(), 4);
+        assert!(select_ids(&mut db, and(age_over_5, is_dan)).is_empty());
+        assert!(select_ids(&mut db, comparison("age", BinaryOperator::Equals, Literal::Null)).is_empty());
+    }
+
+    #[test]
+    fn test_type_mismatched_predicate_is_rejected_up_front() {
+        let mut db = Database::new();
+        db.create_table("users".to_string(), users_schema()).unwrap();
+        let age_is_abc = comparison("age", BinaryOperator::Equals, Literal::String("abc".to_string()));
+        let ast = Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![SelectColumn::Wildcard],
+            where_clause: Some(and(comparison("id", BinaryOperator::GreaterThan, Literal::Integer(0)), age_is_abc.clone())),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        // The table is empty, so only the up-front check can catch this.
+        assert!(matches!(Executor {}.execute(&ast, &mut db), Err(ExecutionError::TypeMismatch)));
+        let ast = update_users(vec![("age", Literal::Integer(1))], Some(age_is_abc));
+        assert!(matches!(Executor {}.execute(&ast, &mut db), Err(ExecutionError::TypeMismatch)));
+    }
+
+    #[test]
+    fn test_index_planner_prefers_most_selective_index() {
+        let mut table = Table::new(Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).index().build(),
+            ColumnBuilder::new("active", DataType::Boolean).index().build(),
+        ]).unwrap());
+        for id in 0..100 {
+            table.add_row(vec![Value::Integer(id), Value::Boolean(id % 2 == 0)]).unwrap();
+        }
+
+        let active = comparison("active", BinaryOperator::Equals, Literal::Boolean(true));
+        let id_is_42 = comparison("id", BinaryOperator::Equals, Literal::Integer(42));
+        for expression in [and(active.clone(), id_is_42.clone()), and(id_is_42, active)] {
+            let candidates = Executor::index_range_candidates(&expression, &table, NameResolution::default());
+            assert_eq!(candidates, Some(BTreeSet::from([42])));
+        }
+
+        // A conjunct the index can't use doesn't stop the others from being used.
+        let with_or = and(
+            Expression::Binary(
+                Box::new(comparison("id", BinaryOperator::LessThan, Literal::Integer(3))),
+                BinaryOperator::Or,
+                Box::new(comparison("id", BinaryOperator::GreaterThan, Literal::Integer(97))),
+            ),
+            comparison("id", BinaryOperator::Equals, Literal::Integer(7)),
+        );
+        let candidates = Executor::index_range_candidates(&with_or, &table, NameResolution::default());
+        assert_eq!(candidates, Some(BTreeSet::from([7])));
+    }
+
+    fn reversed_comparison(literal: Literal, op: BinaryOperator, column: &str) -> Expression {
+        Expression::Binary(Box::new(Expression::Literal(literal, None)), op, Box::new(Expression::Identifier(column.to_string(), None)))
+    }
+
+    #[test]
+    fn test_literal_on_left_of_comparison() {
+        let mut db = create_mock_db();
+
+        let bob = reversed_comparison(Literal::String("Bob".to_string()), BinaryOperator::Equals, "name");
+        assert_eq!(select_ids(&mut db, bob), vec![Value::Integer(2)]);
+        // 28 < age reads as age > 28.
+        let over_28 = reversed_comparison(Literal::Integer(28), BinaryOperator::LessThan, "age");
+        assert_eq!(select_ids(&mut db, over_28), vec![Value::Integer(1), Value::Integer(3)]);
+
+        let mismatch = reversed_comparison(Literal::String("thirty".to_string()), BinaryOperator::Equals, "age");
+        assert!(matches!(
+            Executor {}.execute(&delete_from_users(Some(mismatch)), &mut db),
+            Err(ExecutionError::TypeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_index_range_with_literal_on_left_matches_full_scan() {
+        let mut db = create_range_fixture();
+        for op in [BinaryOperator::LessThan, BinaryOperator::GreaterThanOrEquals, BinaryOperator::Equals] {
+            let where_clause = and(
+                reversed_comparison(Literal::Integer(40), op.clone(), "age"),
+                reversed_comparison(Literal::Integer(90), BinaryOperator::GreaterThan, "age"),
+            );
+            assert_eq!(
+                select_all_where(&mut db, "indexed", where_clause.clone()),
+                select_all_where(&mut db, "plain", where_clause),
+                "{op:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_replay_stops_at_statement_written_for_older_schema() {
+        let mut db = create_mock_db();
+        // Logged back when `users` still had a `nickname` column.
+        let log = vec![
+            insert_into_users(&[], vec![Literal::Integer(4), Literal::String("Dan".to_string()), Literal::Integer(41)]),
+            insert_into_users(&["id", "name", "nickname"], vec![
+                Literal::Integer(5),
+                Literal::String("Eve".to_string()),
+                Literal::String("evie".to_string()),
+            ]),
+            insert_into_users(&[], vec![Literal::Integer(6), Literal::String("Fay".to_string()), Literal::Integer(29)]),
+        ];
+
+        let error = Executor {}.replay(&log, &mut db).unwrap_err();
+
+        let ExecutionError::ReplayFailed { statement, source } = error else { panic!("expected ReplayFailed, got {error:?}") };
+        assert_eq!(statement, 1);
+        assert!(matches!(*source, ExecutionError::ColumnNotFound(ref column) if column == "nickname"));
+        assert_eq!(user_rows(&db).len(), 4);
+        assert_eq!(user_rows(&db).last(), Some(&user(4, "Dan", 41)));
+    }
+
+    #[test]
+    fn test_validate_checks_columns_without_scanning_rows() {
+        let mut db = Database::new();
+        db.create_table("users".to_string(), users_schema()).unwrap();
+
+        // The table is empty, so no row would ever evaluate the stale column.
+        let stale_delete = delete_from_users(Some(comparison("email", BinaryOperator::Equals, Literal::Null)));
+        assert!(matches!(Executor {}.validate(&stale_delete, &db), Err(ExecutionError::ColumnNotFound(ref c)) if c == "email"));
+
+        let short_insert = insert_into_users(&[], vec![Literal::Integer(1)]);
+        assert!(matches!(
+            Executor {}.execute(&short_insert, &mut db),
+            Err(ExecutionError::InvalidRow(TableErrors::RowConstructionError(RowErrors::WrongValueCount { expected: 3, got: 1 })))
+        ));
+        assert!(Executor {}.validate(&delete_from_users(None), &db).is_ok());
+    }
+
+    fn name_like(pattern: &str) -> Expression {
+        comparison("name", BinaryOperator::Like, Literal::String(pattern.to_string()))
+    }
+
+    #[test]
+    fn test_like_prefix_suffix_and_contains() {
+        let mut db = create_mock_db();
+
+        assert_eq!(select_ids(&mut db, name_like("Al%")), vec![Value::Integer(1)]);
+        assert_eq!(select_ids(&mut db, name_like("%e")), vec![Value::Integer(1), Value::Integer(3)]);
+        assert_eq!(select_ids(&mut db, name_like("%li%")), vec![Value::Integer(1), Value::Integer(3)]);
+        assert_eq!(select_ids(&mut db, name_like("B_b")), vec![Value::Integer(2)]);
+        assert_eq!(select_ids(&mut db, name_like("%")).len(), 3);
+        assert!(select_ids(&mut db, name_like("al%")).is_empty());
+    }
+
+    #[test]
+    fn test_not_like() {
+        let mut db = create_mock_db();
+        Executor {}.execute(&insert_into_users(&["id"], vec![Literal::Integer(4)]), &mut db).unwrap();
+        let name_not_like = |pattern: &str| comparison("name", BinaryOperator::NotLike, Literal::String(pattern.to_string()));
+
+        assert_eq!(select_ids(&mut db, name_not_like("Al%")), vec![Value::Integer(2), Value::Integer(3)]);
+        assert_eq!(select_ids(&mut db, name_not_like("%li%")), vec![Value::Integer(2)]);
+        // A NULL name is unknown either way, so neither LIKE nor NOT LIKE keeps it.
+        assert!(select_ids(&mut db, name_not_like("%")).is_empty());
+        assert!(matches!(
+            Executor {}.execute(&delete_from_users(Some(comparison("age", BinaryOperator::NotLike, Literal::String("3%".to_string())))), &mut db),
+            Err(ExecutionError::TypeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_like_wildcards_and_escapes() {
+        assert!(Executor::like("", "%"));
+        assert!(!Executor::like("", "_"));
+        assert!(Executor::like("abcabd", "%ab_"));
+        assert!(Executor::like("mississippi", "m%ss%pi"));
+        assert!(!Executor::like("mississippi", "m%ss%px"));
+        assert!(Executor::like("50%", "50\\%"));
+        assert!(!Executor::like("500", "50\\%"));
+        assert!(Executor::like("a_b", "a\\_b"));
+        assert!(!Executor::like("axb", "a\\_b"));
+        assert!(Executor::like("back\\", "back\\"));
+        assert!(Executor::like("naïve", "na_ve"));
+    }
+
+    #[test]
+    fn test_like_on_non_string_is_type_mismatch() {
+        let mut db = create_mock_db();
+        for where_clause in [
+            comparison("age", BinaryOperator::Like, Literal::String("3%".to_string())),
+            comparison("age", BinaryOperator::Like, Literal::Integer(30)),
+        ] {
+            let ast = delete_from_users(Some(where_clause));
+            assert!(matches!(Executor {}.execute(&ast, &mut db), Err(ExecutionError::TypeMismatch)));
+        }
+        assert!(matches!(
+            Executor::compare(&Value::Integer(30), &BinaryOperator::Like, &Value::Integer(30)),
+            Err(ExecutionError::TypeMismatch)
+        ));
+    }
+
+    fn in_list(column: &str, list: Vec<Literal>) -> Expression {
+        Expression::InList(Box::new(Expression::Identifier(column.to_string(), None)), list, false)
+    }
+
+    fn not_in_list(column: &str, list: Vec<Literal>) -> Expression {
+        Expression::InList(Box::new(Expression::Identifier(column.to_string(), None)), list, true)
+    }
+
+    #[test]
+    fn test_in_list_membership() {
+        let mut db = create_mock_db();
+
+        let ids = vec![Literal::Integer(1), Literal::Integer(3), Literal::Integer(7)];
+        assert_eq!(select_ids(&mut db, in_list("id", ids)), vec![Value::Integer(1), Value::Integer(3)]);
+        let names = vec![Literal::String("Bob".to_string()), Literal::String("Zed".to_string())];
+        assert_eq!(select_ids(&mut db, in_list("name", names)), vec![Value::Integer(2)]);
+        // Integers and floats compare numerically.
+        assert_eq!(select_ids(&mut db, in_list("age", vec![Literal::Float(25.0)])), vec![Value::Integer(2)]);
+        assert!(select_ids(&mut db, in_list("id", Vec::new())).is_empty());
+    }
+
+    #[test]
+    fn test_in_list_with_nulls() {
+        let mut db = create_mock_db();
+        Executor {}.execute(&insert_into_users(&["id"], vec![Literal::Integer(4)]), &mut db).unwrap();
+
+        // A NULL entry can make a miss unknown, but never blocks a hit.
+        assert_eq!(select_ids(&mut db, in_list("age", vec![Literal::Integer(25), Literal::Null])), vec![Value::Integer(2)]);
+        assert!(select_ids(&mut db, in_list("age", vec![Literal::Null])).is_empty());
+        assert_eq!(Executor::in_list(&Value::Null, &[]).unwrap(), Some(false));
+        assert_eq!(Executor::in_list(&Value::Null, &[Literal::Integer(1)]).unwrap(), None);
+        assert_eq!(Executor::in_list(&Value::Integer(2), &[Literal::Integer(1), Literal::Null]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_not_in_list() {
+        let mut db = create_mock_db();
+        Executor {}.execute(&insert_into_users(&["id"], vec![Literal::Integer(4)]), &mut db).unwrap();
+
+        assert_eq!(select_ids(&mut db, not_in_list("id", vec![Literal::Integer(1), Literal::Integer(3)])), vec![Value::Integer(2), Value::Integer(4)]);
+        assert_eq!(select_ids(&mut db, not_in_list("age", vec![Literal::Integer(30)])), vec![Value::Integer(2)]);
+        assert_eq!(select_ids(&mut db, not_in_list("id", Vec::new())).len(), 4);
+        // A NULL in the list makes every miss unknown, so NOT IN matches nothing;
+        // a hit is still a definite false.
+        assert!(select_ids(&mut db, not_in_list("id", vec![Literal::Integer(1), Literal::Null])).is_empty());
+        assert!(select_ids(&mut db, not_in_list("name", vec![Literal::Null])).is_empty());
+    }
+
+    #[test]
+    fn test_in_list_type_mismatch() {
+        let mut db = create_mock_db();
+        for where_clause in [
+            in_list("id", vec![Literal::Integer(1), Literal::String("2".to_string())]),
+            in_list("id", vec![Literal::String("1".to_string())]),
+        ] {
+            let ast = delete_from_users(Some(where_clause));
+            assert!(matches!(Executor {}.execute(&ast, &mut db), Err(ExecutionError::TypeMismatch)));
+        }
+        assert!(matches!(
+            Executor::in_list(&Value::Integer(1), &[Literal::Integer(1), Literal::Boolean(true)]),
+            Err(ExecutionError::TypeMismatch)
+        ));
+    }
+
+    fn is_distinct_from(left: Expression, right: Expression, negated: bool) -> Expression {
+        Expression::IsDistinctFrom(Box::new(left), Box::new(right), negated)
+    }
+
+    #[test]
+    fn test_is_distinct_from_is_null_safe() {
+        assert!(!Executor::is_distinct(&Value::Null, &Value::Null).unwrap());
+        assert!(Executor::is_distinct(&Value::Null, &Value::Integer(1)).unwrap());
+        assert!(Executor::is_distinct(&Value::String("a".to_string()), &Value::Null).unwrap());
+        assert!(Executor::is_distinct(&Value::Integer(1), &Value::Integer(2)).unwrap());
+        assert!(!Executor::is_distinct(&Value::Integer(2), &Value::Float(2.0)).unwrap());
+        assert!(matches!(
+            Executor::is_distinct(&Value::Integer(1), &Value::Boolean(true)),
+            Err(ExecutionError::TypeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_is_distinct_from_in_where() {
+        let mut db = create_mock_db();
+        Executor {}.execute(&insert_into_users(&["id"], vec![Literal::Integer(4)]), &mut db).unwrap();
+        let column = |name: &str| Expression::Identifier(name.to_string(), None);
+        let literal = |literal: Literal| Expression::Literal(literal, None);
+
+        // Value differences and one-NULL comparisons are distinct; `<>` would skip row 4.
+        let not_bob = is_distinct_from(column("name"), literal(Literal::String("Bob".to_string())), false);
+        assert_eq!(select_ids(&mut db, not_bob), vec![Value::Integer(1), Value::Integer(3), Value::Integer(4)]);
+        // Both NULL: not distinct.
+        let age_is_null = is_distinct_from(column("age"), literal(Literal::Null), true);
+        assert_eq!(select_ids(&mut db, age_is_null), vec![Value::Integer(4)]);
+        let same_age = is_distinct_from(literal(Literal::Integer(30)), column("age"), true);
+        assert_eq!(select_ids(&mut db, same_age), vec![Value::Integer(1), Value::Integer(3)]);
+        // Column against column.
+        let id_differs_from_age = is_distinct_from(column("id"), column("age"), false);
+        assert_eq!(select_ids(&mut db, id_differs_from_age).len(), 4);
+
+        let mismatch = is_distinct_from(column("age"), literal(Literal::String("x".to_string())), false);
+        assert!(matches!(
+            Executor {}.execute(&delete_from_users(Some(mismatch)), &mut db),
+            Err(ExecutionError::TypeMismatch)
+        ));
+    }
+
+    fn age_between(low: Literal, high: Literal) -> Expression {
+        Expression::Between {
+            expr: Box::new(Expression::Identifier("age".to_string(), None)),
+            low: Box::new(Expression::Literal(low, None)),
+            high: Box::new(Expression::Literal(high, None)),
+        }
+    }
+
+    #[test]
+    fn test_between_is_inclusive() {
+        let mut db = create_mock_db();
+
+        let ids = select_ids(&mut db, age_between(Literal::Integer(25), Literal::Integer(30)));
+        assert_eq!(ids, vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)]);
+        let ids = select_ids(&mut db, age_between(Literal::Integer(26), Literal::Integer(30)));
+        assert_eq!(ids, vec![Value::Integer(1), Value::Integer(3)]);
+        let ids = select_ids(&mut db, age_between(Literal::Float(24.5), Literal::Float(25.5)));
+        assert_eq!(ids, vec![Value::Integer(2)]);
+    }
+
+    #[test]
+    fn test_between_empty_and_null_ranges() {
+        let mut db = create_mock_db();
+        Executor {}.execute(&insert_into_users(&["id"], vec![Literal::Integer(4)]), &mut db).unwrap();
+
+        assert!(select_ids(&mut db, age_between(Literal::Integer(30), Literal::Integer(25))).is_empty());
+        // NULL age is unknown; a NULL bound is unknown unless the other bound already fails.
+        assert!(select_ids(&mut db, age_between(Literal::Integer(0), Literal::Null)).is_empty());
+        assert_eq!(select_ids(&mut db, age_between(Literal::Integer(0), Literal::Integer(100))).len(), 3);
+    }
+
+    #[test]
+    fn test_between_type_mismatch() {
+        let mut db = create_mock_db();
+        for where_clause in [
+            age_between(Literal::String("a".to_string()), Literal::Integer(30)),
+            age_between(Literal::Integer(0), Literal::Boolean(true)),
+        ] {
+            let ast = delete_from_users(Some(where_clause));
+            assert!(matches!(Executor {}.execute(&ast, &mut db), Err(ExecutionError::TypeMismatch)));
+        }
+    }
+
+    #[test]
+    fn test_not_inverts_predicates() {
+        let mut db = create_mock_db();
+        Executor {}.execute(&insert_into_users(&["id"], vec![Literal::Integer(4)]), &mut db).unwrap();
+        let not = |expression| Expression::Not(Box::new(expression));
+
+        // Row 4's age is NULL, so `age = 30` is unknown and so is its negation.
+        let age_30 = comparison("age", BinaryOperator::Equals, Literal::Integer(30));
+        assert_eq!(select_ids(&mut db, not(age_30.clone())), vec![Value::Integer(2)]);
+        assert_eq!(select_ids(&mut db, not(not(age_30))), vec![Value::Integer(1), Value::Integer(3)]);
+        assert_eq!(select_ids(&mut db, not(is_null("age", false))), vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)]);
+        assert_eq!(
+            not(Expression::Literal(Literal::Boolean(true), None)).fold_constants(),
+            Expression::Literal(Literal::Boolean(false), None)
+        );
+    }
+
+    #[test]
+    fn test_select_over_max_rows_suggests_limit() {
+        let mut db = create_mock_db();
+        db.set_executor_config(ExecutorConfig { max_rows: Some(2), ..ExecutorConfig::default() });
+        let everyone = comparison("id", BinaryOperator::GreaterThan, Literal::Integer(0));
+
+        let error = Executor {}.execute(&select_users_where(everyone.clone(), None, Vec::new()), &mut db).unwrap_err();
+        assert!(matches!(error, ExecutionError::TooManyRows { max_rows: 2 }));
+        assert!(error.to_string().contains("LIMIT"));
+
+        // A LIMIT without ORDER BY stops the scan before the cap is reached...
+        let limited = Executor {}.execute(&select_users_where(everyone.clone(), Some(2), Vec::new()), &mut db).unwrap();
+        assert_eq!(limited.rows.len(), 2);
+        // ...but sorting still needs every matching row.
+        let sorted = select_users_where(everyone, Some(2), vec![("age".to_string(), SortDirection::Desc, None)]);
+        assert!(matches!(Executor {}.execute(&sorted, &mut db), Err(ExecutionError::TooManyRows { .. })));
+
+        let narrow = comparison("age", BinaryOperator::Equals, Literal::Integer(30));
+        assert_eq!(Executor {}.execute(&select_users_where(narrow, None, Vec::new()), &mut db).unwrap().rows.len(), 2);
+    }
+
+    fn select_users_where(
+        where_clause: Expression,
+        limit: Option<usize>,
+        order_by: Vec<(String, SortDirection, Option<NullsOrder>)>,
+    ) -> Statements {
+        Statements::Select(SelectStatement {
+            from_table: "users".to_string(),
+            columns: vec![SelectColumn::Wildcard],
+            where_clause: Some(where_clause),
+            order_by,
+            limit,
+            offset: None,
+            distinct: false,
+        })
+    }
+
+    fn drop_table(table_name: &str, if_exists: bool) -> Statements {
+        Statements::DropTable(DropTableStatement { table_name: table_name.to_string(), if_exists })
+    }
+
+    #[test]
+    fn test_drop_table() {
+        let mut db = create_mock_db();
+
+        Executor {}.execute(&drop_table("users", false), &mut db).unwrap();
+        assert!(db.get_table("users".to_string()).is_err());
+        assert!(matches!(Executor {}.execute(&drop_table("users", false), &mut db), Err(ExecutionError::TableNotFound)));
+    }
+
+    #[test]
+    fn test_drop_table_if_exists_ignores_missing_table() {
+        let mut db = create_mock_db();
+
+        Executor {}.execute(&drop_table("missing", true), &mut db).unwrap();
+        Executor {}.execute(&drop_table("users", true), &mut db).unwrap();
+        assert!(db.get_table("users".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_alter_table_drop_column() {
+        let mut db = create_mock_db();
+        let alter = Statements::AlterTable(AlterTableStatement {
+            table_name: "users".to_string(),
+            action: AlterTableAction::DropColumn("name".to_string()),
+        });
+
+        Executor {}.execute(&alter, &mut db).unwrap();
+
+        assert_eq!(user_rows(&db), vec![
+            vec![Value::Integer(1), Value::Integer(30)],
+            vec![Value::Integer(2), Value::Integer(25)],
+            vec![Value::Integer(3), Value::Integer(30)],
+        ]);
+        assert_eq!(select_ids(&mut db, comparison("age", BinaryOperator::Equals, Literal::Integer(25))), vec![Value::Integer(2)]);
+        assert!(matches!(Executor {}.execute(&alter, &mut db), Err(ExecutionError::ColumnNotFound(name)) if name == "name"));
+    }
+
+    #[test]
+    fn test_alter_column_type() {
+        let mut db = create_mock_db();
+        let alter = |column: &str, data_type: DataType| Statements::AlterTable(AlterTableStatement {
+            table_name: "users".to_string(),
+            action: AlterTableAction::AlterColumnType { column: column.to_string(), data_type },
+        });
+
+        Executor {}.execute(&alter("age", DataType::String), &mut db).unwrap();
+        assert_eq!(user_rows(&db)[1], vec![Value::Integer(2), Value::String("Bob".to_string()), Value::String("25".to_string())]);
+
+        // "Alice" isn't a number, so nothing changes.
+        let before = user_rows(&db);
+        assert!(matches!(
+            Executor {}.execute(&alter("name", DataType::Integer), &mut db),
+            Err(ExecutionError::InvalidRow(TableErrors::ConversionFailed { .. }))
+        ));
+        assert_eq!(user_rows(&db), before);
+        assert!(matches!(
+            Executor {}.execute(&alter("missing", DataType::Integer), &mut db),
+            Err(ExecutionError::ColumnNotFound(name)) if name == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_step_budget_bounds_scans() {
+        let mut db = create_mock_db();
+        let select = select_users_where(comparison("age", BinaryOperator::Equals, Literal::Integer(25)), None, vec![]);
+
+        db.set_executor_config(ExecutorConfig { max_steps: Some(2), ..ExecutorConfig::default() });
+        assert!(matches!(Executor {}.execute(&select, &mut db), Err(ExecutionError::BudgetExceeded { max_steps: 2 })));
+        assert!(matches!(Executor {}.execute(&delete_from_users(None), &mut db), Err(ExecutionError::BudgetExceeded { .. })));
+        assert_eq!(user_rows(&db).len(), 3);
+
+        db.set_executor_config(ExecutorConfig { max_steps: Some(100), ..ExecutorConfig::default() });
+        assert_eq!(Executor {}.execute(&select, &mut db).unwrap().rows.len(), 1);
+    }
+
+    #[test]
+    fn test_alter_table_rename_to() {
+        let mut db = create_mock_db();
+        db.create_table("orders".to_string(), users_schema()).unwrap();
+        let rename = |new_name: &str| Statements::AlterTable(AlterTableStatement {
+            table_name: "users".to_string(),
+            action: AlterTableAction::RenameTo(new_name.to_string()),
+        });
+
+        assert!(matches!(Executor {}.execute(&rename("orders"), &mut db), Err(ExecutionError::TableAlreadyExists(name)) if name == "orders"));
+        Executor {}.execute(&rename("members"), &mut db).unwrap();
+
+        let select = |table: &str| Statements::Select(SelectStatement {
+            from_table: table.to_string(),
+            columns: vec![SelectColumn::Wildcard],
+            where_clause: None,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+        assert_eq!(Executor {}.execute(&select("members"), &mut db).unwrap().rows.len(), 3);
+        assert!(matches!(Executor {}.execute(&select("users"), &mut db), Err(ExecutionError::TableNotFound)));
+        assert!(matches!(Executor {}.execute(&rename("people"), &mut db), Err(ExecutionError::TableNotFound)));
+    }
+
+    #[test]
+    fn test_min_max_on_indexed_column_reads_the_index() {
+        let mut db = create_range_fixture();
+        let select = |table: &str, columns: Vec<SelectColumn>| Statements::Select(SelectStatement {
+            from_table: table.to_string(),
+            columns,
+            where_clause: None,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+        let extremes = || vec![aggregate(AggregateFunc::Min, "age"), aggregate(AggregateFunc::Max, "age")];
+
+        let scanned = Executor {}.execute(&select("plain", extremes()), &mut db).unwrap();
+        // No row budget at all: only the index can answer.
+        db.set_executor_config(ExecutorConfig { max_steps: Some(0), ..ExecutorConfig::default() });
+        let indexed = Executor {}.execute(&select("indexed", extremes()), &mut db).unwrap();
+
+        assert_eq!(indexed.columns, vec!["MIN(age)".to_string(), "MAX(age)".to_string()]);
+        assert_eq!(indexed.rows, vec![Row { values: vec![Value::Integer(0), Value::Integer(100)] }]);
+        assert_eq!(indexed.rows, scanned.rows);
+        let with_count = vec![aggregate(AggregateFunc::Min, "age"), aggregate(AggregateFunc::Count, "age")];
+        assert!(matches!(Executor {}.execute(&select("indexed", with_count), &mut db), Err(ExecutionError::BudgetExceeded { .. })));
+    }
+
+    #[test]
+    fn test_create_table_default_applies_on_insert() {
+        let mut db = Database::new();
+        let create = |default: Literal| Statements::CreateTable(CreateTableStatement {
+            table_name: "tasks".to_string(),
+            columns: vec![
+                ColumnDefinition { name: "id".to_string(), data_type: DataType::Integer, constraints: vec![], default: None, check: None },
+                ColumnDefinition { name: "status".to_string(), data_type: DataType::Integer, constraints: vec![], default: Some(default), check: None },
+            ],
+        });
+
+        assert!(matches!(
+            Executor {}.execute(&create(Literal::String("done".to_string())), &mut db),
+            Err(ExecutionError::InvalidSchema(SchemaError::DefaultValueTypeMismatch { column_name })) if column_name == "status"
+        ));
+        Executor {}.execute(&create(Literal::Integer(0)), &mut db).unwrap();
+        let insert = Statements::Insert(InsertStatement {
+            table_name: "tasks".to_string(),
+            columns: vec!["id".to_string()],
+            values: vec![Literal::Integer(1)],
+        });
+        Executor {}.execute(&insert, &mut db).unwrap();
+
+        let table = db.get_table("tasks".to_string()).unwrap();
+        assert_eq!(table.first_row().map(|(_, row)| row.values.clone()), Some(vec![Value::Integer(1), Value::Integer(0)]));
+    }
+
+    fn select_from(table: &str, columns: Vec<SelectColumn>) -> Box<SelectStatement> {
+        Box::new(SelectStatement {
+            from_table: table.to_string(),
+            columns,
+            where_clause: None,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        })
+    }
+
+    #[test]
+    fn test_union_deduplicates_and_union_all_keeps_duplicates() {
+        let mut db = create_mock_db();
+        let ages = || select_from("users", vec![SelectColumn::Identifier("age".to_string())]);
+
+        let union = Statements::SetOperation(SetOperationStatement { left: ages(), right: ages(), all: false, order_by: Vec::new(), limit: None, offset: None });
+        let result = Executor {}.execute(&union, &mut db).unwrap();
+        assert_eq!(result.columns, vec!["age".to_string()]);
+        let values: Vec<Value> = result.rows.iter().map(|row| row.values[0].clone()).collect();
+        assert_eq!(values.len(), 2);
+        assert!(values.contains(&Value::Integer(30)) && values.contains(&Value::Integer(25)));
+
+        let union_all = Statements::SetOperation(SetOperationStatement { left: ages(), right: ages(), all: true, order_by: Vec::new(), limit: None, offset: None });
+        assert_eq!(Executor {}.execute(&union_all, &mut db).unwrap().rows.len(), 6);
+    }
+
+    #[test]
+    fn test_union_rejects_incompatible_sides() {
+        let mut db = create_mock_db();
+        let column = |name: &str| SelectColumn::Identifier(name.to_string());
+
+        let count_mismatch = Statements::SetOperation(SetOperationStatement {
+            left: select_from("users", vec![column("id")]),
+            right: select_from("users", vec![column("id"), column("age")]),
+            all: true,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+        });
+        assert!(matches!(
+            Executor {}.execute(&count_mismatch, &mut db),
+            Err(ExecutionError::UnionColumnCount { left: 1, right: 2 })
+        ));
+
+        let type_mismatch = Statements::SetOperation(SetOperationStatement {
+            left: select_from("users", vec![column("id")]),
+            right: select_from("users", vec![column("name")]),
+            all: true,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+        });
+        assert!(matches!(Executor {}.execute(&type_mismatch, &mut db), Err(ExecutionError::TypeMismatch)));
+
+        let missing_column = Statements::SetOperation(SetOperationStatement {
+            left: select_from("users", vec![column("id")]),
+            right: select_from("users", vec![column("missing")]),
+            all: true,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+        });
+        assert!(matches!(
+            Executor {}.execute(&missing_column, &mut db),
+            Err(ExecutionError::ColumnNotFound(name)) if name == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_show_tables_lists_table_names() {
+        let mut db = create_mock_db();
+        db.create_table("accounts".to_string(), users_schema()).unwrap();
+
+        let result = Executor {}.execute(&Statements::ShowTables, &mut db).unwrap();
+        assert_eq!(result.columns, vec!["table_name".to_string()]);
+        let names: Vec<Value> = result.rows.into_iter().flat_map(|row| row.values).collect();
+        assert_eq!(names, vec![Value::String("accounts".to_string()), Value::String("users".to_string())]);
+    }
+
+    #[test]
+    fn test_describe_lists_columns_in_schema_order() {
+        let mut db = create_mock_db();
+        let accounts = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).auto_increment().not_null().unique().build(),
+            ColumnBuilder::new("role", DataType::String).default(Value::String("guest".to_string())).unwrap().index().build(),
+        ])
+        .unwrap();
+        db.create_table("accounts".to_string(), accounts).unwrap();
+        let describe = |db: &mut Database, table: &str| {
+            let ast = Statements::Describe(DescribeStatement { table_name: table.to_string() });
+            Executor {}.execute(&ast, db)
+        };
+        let strings = |values: [&str; 3]| values.map(|value| Value::String(value.to_string())).to_vec();
+
+        let result = describe(&mut db, "users").unwrap();
+        assert_eq!(result.columns, vec!["name".to_string(), "data_type".to_string(), "constraints".to_string()]);
+        assert_eq!(
+            result.rows.into_iter().map(|row| row.values).collect::<Vec<_>>(),
+            vec![strings(["id", "INTEGER", ""]), strings(["name", "STRING", ""]), strings(["age", "INTEGER", ""])]
+        );
+
+        let result = describe(&mut db, "accounts").unwrap();
+        assert_eq!(
+            result.rows.into_iter().map(|row| row.values).collect::<Vec<_>>(),
+            vec![
+                strings(["id", "INTEGER", "NOT NULL UNIQUE AUTO_INCREMENT"]),
+                strings(["role", "STRING", "DEFAULT 'guest' INDEX"]),
+            ]
+        );
+
+        assert!(matches!(describe(&mut db, "missing"), Err(ExecutionError::TableNotFound)));
+    }
+
+    #[test]
+    fn test_repeated_projected_names_are_made_unique() {
+        let mut db = create_mock_db();
+        let column = |name: &str| SelectColumn::Identifier(name.to_string());
+
+        let ast = Statements::Select(*select_from("users", vec![column("name"), column("id"), column("name")]));
+        let result = Executor {}.execute(&ast, &mut db).unwrap();
+        assert_eq!(result.columns, vec!["name", "id", "name_1"]);
+        assert_eq!(result.rows[0].values[0], result.rows[0].values[2]);
+
+        // A generated suffix never clashes with a name the query already uses.
+        assert_eq!(
+            Executor::unique_column_names(["a", "a_1", "a", "a"].map(String::from).to_vec()),
+            vec!["a", "a_1", "a_2", "a_3"]
+        );
+        let counts = Statements::Select(*select_from("users", vec![aggregate(AggregateFunc::Count, "id"), aggregate(AggregateFunc::Count, "id")]));
+        assert_eq!(Executor {}.execute(&counts, &mut db).unwrap().columns, vec!["COUNT(id)", "COUNT(id)_1"]);
+    }
 }
@@ -7,4 +7,5 @@ pub mod column;
 pub mod constraint_state;
 pub mod tokenizer;
 pub mod parser;
-pub mod executor;
\ No newline at end of file
+pub mod executor;
+pub mod query;
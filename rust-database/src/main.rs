@@ -30,13 +30,16 @@ fn main() {
                     match kind {
                         ConstraintKind::NotNull => builder.not_null(),
                         ConstraintKind::Unique => builder.unique(),
-                        ConstraintKind::Default => {
+                        ConstraintKind::Default | ConstraintKind::Check => {
                             builder
                         },
                         ConstraintKind::Index => builder.index(),
+                        ConstraintKind::AutoIncrement => builder.auto_increment(),
                     }
                 }
 
+                Constraint::Check(predicate) => builder.check(predicate.clone()),
+
                 Constraint::WithValue(kind, val) => {
                     match kind {
                         ConstraintKind::Default => builder.default(val.clone()).expect("Default value type mismatch"),
@@ -1,6 +1,7 @@
 
 use crate::column::{DataType};
-use crate::tokenizer::{Token};
+use crate::constraint_state::ConstraintKind;
+use crate::tokenizer::{Span, SpannedToken, Token};
 use thiserror::Error;
 
 // ========================================================================================
@@ -13,6 +14,32 @@ pub enum ParserError {
 
     #[error("Invalid Integer '{0}' at position '{1}'")]
     InvalidInteger(String, usize),
+
+    #[error("Invalid Float '{0}' at position '{1}'")]
+    InvalidFloat(String, usize),
+
+    #[error("Expression nesting exceeds the maximum depth of {0}")]
+    NestingTooDeep(usize),
+
+    #[error("Insert lists {columns} columns but {values} values")]
+    InsertArityMismatch { columns: usize, values: usize },
+
+    /// The input ended, or held only a `;`, before any statement began.
+    #[error("Empty statement")]
+    EmptyStatement,
+}
+
+impl ParserError {
+    /// Index of the offending token, for errors that point at one.
+    #[must_use]
+    pub fn token_position(&self) -> Option<usize> {
+        match self {
+            Self::UnexpectedToken(_, position)
+            | Self::InvalidInteger(_, position)
+            | Self::InvalidFloat(_, position) => Some(*position),
+            Self::NestingTooDeep(_) | Self::InsertArityMismatch { .. } | Self::EmptyStatement => None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -20,22 +47,97 @@ pub enum Statements {
     Select(SelectStatement),
     Insert(InsertStatement),
     CreateTable(CreateTableStatement),
+    Truncate(TruncateStatement),
+    Delete(DeleteStatement),
+    Update(UpdateStatement),
+    DropTable(DropTableStatement),
+    AlterTable(AlterTableStatement),
+    SetOperation(SetOperationStatement),
+    /// `SHOW TABLES;`: one row per table name.
+    ShowTables,
+    Describe(DescribeStatement),
+}
+
+/// Whether `TRUNCATE` resets the table's identity sequence. `CONTINUE IDENTITY`
+/// is the default, matching standard SQL.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TruncateIdentity {
+    Restart,
+    #[default]
+    Continue,
+}
+
+/// Direction of an `ORDER BY` key. `ASC` is the default when none is given.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Where NULLs go in an `ORDER BY` key, whatever its direction. A key without
+/// `NULLS FIRST` or `NULLS LAST` uses `ExecutorConfig::nulls_order`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullsOrder {
+    First,
+    #[default]
+    Last,
 }
 
 #[derive(Debug, PartialEq)]
 pub enum SelectColumn {
     Wildcard,
     Identifier(String),
+    /// `func(column)`, or `COUNT(*)` when `arg` is `None`.
+    Aggregate { func: AggregateFunc, arg: Option<String> },
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFunc {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl AggregateFunc {
+    /// Matches a function name case-insensitively; aggregate names are not
+    /// keywords, so they stay usable as column names.
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_uppercase().as_str() {
+            "COUNT" => Some(AggregateFunc::Count),
+            "SUM" => Some(AggregateFunc::Sum),
+            "AVG" => Some(AggregateFunc::Avg),
+            "MIN" => Some(AggregateFunc::Min),
+            "MAX" => Some(AggregateFunc::Max),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for AggregateFunc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AggregateFunc::Count => write!(f, "COUNT"),
+            AggregateFunc::Sum => write!(f, "SUM"),
+            AggregateFunc::Avg => write!(f, "AVG"),
+            AggregateFunc::Min => write!(f, "MIN"),
+            AggregateFunc::Max => write!(f, "MAX"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Literal {
     String(String),
     Integer(i64),
+    Float(f64),
     Boolean(bool),
+    Null,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BinaryOperator {
     Equals,
     NotEquals,
@@ -45,15 +147,32 @@ pub enum BinaryOperator {
     LessThanOrEquals,
     And,
     Or,
+    /// SQL pattern match: `%` is any run of characters, `_` any one, `\` escapes.
+    Like,
+    /// `NOT LIKE`: the negation of `Like`, still unknown when either side is NULL.
+    NotLike,
 }
 
 // The main Expression enum
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Expression {
-    Literal(Literal),
-    Identifier(String),
+    /// The span is the literal's source range when parsed with `Parser::from_spanned`.
+    Literal(Literal, Option<Span>),
+    /// The span is the identifier's source range when parsed with `Parser::from_spanned`.
+    Identifier(String, Option<Span>),
     // We use Box to handle recursive data structures, preventing infinite size.
     Binary(Box<Expression>, BinaryOperator, Box<Expression>),
+    /// `expr IS NULL`, or `expr IS NOT NULL` when the flag is set.
+    IsNull(Box<Expression>, bool),
+    /// `expr IN (literal, ...)`, or `expr NOT IN (...)` when the flag is set. An
+    /// empty list matches nothing, so `NOT IN ()` matches everything.
+    InList(Box<Expression>, Vec<Literal>, bool),
+    /// `a IS DISTINCT FROM b`, or `IS NOT DISTINCT FROM` when the flag is set. A
+    /// NULL-safe comparison: two NULLs are not distinct, and the result is never unknown.
+    IsDistinctFrom(Box<Expression>, Box<Expression>, bool),
+    /// `expr BETWEEN low AND high`, inclusive at both ends.
+    Between { expr: Box<Expression>, low: Box<Expression>, high: Box<Expression> },
+    Not(Box<Expression>),
 }
 
 // ========================================================================================
@@ -63,7 +182,14 @@ pub enum Expression {
 pub struct SelectStatement {
     pub columns: Vec<SelectColumn>,
     pub from_table: String,
-    pub where_clause: Option<Expression>
+    pub where_clause: Option<Expression>,
+    pub order_by: Vec<(String, SortDirection, Option<NullsOrder>)>,
+    /// Set by `LIMIT n` or the standard `FETCH {FIRST | NEXT} n {ROW | ROWS} ONLY`.
+    pub limit: Option<usize>,
+    /// Set by `OFFSET n`, optionally followed by `ROW` or `ROWS`.
+    pub offset: Option<usize>,
+    /// `SELECT DISTINCT`: drop repeated result rows.
+    pub distinct: bool,
 }
 
 #[derive(Debug, PartialEq)]
@@ -73,13 +199,80 @@ pub struct InsertStatement {
     pub columns: Vec<String>,
 }
 
+#[derive(Debug, PartialEq)]
+pub struct DeleteStatement {
+    pub table_name: String,
+    pub where_clause: Option<Expression>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UpdateStatement {
+    pub table_name: String,
+    pub assignments: Vec<(String, Literal)>,
+    pub where_clause: Option<Expression>,
+}
+
+/// `DROP TABLE [IF EXISTS] name`. With `if_exists`, a missing table is not an error.
+#[derive(Debug, PartialEq)]
+pub struct DropTableStatement {
+    pub table_name: String,
+    pub if_exists: bool,
+}
+
+/// `left UNION [ALL] right`. Plain `UNION` drops duplicate rows; `all` keeps them.
+/// A trailing ORDER BY, LIMIT or OFFSET applies to the combined rows, so it is
+/// stored here; neither side has its own.
+#[derive(Debug, PartialEq)]
+pub struct SetOperationStatement {
+    pub left: Box<SelectStatement>,
+    pub right: Box<SelectStatement>,
+    pub all: bool,
+    pub order_by: Vec<(String, SortDirection, Option<NullsOrder>)>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AlterTableStatement {
+    pub table_name: String,
+    pub action: AlterTableAction,
+}
+
+/// The change an `ALTER TABLE` makes.
+#[derive(Debug, PartialEq)]
+pub enum AlterTableAction {
+    /// `DROP COLUMN name`
+    DropColumn(String),
+    /// `RENAME TO new_name`
+    RenameTo(String),
+    /// `ALTER COLUMN name TYPE data_type`
+    AlterColumnType { column: String, data_type: DataType },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DescribeStatement {
+    pub table_name: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TruncateStatement {
+    pub table_name: String,
+    pub identity: TruncateIdentity,
+}
+
 // Special Case for Create Tables
 // =============================================
 #[derive(Debug, PartialEq)]
 pub struct ColumnDefinition {
     pub name: String,
     pub data_type: DataType,
-    // Potentially add constraints like PRIMARY KEY, NOT NULL later
+    /// `NOT NULL`, `UNIQUE` and `AUTO_INCREMENT`, in the order written; `PRIMARY KEY`
+    /// adds the first two.
+    pub constraints: Vec<ConstraintKind>,
+    /// The `DEFAULT` literal, if one was given.
+    pub default: Option<Literal>,
+    /// The `CHECK (predicate)`, if one was given.
+    pub check: Option<Expression>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -89,9 +282,19 @@ pub struct CreateTableStatement {
 }
 // =============================================
 
+/// Default limit on parenthesis nesting, keeping recursion well clear of the stack limit.
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 64;
+
+/// Binding power of prefix NOT: above AND, so AND ends its operand, and equal to
+/// the comparisons' left power, so a comparison is taken in.
+const NOT_BINDING_POWER: u8 = 5;
+
 pub struct Parser { 
     tokens: Vec<Token>, 
     position: usize, // Track which token 
+    max_depth: usize,
+    depth: usize, // Current parenthesis nesting
+    spans: Vec<Span>, // Empty unless built from spanned tokens
 }
 
 // ==============================================================================
@@ -99,33 +302,163 @@ pub struct Parser {
 // ==============================================================================
 
 impl Parser { 
+    #[must_use]
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, position: 0 }
+        Self::with_max_depth(tokens, DEFAULT_MAX_NESTING_DEPTH)
+    }
+
+    #[must_use]
+    pub fn with_max_depth(tokens: Vec<Token>, max_depth: usize) -> Self {
+        Self { tokens, position: 0, max_depth, depth: 0, spans: Vec::new() }
+    }
+
+    /// Builds a parser that remembers where each token came from, so errors can
+    /// be mapped back to the source with `error_span`.
+    #[must_use]
+    pub fn from_spanned(tokens: Vec<SpannedToken>) -> Self {
+        let (tokens, spans) = tokens.into_iter().map(|spanned| (spanned.token, spanned.span)).unzip();
+        Self { spans, ..Self::new(tokens) }
     }
 
+    /// Source span of the token at `position`, if spans were provided.
+    #[must_use]
+    pub fn span_at(&self, position: usize) -> Option<Span> {
+        self.spans.get(position).copied()
+    }
+
+    /// Source span of the token an error points at.
+    #[must_use]
+    pub fn error_span(&self, error: &ParserError) -> Option<Span> {
+        error.token_position().and_then(|position| self.span_at(position))
+    }
+
+    /// Parses one statement, including its terminating semicolon.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` pointing at the first token that doesn't fit.
     pub fn parse_statement(&mut self) -> Result<Statements, ParserError> {
         let current_token = self.current_token()?.clone();
 
         match current_token {
             Token::Select => {
-                let select_stmt = self.parse_select_statement()?;
+                let select_stmt = self.parse_select_body()?;
+                if let Ok(Token::Union) = self.current_token() {
+                    let set_stmt = self.parse_union(select_stmt)?;
+                    return Ok(Statements::SetOperation(set_stmt));
+                }
+                self.expect_token(&Token::Semicolon)?;
                 Ok(Statements::Select(select_stmt))
             }
             Token::Insert => {
-                Err(ParserError::UnexpectedToken("INSERT".to_string(), self.position))
-            },
-            Token::CreateTable => {
-                Err(ParserError::UnexpectedToken("CREATE TABLE".to_string(), self.position))
-            },
+                let insert_stmt = self.parse_insert_statement()?;
+                Ok(Statements::Insert(insert_stmt))
+            }
+            Token::Create => {
+                let create_stmt = self.parse_create_table_statement()?;
+                Ok(Statements::CreateTable(create_stmt))
+            }
+            Token::Truncate => {
+                let truncate_stmt = self.parse_truncate_statement()?;
+                Ok(Statements::Truncate(truncate_stmt))
+            }
+            Token::Delete => {
+                let delete_stmt = self.parse_delete_statement()?;
+                Ok(Statements::Delete(delete_stmt))
+            }
+            Token::Update => {
+                let update_stmt = self.parse_update_statement()?;
+                Ok(Statements::Update(update_stmt))
+            }
+            Token::Drop => {
+                let drop_stmt = self.parse_drop_table_statement()?;
+                Ok(Statements::DropTable(drop_stmt))
+            }
+            Token::Alter => {
+                let alter_stmt = self.parse_alter_table_statement()?;
+                Ok(Statements::AlterTable(alter_stmt))
+            }
+            _ if self.at_keyword("SHOW") => {
+                self.parse_show_tables_statement()?;
+                Ok(Statements::ShowTables)
+            }
+            Token::Describe => {
+                let describe_stmt = self.parse_describe_statement()?;
+                Ok(Statements::Describe(describe_stmt))
+            }
+            Token::Eof | Token::Semicolon => Err(ParserError::EmptyStatement),
             _ => {
-                Err(ParserError::UnexpectedToken(format!("{:?}", current_token), self.position))
+                Err(ParserError::UnexpectedToken(format!("{current_token:?}"), self.position))
             }
         }        
     }
 
+    /// Parses every statement up to `Eof`, as in a script. Empty statements (stray
+    /// semicolons) between them are skipped, so empty input gives an empty list.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first statement's `ParserError`.
+    pub fn parse_program(&mut self) -> Result<Vec<Statements>, ParserError> {
+        let mut statements = Vec::new();
+        loop {
+            match self.current_token() {
+                Ok(Token::Semicolon) => {
+                    self.consume_token()?;
+                }
+                Ok(Token::Eof) | Err(_) => return Ok(statements),
+                Ok(_) => statements.push(self.parse_statement()?),
+            }
+        }
+    }
+
+    /// Parses a SELECT ending in a semicolon.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` pointing at the first token that doesn't fit.
     pub fn parse_select_statement(&mut self) -> Result<SelectStatement, ParserError> {
+        let select_stmt = self.parse_select_body()?;
+        self.expect_token(&Token::Semicolon)?;
+        Ok(select_stmt)
+    }
+
+    /// Parses `UNION [ALL] SELECT ... [ORDER BY ...] [LIMIT n] [OFFSET n];` following
+    /// `left`, which may not have an ORDER BY, LIMIT or OFFSET of its own.
+    fn parse_union(&mut self, left: SelectStatement) -> Result<SetOperationStatement, ParserError> {
+        if !left.order_by.is_empty() || left.limit.is_some() || left.offset.is_some() {
+            return Err(ParserError::UnexpectedToken(
+                "ORDER BY, LIMIT and OFFSET must follow the last SELECT of a UNION".to_string(),
+                self.position,
+            ));
+        }
+        self.expect_token(&Token::Union)?;
+        let all = self.consume_keyword("ALL");
+        if !matches!(self.current_token(), Ok(Token::Select)) {
+            let t = self.consume_token()?;
+            return Err(ParserError::UnexpectedToken(
+                format!("Expected SELECT after UNION, found {t:?}"),
+                self.position - 1,
+            ));
+        }
+        let mut right = self.parse_select_statement()?;
+
+        // The trailing clauses parse as part of the right SELECT but belong to the union.
+        let order_by = std::mem::take(&mut right.order_by);
+        let (limit, offset) = (right.limit.take(), right.offset.take());
+        Ok(SetOperationStatement { left: Box::new(left), right: Box::new(right), all, order_by, limit, offset })
+    }
+
+    /// Parses a SELECT up to, but not including, its terminating semicolon.
+    fn parse_select_body(&mut self) -> Result<SelectStatement, ParserError> {
         self.consume_token()?; // Consume SELECT token
 
+        let mut distinct = false;
+        if let Ok(Token::Distinct) = self.current_token() {
+            self.consume_token()?;
+            distinct = true;
+        }
+
         let columns = self.parse_select_columns()?;
 
         // Expect FROM
@@ -136,7 +469,7 @@ impl Parser {
             Token::Identifier(name) => name,
             t => {
                 return Err(ParserError::UnexpectedToken(
-                    format!("Expected table name, found {:?}", t),
+                    format!("Expected table name, found {t:?}"),
                     self.position - 1,
                 ))
             }
@@ -148,141 +481,825 @@ impl Parser {
             where_clause = Some(self.parse_expression()?);
         }
 
-        self.expect_token(&Token::Semicolon)?;
+        let mut order_by = Vec::new();
+        if let Ok(Token::Order) = self.current_token() {
+            self.consume_token()?;
+            self.expect_token(&Token::By)?;
+            order_by = self.parse_order_by_list()?;
+        }
+
+        let mut limit = None;
+        if let Ok(Token::Limit) = self.current_token() {
+            self.consume_token()?;
+            limit = Some(self.parse_row_count()?);
+        }
+
+        let mut offset = None;
+        if let Ok(Token::Offset) = self.current_token() {
+            self.consume_token()?;
+            offset = Some(self.parse_row_count()?);
+            if !self.consume_keyword("ROW") {
+                self.consume_keyword("ROWS");
+            }
+        }
+
+        if limit.is_none() && let Ok(Token::Fetch) = self.current_token() {
+            limit = Some(self.parse_fetch_first()?);
+        }
 
         Ok(SelectStatement {
             columns,
             from_table,
             where_clause,
+            order_by,
+            limit,
+            offset,
+            distinct,
         })
     }
     
-    /// Parses the column part of a SELECT statement 
-    fn parse_select_columns(&mut self) -> Result<Vec<SelectColumn>, ParserError> {
-        let mut columns = vec![];
+    /// Parses `INSERT INTO name [(column, ...)] VALUES (literal, ...);`. Without a
+    /// column list the values are taken in schema order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` pointing at the first token that doesn't fit.
+    pub fn parse_insert_statement(&mut self) -> Result<InsertStatement, ParserError> {
+        self.consume_token()?; // Consume INSERT token
+        self.expect_token(&Token::Into)?;
 
-        // Handle wildcard *
-        if let Ok(Token::Asterisk) = self.current_token() {
+        let table_name = self.expect_identifier("table name")?;
+
+        let mut columns = Vec::new();
+        if let Ok(Token::OpenBracket) = self.current_token() {
             self.consume_token()?;
-            columns.push(SelectColumn::Wildcard);
-            return Ok(columns);
+            loop {
+                columns.push(self.expect_identifier("column name")?);
+                if let Ok(Token::Comma) = self.current_token() {
+                    self.consume_token()?;
+                } else {
+                    break;
+                }
+            }
+            self.expect_token(&Token::CloseBracket)?;
         }
 
-        // Handle one or more comma-separated identifiers
+        self.expect_token(&Token::Values)?;
+        self.expect_token(&Token::OpenBracket)?;
+        let mut values = Vec::new();
         loop {
-            match self.consume_token()? {
-                Token::Identifier(name) => columns.push(SelectColumn::Identifier(name)),
-                t => {
-                    return Err(ParserError::UnexpectedToken(
-                        format!("Expected column name or '*', found {:?}", t),
-                        self.position - 1,
-                    ))
-                }
+            // An omitted value already takes the column's default, so `DEFAULT` is
+            // read as NULL.
+            if let Ok(Token::Default) = self.current_token() {
+                self.consume_token()?;
+                values.push(Literal::Null);
+            } else {
+                values.push(self.parse_literal()?);
             }
-            // If the next token is not a comma, we're done with columns
             if let Ok(Token::Comma) = self.current_token() {
                 self.consume_token()?;
             } else {
                 break;
             }
         }
-        Ok(columns)
+        self.expect_token(&Token::CloseBracket)?;
+        self.expect_token(&Token::Semicolon)?;
+
+        if !columns.is_empty() && columns.len() != values.len() {
+            return Err(ParserError::InsertArityMismatch { columns: columns.len(), values: values.len() });
+        }
+
+        Ok(InsertStatement { table_name, values, columns })
     }
 
+    /// Parses `CREATE TABLE name (column TYPE, ...);`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` pointing at the first token that doesn't fit.
+    pub fn parse_create_table_statement(&mut self) -> Result<CreateTableStatement, ParserError> {
+        self.consume_token()?; // Consume CREATE token
+        self.expect_token(&Token::Table)?;
 
-    fn parse_expression(&mut self) -> Result<Expression, ParserError> {
-        let left = match self.consume_token()? {
-            Token::Identifier(name) => Expression::Identifier(name),
+        let table_name = self.expect_identifier("table name")?;
+        self.expect_token(&Token::OpenBracket)?;
+
+        let mut columns = Vec::new();
+        loop {
+            let name = self.expect_identifier("column name")?;
+            let data_type = self.parse_data_type()?;
+            let mut column = ColumnDefinition { name, data_type, constraints: Vec::new(), default: None, check: None };
+            self.parse_column_constraints(&mut column)?;
+            columns.push(column);
+
+            if let Ok(Token::Comma) = self.current_token() {
+                self.consume_token()?;
+            } else {
+                break;
+            }
+        }
+
+        self.expect_token(&Token::CloseBracket)?;
+        self.expect_token(&Token::Semicolon)?;
+
+        Ok(CreateTableStatement { table_name, columns })
+    }
+
+    /// Parses a type name such as `INTEGER`, case-insensitively.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnexpectedToken` for a name that isn't a type.
+    pub fn parse_data_type(&mut self) -> Result<DataType, ParserError> {
+        let type_position = self.position;
+        let type_name = self.expect_identifier("data type")?;
+        match type_name.to_uppercase().as_str() {
+            "INTEGER" => Ok(DataType::Integer),
+            "STRING" => Ok(DataType::String),
+            "FLOAT" => Ok(DataType::Float),
+            "BOOLEAN" => Ok(DataType::Boolean),
+            _ => Err(ParserError::UnexpectedToken(format!("Unknown data type {type_name}"), type_position)),
+        }
+    }
+
+    /// Reads the constraint keywords following a column's type, until a comma or
+    /// closing bracket. Repeats are kept only once; a repeated DEFAULT or CHECK keeps the last.
+    fn parse_column_constraints(&mut self, column: &mut ColumnDefinition) -> Result<(), ParserError> {
+        loop {
+            let kinds: &[ConstraintKind] = match self.current_token()? {
+                Token::Not => {
+                    self.consume_token()?;
+                    self.expect_token(&Token::Null)?;
+                    &[ConstraintKind::NotNull]
+                }
+                Token::Unique => {
+                    self.consume_token()?;
+                    &[ConstraintKind::Unique]
+                }
+                _ if self.at_keyword("PRIMARY") => {
+                    self.consume_token()?;
+                    self.expect_keyword("KEY")?;
+                    &[ConstraintKind::NotNull, ConstraintKind::Unique]
+                }
+                _ if self.at_keyword("AUTO_INCREMENT") => {
+                    self.consume_token()?;
+                    &[ConstraintKind::AutoIncrement]
+                }
+                Token::Default => {
+                    self.consume_token()?;
+                    column.default = Some(self.parse_literal()?);
+                    continue;
+                }
+                _ if self.at_keyword("CHECK") => {
+                    self.consume_token()?;
+                    if !matches!(self.current_token(), Ok(Token::OpenBracket)) {
+                        return Err(ParserError::UnexpectedToken("Expected ( after CHECK".to_string(), self.position));
+                    }
+                    column.check = Some(self.parse_parenthesized_expression()?);
+                    continue;
+                }
+                _ => return Ok(()),
+            };
+            for kind in kinds {
+                if !column.constraints.contains(kind) {
+                    column.constraints.push(*kind);
+                }
+            }
+        }
+    }
+
+    /// Parses `DROP TABLE [IF EXISTS] name;`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` pointing at the first token that doesn't fit.
+    pub fn parse_drop_table_statement(&mut self) -> Result<DropTableStatement, ParserError> {
+        self.consume_token()?; // Consume DROP token
+        self.expect_token(&Token::Table)?;
+
+        // IF only starts `IF EXISTS` when EXISTS follows, so a table can be named `if`.
+        let if_exists = self.at_keyword("IF")
+            && matches!(self.tokens.get(self.position + 1), Some(Token::Identifier(word)) if word.eq_ignore_ascii_case("EXISTS"));
+        if if_exists {
+            self.position += 2;
+        }
+
+        let table_name = self.expect_identifier("table name")?;
+        self.expect_token(&Token::Semicolon)?;
+
+        Ok(DropTableStatement { table_name, if_exists })
+    }
+
+    /// Parses `ALTER TABLE name DROP COLUMN column;` or `ALTER TABLE name RENAME TO new_name;`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` pointing at the first token that doesn't fit.
+    pub fn parse_alter_table_statement(&mut self) -> Result<AlterTableStatement, ParserError> {
+        self.consume_token()?; // Consume ALTER token
+        self.expect_token(&Token::Table)?;
+        let table_name = self.expect_identifier("table name")?;
+
+        let action = match self.consume_token()? {
+            Token::Drop => {
+                self.expect_keyword("COLUMN")?;
+                AlterTableAction::DropColumn(self.expect_identifier("column name")?)
+            }
+            Token::Identifier(word) if word.eq_ignore_ascii_case("RENAME") => {
+                self.expect_keyword("TO")?;
+                AlterTableAction::RenameTo(self.expect_identifier("new table name")?)
+            }
+            Token::Alter => {
+                self.expect_keyword("COLUMN")?;
+                let column = self.expect_identifier("column name")?;
+                // TYPE isn't a keyword, so it stays usable as a column name.
+                let type_position = self.position;
+                if !self.expect_identifier("TYPE")?.eq_ignore_ascii_case("TYPE") {
+                    return Err(ParserError::UnexpectedToken("Expected TYPE".to_string(), type_position));
+                }
+                AlterTableAction::AlterColumnType { column, data_type: self.parse_data_type()? }
+            }
             t => {
                 return Err(ParserError::UnexpectedToken(
-                    format!("Expected identifier in expression, found {:?}", t),
+                    format!("Expected ALTER TABLE action, found {t:?}"),
                     self.position - 1,
                 ))
             }
         };
+        self.expect_token(&Token::Semicolon)?;
 
-        // Operator
-        let op = self.match_binary_operator()?;
+        Ok(AlterTableStatement { table_name, action })
+    }
 
-        // Right-hand side (expecting a literal)
-        let right = match self.consume_token()? {
-            Token::StringLiteral(s) => Expression::Literal(Literal::String(s)),
-            Token::NumericLiteral(n) => {
-                let val = n.parse::<i64>().map_err(|_| {
-                    ParserError::InvalidInteger(n.clone(), self.position - 1)
-                })?;
-                Expression::Literal(Literal::Integer(val))
-            }
+    /// Parses `SHOW TABLES;`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` pointing at the first token that doesn't fit.
+    pub fn parse_show_tables_statement(&mut self) -> Result<(), ParserError> {
+        self.consume_token()?; // Consume SHOW token
+        self.expect_keyword("TABLES")?;
+        self.expect_token(&Token::Semicolon)?;
+        Ok(())
+    }
+
+    /// Parses `DESCRIBE name;`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` pointing at the first token that doesn't fit.
+    pub fn parse_describe_statement(&mut self) -> Result<DescribeStatement, ParserError> {
+        self.consume_token()?; // Consume DESCRIBE token
+        let table_name = self.expect_identifier("table name")?;
+        self.expect_token(&Token::Semicolon)?;
+
+        Ok(DescribeStatement { table_name })
+    }
+
+    /// Parses `TRUNCATE [TABLE] name [RESTART IDENTITY | CONTINUE IDENTITY];`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` pointing at the first token that doesn't fit.
+    pub fn parse_truncate_statement(&mut self) -> Result<TruncateStatement, ParserError> {
+        self.consume_token()?; // Consume TRUNCATE token
+
+        if let Ok(Token::Table) = self.current_token() {
+            self.consume_token()?;
+        }
+
+        let table_name = match self.consume_token()? {
+            Token::Identifier(name) => name,
             t => {
                 return Err(ParserError::UnexpectedToken(
-                    format!("Expected literal in expression, found {:?}", t),
+                    format!("Expected table name, found {t:?}"),
                     self.position - 1,
                 ))
             }
         };
 
-        Ok(Expression::Binary(Box::new(left), op, Box::new(right)))
+        let identity = if self.consume_keyword("RESTART") {
+            self.expect_keyword("IDENTITY")?;
+            TruncateIdentity::Restart
+        } else if self.consume_keyword("CONTINUE") {
+            self.expect_keyword("IDENTITY")?;
+            TruncateIdentity::Continue
+        } else {
+            TruncateIdentity::default()
+        };
+
+        self.expect_token(&Token::Semicolon)?;
+
+        Ok(TruncateStatement { table_name, identity })
     }
 
+    /// Parses `DELETE FROM name [WHERE expression];`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` pointing at the first token that doesn't fit.
+    pub fn parse_delete_statement(&mut self) -> Result<DeleteStatement, ParserError> {
+        self.consume_token()?; // Consume DELETE token
+        self.expect_token(&Token::From)?;
 
-    // ==============================================================================
-    // UTILITY FUNCTIONS
-    // ==============================================================================
+        let table_name = self.expect_identifier("table name")?;
 
-    /// Consumes the current token only if it matches the expected one
-    fn expect_token(&mut self, expected: &Token) -> Result<Token, ParserError> {
-        let token = self.consume_token()?;
-        if &token == expected {
-            Ok(token)
-        } else {
-            Err(ParserError::UnexpectedToken(
-                format!("Expected {:?}, found {:?}", expected, token),
-                self.position - 1,
-            ))
+        let mut where_clause = None;
+        if let Ok(Token::Where) = self.current_token() {
+            self.consume_token()?;
+            where_clause = Some(self.parse_expression()?);
         }
-    }
 
-    pub fn current_token(&self) -> Result<&Token, ParserError> {
-        if self.position < self.tokens.len() {
-            Ok(&self.tokens[self.position])
-        } else {
-            Err(ParserError::UnexpectedToken("End of input".to_string(), self.position))
-        }
+        self.expect_token(&Token::Semicolon)?;
+
+        Ok(DeleteStatement { table_name, where_clause })
     }
 
-    pub fn consume_token(&mut self) -> Result<Token, ParserError> {
-        if self.position < self.tokens.len() {
-            let token = self.tokens[self.position].clone(); // Clone to return by value
-            self.position += 1;
-            Ok(token)
-        } else {
-            Err(ParserError::UnexpectedToken("End of input".to_string(), self.position))
+    /// Parses `UPDATE name SET column = literal, ... [WHERE expression];`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` pointing at the first token that doesn't fit.
+    pub fn parse_update_statement(&mut self) -> Result<UpdateStatement, ParserError> {
+        self.consume_token()?; // Consume UPDATE token
+
+        let table_name = self.expect_identifier("table name")?;
+        self.expect_keyword("SET")?;
+
+        let mut assignments = Vec::new();
+        loop {
+            let column = self.expect_identifier("column name")?;
+            self.expect_token(&Token::Equals)?;
+            assignments.push((column, self.parse_literal()?));
+
+            if let Ok(Token::Comma) = self.current_token() {
+                self.consume_token()?;
+            } else {
+                break;
+            }
         }
-    }
 
-    fn match_binary_operator(&mut self) -> Result<BinaryOperator, ParserError> {
-        let token = self.consume_token()?; // Consume the operator token
-        match token {
-            Token::Equals => Ok(BinaryOperator::Equals),
-            Token::NotEquals => Ok(BinaryOperator::NotEquals),
-            Token::GreaterThan => Ok(BinaryOperator::GreaterThan),
-            Token::LessThan => Ok(BinaryOperator::LessThan),
-            Token::GreaterThanOrEquals => Ok(BinaryOperator::GreaterThanOrEquals),
-            Token::LessThanOrEquals => Ok(BinaryOperator::LessThanOrEquals),
-            Token::And => Ok(BinaryOperator::And),
-            Token::Or => Ok(BinaryOperator::Or),
-            t => Err(ParserError::UnexpectedToken(
-                format!("Expected binary operator, found {:?}", t),
-                self.position - 1,
-            )),
+        let mut where_clause = None;
+        if let Ok(Token::Where) = self.current_token() {
+            self.consume_token()?;
+            where_clause = Some(self.parse_expression()?);
         }
-    }
 
+        self.expect_token(&Token::Semicolon)?;
 
-}
+        Ok(UpdateStatement { table_name, assignments, where_clause })
+    }
 
-// ==============================================================================
-// TESTS
+    /// Parses the column part of a SELECT statement 
+    fn parse_select_columns(&mut self) -> Result<Vec<SelectColumn>, ParserError> {
+        let mut columns = vec![];
+
+        // Handle wildcard *
+        if let Ok(Token::Asterisk) = self.current_token() {
+            self.consume_token()?;
+            columns.push(SelectColumn::Wildcard);
+            return Ok(columns);
+        }
+
+        // Handle one or more comma-separated identifiers
+        loop {
+            match self.consume_token()? {
+                Token::Identifier(name) => {
+                    let func = AggregateFunc::from_name(&name);
+                    match (func, self.current_token()) {
+                        (Some(func), Ok(Token::OpenBracket)) => columns.push(self.parse_aggregate(func)?),
+                        _ => columns.push(SelectColumn::Identifier(name)),
+                    }
+                }
+                Token::Asterisk => {
+                    return Err(ParserError::UnexpectedToken(
+                        "'*' must be the only column in the select list".to_string(),
+                        self.position - 1,
+                    ))
+                }
+                t => {
+                    return Err(ParserError::UnexpectedToken(
+                        format!("Expected column name or '*', found {t:?}"),
+                        self.position - 1,
+                    ))
+                }
+            }
+            // If the next token is not a comma, we're done with columns
+            if let Ok(Token::Comma) = self.current_token() {
+                self.consume_token()?;
+            } else {
+                break;
+            }
+        }
+        Ok(columns)
+    }
+
+
+    /// Parses the `(column)` or `(*)` argument of an aggregate call. Only `COUNT`
+    /// accepts `*`.
+    fn parse_aggregate(&mut self, func: AggregateFunc) -> Result<SelectColumn, ParserError> {
+        self.expect_token(&Token::OpenBracket)?;
+
+        let arg = match self.consume_token()? {
+            Token::Asterisk if func == AggregateFunc::Count => None,
+            Token::Asterisk => {
+                return Err(ParserError::UnexpectedToken(
+                    format!("'*' is only allowed in COUNT(*), not {func}(*)"),
+                    self.position - 1,
+                ))
+            }
+            Token::Identifier(name) => Some(name),
+            t => {
+                return Err(ParserError::UnexpectedToken(
+                    format!("Expected column name in {func}(), found {t:?}"),
+                    self.position - 1,
+                ))
+            }
+        };
+
+        self.expect_token(&Token::CloseBracket)?;
+        Ok(SelectColumn::Aggregate { func, arg })
+    }
+
+    /// Parses the `column [ASC | DESC] [NULLS {FIRST | LAST}], ...` list following `ORDER BY`
+    fn parse_order_by_list(&mut self) -> Result<Vec<(String, SortDirection, Option<NullsOrder>)>, ParserError> {
+        let mut order_by = Vec::new();
+        loop {
+            let column = self.expect_identifier("column name")?;
+            let direction = match self.current_token() {
+                Ok(Token::Asc) => {
+                    self.consume_token()?;
+                    SortDirection::Asc
+                }
+                Ok(Token::Desc) => {
+                    self.consume_token()?;
+                    SortDirection::Desc
+                }
+                _ => SortDirection::default(),
+            };
+            let nulls = if self.consume_keyword("NULLS") {
+                if self.consume_keyword("FIRST") {
+                    Some(NullsOrder::First)
+                } else if self.consume_keyword("LAST") {
+                    Some(NullsOrder::Last)
+                } else {
+                    return Err(ParserError::UnexpectedToken(
+                        format!("Expected FIRST or LAST after NULLS, found {:?}", self.current_token()?),
+                        self.position,
+                    ));
+                }
+            } else {
+                None
+            };
+            order_by.push((column, direction, nulls));
+
+            if let Ok(Token::Comma) = self.current_token() {
+                self.consume_token()?;
+            } else {
+                break;
+            }
+        }
+        Ok(order_by)
+    }
+
+    /// Parses `FETCH {FIRST | NEXT} n {ROW | ROWS} ONLY`, the standard spelling of `LIMIT n`
+    fn parse_fetch_first(&mut self) -> Result<usize, ParserError> {
+        self.consume_token()?; // Consume FETCH token
+
+        if !self.consume_keyword("FIRST") && !self.consume_keyword("NEXT") {
+            return Err(ParserError::UnexpectedToken(
+                format!("Expected FIRST or NEXT, found {:?}", self.current_token()?),
+                self.position,
+            ));
+        }
+        let count = self.parse_row_count()?;
+        if !self.consume_keyword("ROW") && !self.consume_keyword("ROWS") {
+            return Err(ParserError::UnexpectedToken(
+                format!("Expected ROW or ROWS, found {:?}", self.current_token()?),
+                self.position,
+            ));
+        }
+        self.expect_keyword("ONLY")?;
+
+        Ok(count)
+    }
+
+    /// Parses the non-negative integer following `LIMIT` or `OFFSET`
+    fn parse_row_count(&mut self) -> Result<usize, ParserError> {
+        match self.consume_token()? {
+            Token::NumericLiteral(n) => n
+                .parse::<usize>()
+                .map_err(|_| ParserError::InvalidInteger(n.clone(), self.position - 1)),
+            t => Err(ParserError::UnexpectedToken(
+                format!("Expected row count, found {t:?}"),
+                self.position - 1,
+            )),
+        }
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression, ParserError> {
+        self.parse_expression_with_binding_power(0)
+    }
+
+    /// Precedence climbing: keeps folding operators into `left` while they bind at
+    /// least as tightly as `min_power`. Chains of one operator loop rather than
+    /// recurse, so they associate to the left without growing the stack.
+    fn parse_expression_with_binding_power(&mut self, min_power: u8) -> Result<Expression, ParserError> {
+        let mut left = if let Ok(Token::Not) = self.current_token() {
+            self.parse_not()?
+        } else {
+            self.parse_operand()?
+        };
+
+        while let Some((op, width)) = self.next_binary_operator() {
+            let (left_power, right_power) = Self::binding_power(&op);
+            if left_power < min_power {
+                break;
+            }
+            for _ in 0..width {
+                self.consume_token()?; // Consume the operator
+            }
+            let right = self.parse_expression_with_binding_power(right_power)?;
+            left = Expression::Binary(Box::new(left), op, Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    /// Parses `NOT expr`. NOT takes in comparisons but stops at AND/OR, so
+    /// `NOT a = 1 AND b = 2` is `(NOT a = 1) AND b = 2`. Stacked NOTs count toward
+    /// the nesting limit, like parentheses.
+    fn parse_not(&mut self) -> Result<Expression, ParserError> {
+        self.consume_token()?; // Consume NOT
+        if self.depth >= self.max_depth {
+            return Err(ParserError::NestingTooDeep(self.max_depth));
+        }
+
+        self.depth += 1;
+        let operand = self.parse_expression_with_binding_power(NOT_BINDING_POWER);
+        self.depth -= 1;
+
+        Ok(Expression::Not(Box::new(operand?)))
+    }
+
+    /// A parenthesized expression, a column (optionally followed by `IS [NOT] NULL`,
+    /// `IS [NOT] DISTINCT FROM operand`, `BETWEEN low AND high` or `IN (...)`), or a
+    /// literal.
+    fn parse_operand(&mut self) -> Result<Expression, ParserError> {
+        match self.current_token()? {
+            Token::OpenBracket => return self.parse_parenthesized_expression(),
+            Token::Identifier(_) => {}
+            Token::StringLiteral(_)
+            | Token::NumericLiteral(_)
+            | Token::FloatLiteral(_)
+            | Token::BooleanLiteral(_)
+            | Token::Null => {
+                let span = self.span_at(self.position);
+                return Ok(Expression::Literal(self.parse_literal()?, span));
+            }
+            t => {
+                return Err(ParserError::UnexpectedToken(
+                    format!("Expected expression, found {t:?}"),
+                    self.position,
+                ))
+            }
+        }
+
+        let span = self.span_at(self.position);
+        let left = Expression::Identifier(self.expect_identifier("identifier")?, span);
+
+        if let Ok(Token::Is) = self.current_token() {
+            self.consume_token()?; // Consume IS
+            let negated = matches!(self.current_token(), Ok(Token::Not));
+            if negated {
+                self.consume_token()?;
+            }
+            if let Ok(Token::Distinct) = self.current_token() {
+                self.consume_token()?;
+                self.expect_token(&Token::From)?;
+                let right = self.parse_operand()?;
+                return Ok(Expression::IsDistinctFrom(Box::new(left), Box::new(right), negated));
+            }
+            self.expect_token(&Token::Null)?;
+            return Ok(Expression::IsNull(Box::new(left), negated));
+        }
+
+        if let Ok(Token::Between) = self.current_token() {
+            self.consume_token()?; // Consume BETWEEN
+            let low = self.parse_operand()?;
+            self.expect_token(&Token::And)?;
+            let high = self.parse_operand()?;
+            return Ok(Expression::Between { expr: Box::new(left), low: Box::new(low), high: Box::new(high) });
+        }
+
+        let negated = matches!(self.current_token(), Ok(Token::Not)) && matches!(self.tokens.get(self.position + 1), Some(Token::In));
+        if negated {
+            self.consume_token()?; // Consume NOT
+        }
+        if let Ok(Token::In) = self.current_token() {
+            self.consume_token()?; // Consume IN
+            return Ok(Expression::InList(Box::new(left), self.parse_literal_list()?, negated));
+        }
+
+        Ok(left)
+    }
+
+    /// Parses `( literal, ... )`, allowing an empty list.
+    fn parse_literal_list(&mut self) -> Result<Vec<Literal>, ParserError> {
+        self.expect_token(&Token::OpenBracket)?;
+        let mut literals = Vec::new();
+        if let Ok(Token::CloseBracket) = self.current_token() {
+            self.consume_token()?;
+            return Ok(literals);
+        }
+        loop {
+            literals.push(self.parse_literal()?);
+            match self.consume_token()? {
+                Token::Comma => {}
+                Token::CloseBracket => return Ok(literals),
+                t => {
+                    return Err(ParserError::UnexpectedToken(
+                        format!("Expected ',' or ')' in IN list, found {t:?}"),
+                        self.position - 1,
+                    ))
+                }
+            }
+        }
+    }
+
+    fn parse_literal(&mut self) -> Result<Literal, ParserError> {
+        match self.consume_token()? {
+            Token::StringLiteral(s) => Ok(Literal::String(s)),
+            Token::NumericLiteral(n) => {
+                let val = n.parse::<i64>().map_err(|_| {
+                    ParserError::InvalidInteger(n.clone(), self.position - 1)
+                })?;
+                Ok(Literal::Integer(val))
+            }
+            Token::FloatLiteral(n) => {
+                let val = n.parse::<f64>().map_err(|_| {
+                    ParserError::InvalidFloat(n.clone(), self.position - 1)
+                })?;
+                Ok(Literal::Float(val))
+            }
+            Token::BooleanLiteral(b) => Ok(Literal::Boolean(b)),
+            Token::Null => Ok(Literal::Null),
+            t => Err(ParserError::UnexpectedToken(
+                format!("Expected literal, found {t:?}"),
+                self.position - 1,
+            )),
+        }
+    }
+
+
+    /// Parses `( expression )`, refusing to nest deeper than `max_depth` so hostile
+    /// input can't overflow the stack.
+    fn parse_parenthesized_expression(&mut self) -> Result<Expression, ParserError> {
+        self.consume_token()?; // Consume '('
+        if self.depth >= self.max_depth {
+            return Err(ParserError::NestingTooDeep(self.max_depth));
+        }
+
+        self.depth += 1;
+        let expression = self.parse_expression();
+        self.depth -= 1;
+
+        let expression = expression?;
+        self.expect_token(&Token::CloseBracket)?;
+        Ok(expression)
+    }
+
+
+    // ==============================================================================
+    // UTILITY FUNCTIONS
+    // ==============================================================================
+
+    /// Consumes the current token only if it matches the expected one
+    fn expect_token(&mut self, expected: &Token) -> Result<Token, ParserError> {
+        let token = self.consume_token()?;
+        if &token == expected {
+            Ok(token)
+        } else {
+            Err(ParserError::UnexpectedToken(
+                format!("Expected {expected:?}, found {token:?}"),
+                self.position - 1,
+            ))
+        }
+    }
+
+    /// Consumes an identifier, naming what was expected in the error otherwise
+    fn expect_identifier(&mut self, what: &str) -> Result<String, ParserError> {
+        match self.consume_token()? {
+            Token::Identifier(name) => Ok(name),
+            t => Err(ParserError::UnexpectedToken(
+                format!("Expected {what}, found {t:?}"),
+                self.position - 1,
+            )),
+        }
+    }
+
+    /// Whether the current token is the non-reserved keyword `keyword`. Such words
+    /// are tokenized as identifiers, so they stay usable as names and only act as
+    /// keywords where the grammar expects one.
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.current_token(), Ok(Token::Identifier(name)) if name.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Consumes the non-reserved keyword `keyword` if it comes next.
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.at_keyword(keyword);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    /// Consumes the non-reserved keyword `keyword`, naming it in the error otherwise.
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), ParserError> {
+        if self.consume_keyword(keyword) {
+            return Ok(());
+        }
+        Err(ParserError::UnexpectedToken(
+            format!("Expected {keyword}, found {:?}", self.current_token()?),
+            self.position,
+        ))
+    }
+
+    /// The token at the current position.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnexpectedToken` past the end of the input.
+    pub fn current_token(&self) -> Result<&Token, ParserError> {
+        if self.position < self.tokens.len() {
+            Ok(&self.tokens[self.position])
+        } else {
+            Err(ParserError::UnexpectedToken("End of input".to_string(), self.position))
+        }
+    }
+
+    /// Returns the current token and moves past it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnexpectedToken` past the end of the input.
+    pub fn consume_token(&mut self) -> Result<Token, ParserError> {
+        if self.position < self.tokens.len() {
+            let token = self.tokens[self.position].clone(); // Clone to return by value
+            self.position += 1;
+            Ok(token)
+        } else {
+            Err(ParserError::UnexpectedToken("End of input".to_string(), self.position))
+        }
+    }
+
+    /// The binary operator at the current position and how many tokens it spans:
+    /// two for `NOT LIKE`, one otherwise.
+    fn next_binary_operator(&self) -> Option<(BinaryOperator, usize)> {
+        match (self.tokens.get(self.position)?, self.tokens.get(self.position + 1)) {
+            (Token::Not, Some(Token::Like)) => Some((BinaryOperator::NotLike, 2)),
+            (token, _) => Self::binary_operator(token).map(|op| (op, 1)),
+        }
+    }
+
+    fn binary_operator(token: &Token) -> Option<BinaryOperator> {
+        match token {
+            Token::Equals => Some(BinaryOperator::Equals),
+            Token::NotEquals => Some(BinaryOperator::NotEquals),
+            Token::GreaterThan => Some(BinaryOperator::GreaterThan),
+            Token::LessThan => Some(BinaryOperator::LessThan),
+            Token::GreaterThanOrEquals => Some(BinaryOperator::GreaterThanOrEquals),
+            Token::LessThanOrEquals => Some(BinaryOperator::LessThanOrEquals),
+            Token::And => Some(BinaryOperator::And),
+            Token::Or => Some(BinaryOperator::Or),
+            Token::Like => Some(BinaryOperator::Like),
+            _ => None,
+        }
+    }
+
+    /// Left and right binding power of an operator. OR binds loosest, then AND, then
+    /// NOT (see `NOT_BINDING_POWER`), then comparisons; the right power being higher
+    /// makes each level left-associative.
+    fn binding_power(op: &BinaryOperator) -> (u8, u8) {
+        match op {
+            BinaryOperator::Or => (1, 2),
+            BinaryOperator::And => (3, 4),
+            BinaryOperator::Equals
+            | BinaryOperator::NotEquals
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::LessThan
+            | BinaryOperator::GreaterThanOrEquals
+            | BinaryOperator::LessThanOrEquals
+            | BinaryOperator::Like
+            | BinaryOperator::NotLike => (5, 6),
+        }
+    }
+
+
+}
+
+// ==============================================================================
+// TESTS
 // ==============================================================================
 // The Parser will be taking in a Vec of tokens.
 // We need to just pass them in and expect an AST out. 
@@ -316,10 +1333,14 @@ mod tests {
             columns: vec![SelectColumn::Wildcard],
             from_table: "table".to_string(),
             where_clause: Some(Expression::Binary(
-                Box::new(Expression::Identifier("name".to_string())),
+                Box::new(Expression::Identifier("name".to_string(), None)),
                 BinaryOperator::Equals,
-                Box::new(Expression::Literal(Literal::String("PHILIP".to_string()))),
+                Box::new(Expression::Literal(Literal::String("PHILIP".to_string()), None)),
             )),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
         });
 
         assert_eq!(statement, expected_statement);
@@ -349,6 +1370,10 @@ mod tests {
             ],
             from_table: "my_table".to_string(),
             where_clause: None,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
         });
 
         assert_eq!(statement, expected_statement);
@@ -376,44 +1401,101 @@ mod tests {
             columns: vec![SelectColumn::Wildcard],
             from_table: "users".to_string(),
             where_clause: Some(Expression::Binary(
-                Box::new(Expression::Identifier("id".to_string())),
+                Box::new(Expression::Identifier("id".to_string(), None)),
                 BinaryOperator::Equals,
-                Box::new(Expression::Literal(Literal::Integer(123))),
+                Box::new(Expression::Literal(Literal::Integer(123), None)),
             )),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
         });
 
         assert_eq!(statement, expected_statement);
     }
 
+    fn insert_tokens(columns: &[&str], values: Vec<Token>) -> Vec<Token> {
+        let mut tokens = vec![Token::Insert, Token::Into, Token::Identifier("my_table".to_string())];
+        if !columns.is_empty() {
+            tokens.push(Token::OpenBracket);
+            for (i, column) in columns.iter().enumerate() {
+                if i > 0 {
+                    tokens.push(Token::Comma);
+                }
+                tokens.push(Token::Identifier((*column).to_string()));
+            }
+            tokens.push(Token::CloseBracket);
+        }
+        tokens.extend([Token::Values, Token::OpenBracket]);
+        for (i, value) in values.into_iter().enumerate() {
+            if i > 0 {
+                tokens.push(Token::Comma);
+            }
+            tokens.push(value);
+        }
+        tokens.extend([Token::CloseBracket, Token::Semicolon, Token::Eof]);
+        tokens
+    }
+
     #[test]
-    fn test_insert_statement_error() {
-        let tokens = vec![
-            Token::Insert,
-            Token::Identifier("INTO".to_string()),
-            Token::Identifier("my_table".to_string()),
-            Token::Identifier("VALUES".to_string()),
-            Token::OpenBracket,
+    fn test_insert_statement() {
+        let tokens = insert_tokens(&[], vec![
             Token::StringLiteral("value1".to_string()),
-            Token::Comma,
             Token::NumericLiteral("123".to_string()),
-            Token::CloseBracket,
-            Token::Semicolon,
-            Token::Eof,
-        ];
+        ]);
 
-        let mut parser = Parser::new(tokens);
-        let error = parser.parse_statement().unwrap_err();
+        let statement = Parser::new(tokens).parse_statement().unwrap();
 
-        assert_eq!(
-            error.to_string(),
-            "Unexpected Token 'INSERT' at position '0'"
-        );
+        let expected_statement = Statements::Insert(InsertStatement {
+            table_name: "my_table".to_string(),
+            values: vec![Literal::String("value1".to_string()), Literal::Integer(123)],
+            columns: Vec::new(),
+        });
+
+        assert_eq!(statement, expected_statement);
+    }
+
+    #[test]
+    fn test_insert_statement_with_columns() {
+        let tokens = insert_tokens(&["name", "id"], vec![
+            Token::StringLiteral("A".to_string()),
+            Token::NumericLiteral("1".to_string()),
+        ]);
+
+        let statement = Parser::new(tokens).parse_statement().unwrap();
+
+        let expected_statement = Statements::Insert(InsertStatement {
+            table_name: "my_table".to_string(),
+            values: vec![Literal::String("A".to_string()), Literal::Integer(1)],
+            columns: vec!["name".to_string(), "id".to_string()],
+        });
+
+        assert_eq!(statement, expected_statement);
+    }
+
+    #[test]
+    fn test_insert_default_value_is_null() {
+        let tokens = insert_tokens(&[], vec![Token::NumericLiteral("1".to_string()), Token::Default]);
+
+        let Ok(Statements::Insert(insert)) = Parser::new(tokens).parse_statement() else { panic!("expected INSERT") };
+
+        assert_eq!(insert.values, vec![Literal::Integer(1), Literal::Null]);
     }
 
     #[test]
-    fn test_create_table_statement_error() {
+    fn test_insert_column_and_value_counts_must_match() {
+        let tokens = insert_tokens(&["name", "id"], vec![Token::StringLiteral("A".to_string())]);
+
+        let error = Parser::new(tokens).parse_statement().unwrap_err();
+
+        assert_eq!(error.to_string(), "Insert lists 2 columns but 1 values");
+    }
+
+    #[test]
+    fn test_create_table_statement() {
         let tokens = vec![
-            Token::CreateTable,
+            Token::Create,
+            Token::Table,
             Token::Identifier("new_table".to_string()),
             Token::OpenBracket,
             Token::Identifier("id".to_string()),
@@ -427,12 +1509,1117 @@ mod tests {
         ];
 
         let mut parser = Parser::new(tokens);
-        let error = parser.parse_statement().unwrap_err();
+        let statement = parser.parse_statement().unwrap();
 
-        assert_eq!(
-            error.to_string(),
-            "Unexpected Token 'CREATE TABLE' at position '0'"
+        let expected_statement = Statements::CreateTable(CreateTableStatement {
+            table_name: "new_table".to_string(),
+            columns: vec![
+                ColumnDefinition { name: "id".to_string(), data_type: DataType::Integer, constraints: vec![], default: None, check: None },
+                ColumnDefinition { name: "name".to_string(), data_type: DataType::String, constraints: vec![], default: None, check: None },
+            ],
+        });
+
+        assert_eq!(statement, expected_statement);
+    }
+
+    #[test]
+    fn test_create_table_unknown_data_type() {
+        let tokens = vec![
+            Token::Create,
+            Token::Table,
+            Token::Identifier("new_table".to_string()),
+            Token::OpenBracket,
+            Token::Identifier("id".to_string()),
+            Token::Identifier("BLOB".to_string()),
+            Token::CloseBracket,
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let error = Parser::new(tokens).parse_statement().unwrap_err();
+
+        assert_eq!(error.to_string(), "Unexpected Token 'Unknown data type BLOB' at position '5'");
+    }
+
+    #[test]
+    fn test_truncate_identity_options() {
+        let parse = |option: Vec<Token>| {
+            let mut tokens = vec![Token::Truncate, Token::Table, Token::Identifier("users".to_string())];
+            tokens.extend(option);
+            tokens.extend([Token::Semicolon, Token::Eof]);
+            Parser::new(tokens).parse_statement().unwrap()
+        };
+        let expected = |identity| Statements::Truncate(TruncateStatement {
+            table_name: "users".to_string(),
+            identity,
+        });
+
+        assert_eq!(parse(vec![Token::Identifier("RESTART".to_string()), Token::Identifier("IDENTITY".to_string())]), expected(TruncateIdentity::Restart));
+        assert_eq!(parse(vec![Token::Identifier("CONTINUE".to_string()), Token::Identifier("IDENTITY".to_string())]), expected(TruncateIdentity::Continue));
+        assert_eq!(parse(vec![]), expected(TruncateIdentity::Continue));
+    }
+
+    #[test]
+    fn test_truncate_restart_requires_identity() {
+        let tokens = vec![
+            Token::Truncate,
+            Token::Identifier("users".to_string()),
+            Token::Identifier("RESTART".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        assert!(Parser::new(tokens).parse_statement().is_err());
+    }
+
+    #[test]
+    fn test_select_with_float_where_clause() {
+        let tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("products".to_string()),
+            Token::Where,
+            Token::Identifier("price".to_string()),
+            Token::LessThan,
+            Token::FloatLiteral("9.5".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let statement = Parser::new(tokens).parse_statement().unwrap();
+
+        let expected_statement = Statements::Select(SelectStatement {
+            columns: vec![SelectColumn::Wildcard],
+            from_table: "products".to_string(),
+            where_clause: Some(Expression::Binary(
+                Box::new(Expression::Identifier("price".to_string(), None)),
+                BinaryOperator::LessThan,
+                Box::new(Expression::Literal(Literal::Float(9.5), None)),
+            )),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        assert_eq!(statement, expected_statement);
+    }
+
+    #[test]
+    fn test_select_with_boolean_where_clause() {
+        let tokens = vec![
+            Token::Select,
+            Token::Identifier("name".to_string()),
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Where,
+            Token::Identifier("active".to_string()),
+            Token::Equals,
+            Token::BooleanLiteral(true),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let statement = Parser::new(tokens).parse_statement().unwrap();
+
+        let expected_statement = Statements::Select(SelectStatement {
+            columns: vec![SelectColumn::Identifier("name".to_string())],
+            from_table: "users".to_string(),
+            where_clause: Some(Expression::Binary(
+                Box::new(Expression::Identifier("active".to_string(), None)),
+                BinaryOperator::Equals,
+                Box::new(Expression::Literal(Literal::Boolean(true), None)),
+            )),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        assert_eq!(statement, expected_statement);
+    }
+
+    fn nested_where_tokens(depth: usize) -> Vec<Token> {
+        let mut tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Where,
+        ];
+        tokens.extend(std::iter::repeat_n(Token::OpenBracket, depth));
+        tokens.extend([
+            Token::Identifier("id".to_string()),
+            Token::Equals,
+            Token::NumericLiteral("1".to_string()),
+        ]);
+        tokens.extend(std::iter::repeat_n(Token::CloseBracket, depth));
+        tokens.extend([Token::Semicolon, Token::Eof]);
+        tokens
+    }
+
+    #[test]
+    fn test_parenthesized_where_clause() {
+        let statement = Parser::new(nested_where_tokens(3)).parse_statement().unwrap();
+
+        let expected_statement = Statements::Select(SelectStatement {
+            columns: vec![SelectColumn::Wildcard],
+            from_table: "users".to_string(),
+            where_clause: Some(Expression::Binary(
+                Box::new(Expression::Identifier("id".to_string(), None)),
+                BinaryOperator::Equals,
+                Box::new(Expression::Literal(Literal::Integer(1), None)),
+            )),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        assert_eq!(statement, expected_statement);
+    }
+
+    #[test]
+    fn test_nesting_too_deep() {
+        let error = Parser::new(nested_where_tokens(100_000)).parse_statement().unwrap_err();
+        assert!(matches!(error, ParserError::NestingTooDeep(DEFAULT_MAX_NESTING_DEPTH)));
+
+        assert!(Parser::with_max_depth(nested_where_tokens(2), 2).parse_statement().is_ok());
+        let error = Parser::with_max_depth(nested_where_tokens(3), 2).parse_statement().unwrap_err();
+        assert!(matches!(error, ParserError::NestingTooDeep(2)));
+    }
+
+    #[test]
+    fn test_delete_statement() {
+        let tokens = vec![
+            Token::Delete,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Where,
+            Token::Identifier("age".to_string()),
+            Token::LessThan,
+            Token::NumericLiteral("30".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let statement = Parser::new(tokens).parse_statement().unwrap();
+
+        let expected_statement = Statements::Delete(DeleteStatement {
+            table_name: "users".to_string(),
+            where_clause: Some(Expression::Binary(
+                Box::new(Expression::Identifier("age".to_string(), None)),
+                BinaryOperator::LessThan,
+                Box::new(Expression::Literal(Literal::Integer(30), None)),
+            )),
+        });
+
+        assert_eq!(statement, expected_statement);
+    }
+
+    #[test]
+    fn test_delete_without_where_clause() {
+        let tokens = vec![
+            Token::Delete,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let statement = Parser::new(tokens).parse_statement().unwrap();
+
+        assert_eq!(statement, Statements::Delete(DeleteStatement {
+            table_name: "users".to_string(),
+            where_clause: None,
+        }));
+    }
+
+    #[test]
+    fn test_update_statement() {
+        let tokens = vec![
+            Token::Update,
+            Token::Identifier("users".to_string()),
+            Token::Identifier("SET".to_string()),
+            Token::Identifier("name".to_string()),
+            Token::Equals,
+            Token::StringLiteral("Bobby".to_string()),
+            Token::Comma,
+            Token::Identifier("age".to_string()),
+            Token::Equals,
+            Token::NumericLiteral("26".to_string()),
+            Token::Where,
+            Token::Identifier("id".to_string()),
+            Token::Equals,
+            Token::NumericLiteral("2".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let statement = Parser::new(tokens).parse_statement().unwrap();
+
+        let expected_statement = Statements::Update(UpdateStatement {
+            table_name: "users".to_string(),
+            assignments: vec![
+                ("name".to_string(), Literal::String("Bobby".to_string())),
+                ("age".to_string(), Literal::Integer(26)),
+            ],
+            where_clause: Some(Expression::Binary(
+                Box::new(Expression::Identifier("id".to_string(), None)),
+                BinaryOperator::Equals,
+                Box::new(Expression::Literal(Literal::Integer(2), None)),
+            )),
+        });
+
+        assert_eq!(statement, expected_statement);
+    }
+
+    #[test]
+    fn test_update_requires_assignment() {
+        let tokens = vec![
+            Token::Update,
+            Token::Identifier("users".to_string()),
+            Token::Identifier("SET".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        assert!(matches!(
+            Parser::new(tokens).parse_statement(),
+            Err(ParserError::UnexpectedToken(_, 3))
+        ));
+    }
+
+    #[test]
+    fn test_select_with_order_by() {
+        let tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Where,
+            Token::Identifier("age".to_string()),
+            Token::GreaterThan,
+            Token::NumericLiteral("18".to_string()),
+            Token::Order,
+            Token::By,
+            Token::Identifier("age".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let statement = Parser::new(tokens).parse_statement().unwrap();
+
+        let Statements::Select(select) = statement else { panic!("expected SELECT") };
+        assert!(select.where_clause.is_some());
+        assert_eq!(select.order_by, vec![("age".to_string(), SortDirection::Asc, None)]);
+    }
+
+    #[test]
+    fn test_select_with_multi_key_order_by() {
+        let tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Order,
+            Token::By,
+            Token::Identifier("age".to_string()),
+            Token::Desc,
+            Token::Comma,
+            Token::Identifier("name".to_string()),
+            Token::Asc,
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let statement = Parser::new(tokens).parse_statement().unwrap();
+
+        let expected_statement = Statements::Select(SelectStatement {
+            columns: vec![SelectColumn::Wildcard],
+            from_table: "users".to_string(),
+            where_clause: None,
+            order_by: vec![
+                ("age".to_string(), SortDirection::Desc, None),
+                ("name".to_string(), SortDirection::Asc, None),
+            ],
+            limit: None,
+            offset: None,
+            distinct: false,
+        });
+
+        assert_eq!(statement, expected_statement);
+    }
+
+    #[test]
+    fn test_order_requires_by() {
+        let tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Order,
+            Token::Identifier("age".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        assert!(matches!(
+            Parser::new(tokens).parse_statement(),
+            Err(ParserError::UnexpectedToken(_, 5))
+        ));
+    }
+
+    fn select_with_limit_offset_tokens(clauses: Vec<Token>) -> Vec<Token> {
+        let mut tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Order,
+            Token::By,
+            Token::Identifier("id".to_string()),
+        ];
+        tokens.extend(clauses);
+        tokens.extend([Token::Semicolon, Token::Eof]);
+        tokens
+    }
+
+    #[test]
+    fn test_select_with_limit_and_offset() {
+        let cases = [
+            (vec![Token::Limit, Token::NumericLiteral("10".to_string())], Some(10), None),
+            (vec![Token::Offset, Token::NumericLiteral("20".to_string())], None, Some(20)),
+            (
+                vec![
+                    Token::Limit,
+                    Token::NumericLiteral("10".to_string()),
+                    Token::Offset,
+                    Token::NumericLiteral("20".to_string()),
+                ],
+                Some(10),
+                Some(20),
+            ),
+        ];
+
+        for (clauses, limit, offset) in cases {
+            let statement = Parser::new(select_with_limit_offset_tokens(clauses)).parse_statement().unwrap();
+            let Statements::Select(select) = statement else { panic!("expected SELECT") };
+            assert_eq!((select.limit, select.offset), (limit, offset));
+        }
+    }
+
+    #[test]
+    fn test_limit_requires_row_count() {
+        let tokens = select_with_limit_offset_tokens(vec![Token::Limit, Token::FloatLiteral("1.5".to_string())]);
+
+        assert!(matches!(
+            Parser::new(tokens).parse_statement(),
+            Err(ParserError::UnexpectedToken(_, 8))
+        ));
+    }
+
+    #[test]
+    fn test_select_aggregates() {
+        let tokens = vec![
+            Token::Select,
+            Token::Identifier("count".to_string()),
+            Token::OpenBracket,
+            Token::Asterisk,
+            Token::CloseBracket,
+            Token::Comma,
+            Token::Identifier("AVG".to_string()),
+            Token::OpenBracket,
+            Token::Identifier("age".to_string()),
+            Token::CloseBracket,
+            Token::Comma,
+            Token::Identifier("max".to_string()),
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let statement = Parser::new(tokens).parse_statement().unwrap();
+
+        let Statements::Select(select) = statement else { panic!("expected SELECT") };
+        assert_eq!(select.columns, vec![
+            SelectColumn::Aggregate { func: AggregateFunc::Count, arg: None },
+            SelectColumn::Aggregate { func: AggregateFunc::Avg, arg: Some("age".to_string()) },
+            // Without a call, an aggregate name is an ordinary column.
+            SelectColumn::Identifier("max".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_only_count_accepts_asterisk() {
+        let tokens = vec![
+            Token::Select,
+            Token::Identifier("SUM".to_string()),
+            Token::OpenBracket,
+            Token::Asterisk,
+            Token::CloseBracket,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        assert!(matches!(
+            Parser::new(tokens).parse_statement(),
+            Err(ParserError::UnexpectedToken(_, 3))
+        ));
+    }
+
+    #[test]
+    fn test_fetch_first_matches_limit() {
+        let number = |n: &str| Token::NumericLiteral(n.to_string());
+        let word = |w: &str| Token::Identifier(w.to_string());
+        let spellings = [
+            (
+                vec![Token::Limit, number("10"), Token::Offset, number("20")],
+                vec![Token::Offset, number("20"), word("ROWS"), Token::Fetch, word("FIRST"), number("10"), word("ROWS"), word("ONLY")],
+            ),
+            (
+                vec![Token::Limit, number("1")],
+                vec![Token::Fetch, word("next"), number("1"), word("Row"), word("only")],
+            ),
+            (
+                vec![Token::Offset, number("5")],
+                vec![Token::Offset, number("5"), word("ROW")],
+            ),
+        ];
+
+        for (limit_spelling, standard_spelling) in spellings {
+            let limit_ast = Parser::new(select_with_limit_offset_tokens(limit_spelling)).parse_statement().unwrap();
+            let standard_ast = Parser::new(select_with_limit_offset_tokens(standard_spelling)).parse_statement().unwrap();
+            assert_eq!(limit_ast, standard_ast);
+        }
+    }
+
+    #[test]
+    fn test_fetch_first_requires_only() {
+        let tokens = select_with_limit_offset_tokens(vec![
+            Token::Fetch,
+            Token::Identifier("FIRST".to_string()),
+            Token::NumericLiteral("10".to_string()),
+            Token::Identifier("ROWS".to_string()),
+        ]);
+
+        assert!(matches!(
+            Parser::new(tokens).parse_statement(),
+            Err(ParserError::UnexpectedToken(_, 11))
+        ));
+    }
+
+    #[test]
+    fn test_select_distinct() {
+        let tokens = vec![
+            Token::Select,
+            Token::Distinct,
+            Token::Identifier("age".to_string()),
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let statement = Parser::new(tokens).parse_statement().unwrap();
+
+        let expected_statement = Statements::Select(SelectStatement {
+            columns: vec![SelectColumn::Identifier("age".to_string())],
+            from_table: "users".to_string(),
+            where_clause: None,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: true,
+        });
+
+        assert_eq!(statement, expected_statement);
+    }
+
+    #[test]
+    fn test_error_span_points_at_offending_token() {
+        use crate::tokenizer::Tokenizer;
+
+        let query = "SELECT name FROM users WHERE age > ;";
+        let tokens = Tokenizer::new(query).tokenize_spanned().unwrap();
+        let mut parser = Parser::from_spanned(tokens);
+
+        let error = parser.parse_statement().unwrap_err();
+        let span = parser.error_span(&error).unwrap();
+        assert_eq!(&query[span.start..span.end], ";");
+
+        assert_eq!(Parser::new(vec![Token::Eof]).span_at(0), None);
+    }
+
+    #[test]
+    fn test_expressions_carry_source_spans() {
+        use crate::tokenizer::Tokenizer;
+
+        let query = "SELECT name FROM users WHERE age > 30 AND name IS NULL;";
+        let tokens = Tokenizer::new(query).tokenize_spanned().unwrap();
+        let Statements::Select(select) = Parser::from_spanned(tokens).parse_statement().unwrap() else {
+            panic!("expected a SELECT");
+        };
+        let where_clause = select.where_clause.unwrap();
+        let source = |span: Option<Span>| span.map(|span| &query[span.start..span.end]);
+        assert_eq!(source(where_clause.span()), Some("age > 30 AND name"));
+
+        let Expression::Binary(comparison, _, _) = &where_clause else { panic!("expected AND") };
+        let Expression::Binary(column, _, literal) = &**comparison else { panic!("expected >") };
+        assert!(matches!(**column, Expression::Identifier(_, span) if source(span) == Some("age")));
+        assert!(matches!(**literal, Expression::Literal(_, span) if source(span) == Some("30")));
+        assert_eq!(source(comparison.span()), Some("age > 30"));
+
+        // Parsed without spans, no node carries one.
+        let tokens = Tokenizer::new(query).tokenize().unwrap();
+        let Statements::Select(unspanned) = Parser::new(tokens).parse_statement().unwrap() else {
+            panic!("expected a SELECT");
+        };
+        assert_eq!(unspanned.where_clause.unwrap().span(), None);
+    }
+
+    #[test]
+    fn test_where_is_null_and_is_not_null() {
+        use crate::tokenizer::Tokenizer;
+
+        for (sql, negated) in [("SELECT * FROM users WHERE age IS NULL;", false), ("SELECT * FROM users WHERE age IS NOT NULL;", true)] {
+            let tokens = Tokenizer::new(sql).tokenize().unwrap();
+            let Statements::Select(select) = Parser::new(tokens).parse_statement().unwrap() else { panic!("expected SELECT") };
+            assert_eq!(
+                select.where_clause,
+                Some(Expression::IsNull(Box::new(Expression::Identifier("age".to_string(), None)), negated))
+            );
+        }
+
+        let tokens = Tokenizer::new("SELECT * FROM users WHERE age IS 3;").tokenize().unwrap();
+        assert!(matches!(Parser::new(tokens).parse_statement(), Err(ParserError::UnexpectedToken(_, 7))));
+    }
+
+    fn parse_where(condition: &str) -> Expression {
+        use crate::tokenizer::Tokenizer;
+
+        let tokens = Tokenizer::new(&format!("SELECT * FROM t WHERE {condition};")).tokenize().unwrap();
+        let Statements::Select(select) = Parser::new(tokens).parse_statement().unwrap() else { panic!("expected SELECT") };
+        select.where_clause.unwrap()
+    }
+
+    fn equals(column: &str, value: i64) -> Expression {
+        Expression::Binary(
+            Box::new(Expression::Identifier(column.to_string(), None)),
+            BinaryOperator::Equals,
+            Box::new(Expression::Literal(Literal::Integer(value), None)),
+        )
+    }
+
+    fn binary(left: Expression, op: BinaryOperator, right: Expression) -> Expression {
+        Expression::Binary(Box::new(left), op, Box::new(right))
+    }
+
+    #[test]
+    fn test_and_binds_tighter_than_or() {
+        assert_eq!(
+            parse_where("a = 1 OR b = 2 AND c = 3"),
+            binary(equals("a", 1), BinaryOperator::Or, binary(equals("b", 2), BinaryOperator::And, equals("c", 3)))
+        );
+        assert_eq!(
+            parse_where("a = 1 AND b = 2 OR c = 3"),
+            binary(binary(equals("a", 1), BinaryOperator::And, equals("b", 2)), BinaryOperator::Or, equals("c", 3))
+        );
+    }
+
+    #[test]
+    fn test_same_operator_associates_left() {
+        assert_eq!(
+            parse_where("a = 1 AND b = 2 AND c = 3"),
+            binary(binary(equals("a", 1), BinaryOperator::And, equals("b", 2)), BinaryOperator::And, equals("c", 3))
+        );
+    }
+
+    #[test]
+    fn test_parentheses_override_precedence() {
+        assert_eq!(
+            parse_where("(a = 1 OR b = 2) AND c = 3"),
+            binary(binary(equals("a", 1), BinaryOperator::Or, equals("b", 2)), BinaryOperator::And, equals("c", 3))
+        );
+        assert_eq!(
+            parse_where("((a = 1) OR (b = 2 AND (c = 3 OR d = 4)))"),
+            binary(
+                equals("a", 1),
+                BinaryOperator::Or,
+                binary(equals("b", 2), BinaryOperator::And, binary(equals("c", 3), BinaryOperator::Or, equals("d", 4))),
+            )
+        );
+        assert_eq!(
+            parse_where("a IS NULL OR b = 2"),
+            binary(Expression::IsNull(Box::new(Expression::Identifier("a".to_string(), None)), false), BinaryOperator::Or, equals("b", 2))
+        );
+    }
+
+    #[test]
+    fn test_incomplete_expression_is_rejected() {
+        use crate::tokenizer::Tokenizer;
+
+        for condition in ["a = 1 AND", "(a = 1", "a = 1)", "AND a = 1"] {
+            let tokens = Tokenizer::new(&format!("SELECT * FROM t WHERE {condition};")).tokenize().unwrap();
+            assert!(Parser::new(tokens).parse_statement().is_err(), "{condition}");
+        }
+    }
+
+    #[test]
+    fn test_literal_on_left_of_comparison() {
+        assert_eq!(
+            parse_where("30 = age"),
+            binary(Expression::Literal(Literal::Integer(30), None), BinaryOperator::Equals, Expression::Identifier("age".to_string(), None))
+        );
+        assert_eq!(
+            parse_where("'Bob' = name AND 18 <= age"),
+            binary(
+                binary(
+                    Expression::Literal(Literal::String("Bob".to_string()), None),
+                    BinaryOperator::Equals,
+                    Expression::Identifier("name".to_string(), None),
+                ),
+                BinaryOperator::And,
+                binary(Expression::Literal(Literal::Integer(18), None), BinaryOperator::LessThanOrEquals, Expression::Identifier("age".to_string(), None)),
+            )
+        );
+    }
+
+    #[test]
+    fn test_like_binds_like_a_comparison() {
+        assert_eq!(
+            parse_where("name LIKE 'Al%' OR a = 1"),
+            binary(
+                binary(Expression::Identifier("name".to_string(), None), BinaryOperator::Like, Expression::Literal(Literal::String("Al%".to_string()), None)),
+                BinaryOperator::Or,
+                equals("a", 1),
+            )
+        );
+    }
+
+    #[test]
+    fn test_in_list() {
+        assert_eq!(
+            parse_where("id IN (1, 2, 3) AND a = 1"),
+            binary(
+                Expression::InList(
+                    Box::new(Expression::Identifier("id".to_string(), None)),
+                    vec![Literal::Integer(1), Literal::Integer(2), Literal::Integer(3)],
+                    false,
+                ),
+                BinaryOperator::And,
+                equals("a", 1),
+            )
+        );
+        assert_eq!(
+            parse_where("name IN ()"),
+            Expression::InList(Box::new(Expression::Identifier("name".to_string(), None)), Vec::new(), false)
+        );
+    }
+
+    #[test]
+    fn test_negative_literals() {
+        use crate::tokenizer::Tokenizer;
+
+        assert_eq!(
+            parse_where("balance = -50 AND rate > -1.5"),
+            binary(
+                binary(Expression::Identifier("balance".to_string(), None), BinaryOperator::Equals, Expression::Literal(Literal::Integer(-50), None)),
+                BinaryOperator::And,
+                binary(Expression::Identifier("rate".to_string(), None), BinaryOperator::GreaterThan, Expression::Literal(Literal::Float(-1.5), None)),
+            )
+        );
+        assert_eq!(
+            parse_where("balance = -9223372036854775808"),
+            binary(Expression::Identifier("balance".to_string(), None), BinaryOperator::Equals, Expression::Literal(Literal::Integer(i64::MIN), None))
+        );
+
+        let tokens = Tokenizer::new("INSERT INTO accounts VALUES (1, -50, -0.25);").tokenize().unwrap();
+        let Ok(Statements::Insert(insert)) = Parser::new(tokens).parse_statement() else { panic!("expected INSERT") };
+        assert_eq!(insert.values, vec![Literal::Integer(1), Literal::Integer(-50), Literal::Float(-0.25)]);
+
+        // Row counts can't be negative.
+        let tokens = Tokenizer::new("SELECT * FROM accounts LIMIT -1;").tokenize().unwrap();
+        assert!(matches!(Parser::new(tokens).parse_statement(), Err(ParserError::InvalidInteger(n, _)) if n == "-1"));
+    }
+
+    #[test]
+    fn test_integer_overflow_is_a_parse_error() {
+        use crate::tokenizer::Tokenizer;
+
+        let huge = "123456789012345678901234567890";
+        for sql in [
+            format!("SELECT * FROM users WHERE id = {huge};"),
+            format!("SELECT * FROM users WHERE id IN (1, {huge});"),
+            format!("INSERT INTO users VALUES ({huge}, 'Alice');"),
+            format!("SELECT * FROM users LIMIT {huge};"),
+            format!("CREATE TABLE users (id INTEGER DEFAULT {huge});"),
+        ] {
+            let tokens = Tokenizer::new(&sql).tokenize().unwrap();
+            assert!(
+                matches!(Parser::new(tokens).parse_statement(), Err(ParserError::InvalidInteger(ref n, _)) if n == huge),
+                "{sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_not_in_and_not_like() {
+        assert_eq!(
+            parse_where("id NOT IN (1, 2) AND name NOT LIKE 'A%'"),
+            binary(
+                Expression::InList(Box::new(Expression::Identifier("id".to_string(), None)), vec![Literal::Integer(1), Literal::Integer(2)], true),
+                BinaryOperator::And,
+                binary(Expression::Identifier("name".to_string(), None), BinaryOperator::NotLike, Expression::Literal(Literal::String("A%".to_string()), None)),
+            )
+        );
+        // A leading NOT still negates the whole comparison.
+        assert_eq!(
+            parse_where("NOT name LIKE 'A%'"),
+            Expression::Not(Box::new(binary(
+                Expression::Identifier("name".to_string(), None),
+                BinaryOperator::Like,
+                Expression::Literal(Literal::String("A%".to_string()), None),
+            )))
+        );
+    }
+
+    #[test]
+    fn test_malformed_in_list_is_rejected() {
+        use crate::tokenizer::Tokenizer;
+
+        for condition in ["id IN 1", "id IN (1, 2", "id IN (1 2)", "id IN (1,)", "id IN (a)"] {
+            let tokens = Tokenizer::new(&format!("SELECT * FROM t WHERE {condition};")).tokenize().unwrap();
+            assert!(Parser::new(tokens).parse_statement().is_err(), "{condition}");
+        }
+    }
+
+    #[test]
+    fn test_is_distinct_from() {
+        let column = |name: &str| Box::new(Expression::Identifier(name.to_string(), None));
+        assert_eq!(parse_where("a IS DISTINCT FROM b"), Expression::IsDistinctFrom(column("a"), column("b"), false));
+        assert_eq!(
+            parse_where("a IS NOT DISTINCT FROM NULL OR b = 2"),
+            binary(
+                Expression::IsDistinctFrom(column("a"), Box::new(Expression::Literal(Literal::Null, None)), true),
+                BinaryOperator::Or,
+                equals("b", 2),
+            )
+        );
+    }
+
+    #[test]
+    fn test_between_uses_and_as_separator() {
+        assert_eq!(
+            parse_where("age BETWEEN 25 AND 35 AND a = 1"),
+            binary(
+                Expression::Between {
+                    expr: Box::new(Expression::Identifier("age".to_string(), None)),
+                    low: Box::new(Expression::Literal(Literal::Integer(25), None)),
+                    high: Box::new(Expression::Literal(Literal::Integer(35), None)),
+                },
+                BinaryOperator::And,
+                equals("a", 1),
+            )
+        );
+    }
+
+    fn not(expression: Expression) -> Expression {
+        Expression::Not(Box::new(expression))
+    }
+
+    #[test]
+    fn test_not_precedence() {
+        assert_eq!(parse_where("NOT (a = 1)"), not(equals("a", 1)));
+        assert_eq!(
+            parse_where("NOT a = 1 AND b = 2"),
+            binary(not(equals("a", 1)), BinaryOperator::And, equals("b", 2))
+        );
+        assert_eq!(
+            parse_where("a = 1 OR NOT NOT b = 2"),
+            binary(equals("a", 1), BinaryOperator::Or, not(not(equals("b", 2))))
+        );
+        assert_eq!(
+            parse_where("NOT (a = 1 OR b = 2)"),
+            not(binary(equals("a", 1), BinaryOperator::Or, equals("b", 2)))
+        );
+    }
+
+    #[test]
+    fn test_stacked_not_is_bounded() {
+        let mut tokens = vec![Token::Select, Token::Asterisk, Token::From, Token::Identifier("t".to_string()), Token::Where];
+        tokens.extend(std::iter::repeat_n(Token::Not, 100_000));
+        tokens.extend([Token::BooleanLiteral(true), Token::Semicolon, Token::Eof]);
+
+        let error = Parser::new(tokens).parse_statement().unwrap_err();
+        assert!(matches!(error, ParserError::NestingTooDeep(DEFAULT_MAX_NESTING_DEPTH)));
+    }
+
+    #[test]
+    fn test_drop_table_statement() {
+        use crate::tokenizer::Tokenizer;
+
+        let parse = |sql: &str| Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement();
+
+        assert_eq!(
+            parse("DROP TABLE users;").unwrap(),
+            Statements::DropTable(DropTableStatement { table_name: "users".to_string(), if_exists: false })
+        );
+        assert_eq!(
+            parse("drop table if exists users;").unwrap(),
+            Statements::DropTable(DropTableStatement { table_name: "users".to_string(), if_exists: true })
+        );
+        for sql in ["DROP users;", "DROP TABLE IF users;", "DROP TABLE;", "DROP TABLE users"] {
+            assert!(parse(sql).is_err(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_alter_table_drop_column() {
+        use crate::tokenizer::Tokenizer;
+
+        let parse = |sql: &str| Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement();
+
+        assert_eq!(
+            parse("ALTER TABLE users DROP COLUMN age;").unwrap(),
+            Statements::AlterTable(AlterTableStatement {
+                table_name: "users".to_string(),
+                action: AlterTableAction::DropColumn("age".to_string()),
+            })
+        );
+        assert_eq!(
+            parse("alter table users rename to members;").unwrap(),
+            Statements::AlterTable(AlterTableStatement {
+                table_name: "users".to_string(),
+                action: AlterTableAction::RenameTo("members".to_string()),
+            })
+        );
+        assert_eq!(
+            parse("ALTER TABLE users ALTER COLUMN age type STRING;").unwrap(),
+            Statements::AlterTable(AlterTableStatement {
+                table_name: "users".to_string(),
+                action: AlterTableAction::AlterColumnType { column: "age".to_string(), data_type: DataType::String },
+            })
+        );
+        for sql in [
+            "ALTER TABLE users RENAME members;",
+            "ALTER TABLE users RENAME TO;",
+            "ALTER users DROP COLUMN age;",
+            "ALTER TABLE users DROP age;",
+            "ALTER TABLE users age;",
+            "ALTER TABLE users DROP COLUMN age",
+            "ALTER TABLE users ALTER COLUMN age STRING;",
+            "ALTER TABLE users ALTER COLUMN age TYPE;",
+            "ALTER TABLE users ALTER COLUMN age TYPE TEXT;",
+        ] {
+            assert!(parse(sql).is_err(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_create_table_column_constraints() {
+        use crate::tokenizer::Tokenizer;
+
+        let sql = "CREATE TABLE users (id INTEGER PRIMARY KEY, email STRING UNIQUE NOT NULL, age INTEGER);";
+        let statement = Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement().unwrap();
+
+        let Statements::CreateTable(create) = statement else { panic!("expected CREATE TABLE") };
+        let constraints: Vec<&[ConstraintKind]> = create.columns.iter().map(|col| col.constraints.as_slice()).collect();
+        assert_eq!(constraints, vec![
+            &[ConstraintKind::NotNull, ConstraintKind::Unique][..],
+            &[ConstraintKind::Unique, ConstraintKind::NotNull][..],
+            &[][..],
+        ]);
+
+        for sql in ["CREATE TABLE t (id INTEGER NOT);", "CREATE TABLE t (id INTEGER PRIMARY);", "CREATE TABLE t (id INTEGER UNIQUE UNIQUE KEY);"] {
+            assert!(Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement().is_err(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_create_table_default_clause() {
+        use crate::tokenizer::Tokenizer;
+
+        let sql = "CREATE TABLE t (status INTEGER DEFAULT 0 NOT NULL, name STRING DEFAULT 'guest', note STRING);";
+        let statement = Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement().unwrap();
+
+        let Statements::CreateTable(create) = statement else { panic!("expected CREATE TABLE") };
+        let defaults: Vec<Option<Literal>> = create.columns.into_iter().map(|col| col.default).collect();
+        assert_eq!(defaults, vec![Some(Literal::Integer(0)), Some(Literal::String("guest".to_string())), None]);
+
+        let missing_value = "CREATE TABLE t (status INTEGER DEFAULT);";
+        assert!(Parser::new(Tokenizer::new(missing_value).tokenize().unwrap()).parse_statement().is_err());
+    }
+
+    #[test]
+    fn test_create_table_auto_increment_and_check() {
+        use crate::tokenizer::Tokenizer;
+
+        let sql = "CREATE TABLE t (id INTEGER AUTO_INCREMENT CHECK (id > 0) NOT NULL, check STRING);";
+        let statement = Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement().unwrap();
+
+        let Statements::CreateTable(create) = statement else { panic!("expected CREATE TABLE") };
+        assert_eq!(create.columns[0].constraints, vec![ConstraintKind::AutoIncrement, ConstraintKind::NotNull]);
+        assert_eq!(
+            create.columns[0].check,
+            Some(Expression::Binary(
+                Box::new(Expression::Identifier("id".to_string(), None)),
+                BinaryOperator::GreaterThan,
+                Box::new(Expression::Literal(Literal::Integer(0), None)),
+            ))
+        );
+        assert_eq!(create.columns[1].name, "check");
+
+        let unbracketed = "CREATE TABLE t (id INTEGER CHECK id > 0);";
+        assert!(Parser::new(Tokenizer::new(unbracketed).tokenize().unwrap()).parse_statement().is_err());
+    }
+
+    #[test]
+    fn test_asterisk_only_standalone_or_in_count() {
+        use crate::tokenizer::Tokenizer;
+
+        let parse = |sql: &str| Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement();
+        let message = |sql: &str| match parse(sql) {
+            Err(ParserError::UnexpectedToken(message, _)) => message,
+            other => panic!("expected an error for {sql}, got {other:?}"),
+        };
+
+        let Ok(Statements::Select(select)) = parse("SELECT COUNT(*), id FROM users;") else { panic!("expected SELECT") };
+        assert_eq!(select.columns[0], SelectColumn::Aggregate { func: AggregateFunc::Count, arg: None });
+        assert!(parse("SELECT * FROM users;").is_ok());
+
+        assert_eq!(message("SELECT SUM(*) FROM users;"), "'*' is only allowed in COUNT(*), not SUM(*)");
+        assert_eq!(message("SELECT id, * FROM users;"), "'*' must be the only column in the select list");
+        assert!(parse("SELECT * FROM users WHERE * = 1;").is_err());
+    }
+
+    #[test]
+    fn test_union_and_union_all() {
+        use crate::tokenizer::Tokenizer;
+
+        let parse = |sql: &str| Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement();
+
+        for (sql, expected_all) in [("SELECT id FROM a UNION SELECT id FROM b;", false), ("SELECT id FROM a UNION ALL SELECT id FROM b;", true)] {
+            let Ok(Statements::SetOperation(set)) = parse(sql) else { panic!("expected a set operation for {sql}") };
+            assert_eq!(set.all, expected_all);
+            assert_eq!(set.left.from_table, "a");
+            assert_eq!(set.right.from_table, "b");
+        }
+        for sql in ["SELECT id FROM a UNION;", "SELECT id FROM a UNION ALL id FROM b;"] {
+            assert!(parse(sql).is_err(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_union_ordering_applies_to_the_whole_union() {
+        use crate::tokenizer::Tokenizer;
+
+        let parse = |sql: &str| Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement();
+
+        let Ok(Statements::SetOperation(set)) = parse("SELECT v FROM n UNION ALL SELECT v FROM n ORDER BY v DESC LIMIT 2 OFFSET 1;") else {
+            panic!("expected a set operation");
+        };
+        assert_eq!(set.order_by, vec![("v".to_string(), SortDirection::Desc, None)]);
+        assert_eq!((set.limit, set.offset), (Some(2), Some(1)));
+        assert!(set.right.order_by.is_empty() && set.right.limit.is_none() && set.right.offset.is_none());
+
+        for sql in ["SELECT v FROM n ORDER BY v UNION SELECT v FROM n;", "SELECT v FROM n LIMIT 1 UNION ALL SELECT v FROM n;"] {
+            assert!(matches!(parse(sql), Err(ParserError::UnexpectedToken(..))), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_empty_input_is_empty_statement() {
+        use crate::tokenizer::Tokenizer;
+
+        for sql in ["", "   ", ";"] {
+            let result = Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement();
+            assert!(matches!(result, Err(ParserError::EmptyStatement)), "{sql:?}");
+        }
+        assert_eq!(ParserError::EmptyStatement.token_position(), None);
+    }
+
+    #[test]
+    fn test_show_tables() {
+        use crate::tokenizer::Tokenizer;
+
+        let parse = |sql: &str| Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement();
+
+        assert!(matches!(parse("SHOW TABLES;"), Ok(Statements::ShowTables)));
+        assert!(matches!(parse("show tables;"), Ok(Statements::ShowTables)));
+        assert!(parse("SHOW users;").is_err());
+        assert!(parse("SHOW TABLES").is_err());
+    }
+
+    #[test]
+    fn test_order_by_nulls_first_and_last() {
+        use crate::tokenizer::Tokenizer;
+
+        let parse = |sql: &str| Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement();
+
+        let Ok(Statements::Select(select)) = parse("SELECT * FROM users ORDER BY age DESC NULLS FIRST, name NULLS LAST, id;") else {
+            panic!("expected SELECT")
+        };
+        assert_eq!(
+            select.order_by,
+            vec![
+                ("age".to_string(), SortDirection::Desc, Some(NullsOrder::First)),
+                ("name".to_string(), SortDirection::Asc, Some(NullsOrder::Last)),
+                ("id".to_string(), SortDirection::Asc, None),
+            ]
         );
+        assert!(parse("SELECT * FROM users ORDER BY age NULLS;").is_err());
+    }
+
+    #[test]
+    fn test_describe() {
+        use crate::tokenizer::Tokenizer;
+
+        let parse = |sql: &str| Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_statement();
+
+        let Ok(Statements::Describe(describe)) = parse("DESCRIBE users;") else { panic!("expected DESCRIBE") };
+        assert_eq!(describe, DescribeStatement { table_name: "users".to_string() });
+        assert!(parse("DESCRIBE;").is_err());
+        assert!(parse("DESCRIBE users").is_err());
+    }
+
+    #[test]
+    fn test_parse_program() {
+        use crate::tokenizer::Tokenizer;
+
+        let parse = |sql: &str| Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_program();
+
+        let statements = parse(
+            "CREATE TABLE users (id INTEGER, name STRING);
+             INSERT INTO users VALUES (1, 'Alice');;
+             SELECT name FROM users WHERE id = 1;
+            ",
+        )
+        .unwrap();
+        assert_eq!(statements.len(), 3);
+        assert!(matches!(statements[0], Statements::CreateTable(_)));
+        assert!(matches!(statements[1], Statements::Insert(_)));
+        assert!(matches!(statements[2], Statements::Select(_)));
+
+        assert!(parse("").unwrap().is_empty());
+        assert!(parse(" ; ;").unwrap().is_empty());
+        assert!(parse("SELECT * FROM users; SELECT FROM users;").is_err());
     }
 
+    #[test]
+    fn test_parse_data_type() {
+        use crate::tokenizer::Tokenizer;
+
+        let parse = |sql: &str| Parser::new(Tokenizer::new(sql).tokenize().unwrap()).parse_data_type();
+
+        assert!(matches!(parse("INTEGER"), Ok(DataType::Integer)));
+        assert!(matches!(parse("string"), Ok(DataType::String)));
+        assert!(matches!(parse("Boolean"), Ok(DataType::Boolean)));
+        assert!(matches!(parse("VARCHAR"), Err(ParserError::UnexpectedToken(message, 0)) if message == "Unknown data type VARCHAR"));
+        assert!(parse("42").is_err());
+    }
 }
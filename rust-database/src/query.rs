@@ -0,0 +1,182 @@
+// ================================
+// query.rs
+// Typed access to query results, for callers that want their own structs
+// back rather than rows of `Value`s.
+// ================================
+use crate::executor::ExecutionError;
+use crate::parser::ParserError;
+use crate::row::Value;
+use crate::tokenizer::TokenizerError;
+use thiserror::Error;
+
+// ========================================================================================
+// ERRORS
+// ========================================================================================
+#[derive(Debug, Error)]
+pub enum QueryError {
+    #[error("Tokenizing failed: {0}")]
+    Tokenize(#[from] TokenizerError),
+
+    #[error("Parsing failed: {0}")]
+    Parse(#[from] ParserError),
+
+    #[error("Execution failed: {0}")]
+    Execution(ExecutionError),
+
+    #[error("Only SELECT statements can be queried into rows")]
+    NotASelect,
+
+    #[error("Expected a single column, but the query returned {0}")]
+    NotAScalar(usize),
+
+    #[error("Result has no column '{0}'")]
+    MissingColumn(String),
+
+    #[error("Column '{column}' holds {value:?}, which doesn't convert to the requested type")]
+    TypeMismatch { column: String, value: Value },
+}
+
+// ========================================================================================
+// TRAITS
+// ========================================================================================
+/// Builds a value from one result row. Implement it for a struct to use
+/// `Database::query_into`:
+///
+/// ```
+/// use rust_database::query::{FromRow, QueryError, RowView};
+///
+/// struct User {
+///     name: String,
+///     age: Option<i64>,
+/// }
+///
+/// impl FromRow for User {
+///     fn from_row(row: &RowView<'_>) -> Result<Self, QueryError> {
+///         Ok(User { name: row.get("name")?, age: row.get("age")? })
+///     }
+/// }
+/// ```
+pub trait FromRow: Sized {
+    /// Builds the value from one result row.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `QueryError` if a column is missing or holds the wrong type.
+    fn from_row(row: &RowView<'_>) -> Result<Self, QueryError>;
+}
+
+/// Converts a single `Value`; `None` means the value is the wrong type.
+pub trait FromValue: Sized {
+    fn from_value(value: &Value) -> Option<Self>;
+}
+
+impl FromValue for i64 {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for f64 {
+    #[allow(clippy::cast_precision_loss)]
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Float(f) => Some(*f),
+            Value::Integer(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(value: &Value) -> Option<Self> {
+        Some(value.clone())
+    }
+}
+
+/// `NULL` becomes `None`; anything else must convert to `T`.
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => Some(None),
+            other => T::from_value(other).map(Some),
+        }
+    }
+}
+
+// ========================================================================================
+// STRUCTS
+// ========================================================================================
+/// One result row with its column names, for looking values up by name.
+pub struct RowView<'a> {
+    columns: &'a [String],
+    values: &'a [Value],
+}
+
+impl<'a> RowView<'a> {
+    #[must_use]
+    pub fn new(columns: &'a [String], values: &'a [Value]) -> Self {
+        Self { columns, values }
+    }
+
+    /// Reads the named column as a `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MissingColumn` if there's no such column, or `TypeMismatch` if its value
+    /// isn't a `T`.
+    pub fn get<T: FromValue>(&self, column: &str) -> Result<T, QueryError> {
+        let value = self
+            .columns
+            .iter()
+            .position(|name| name == column)
+            .and_then(|index| self.values.get(index))
+            .ok_or_else(|| QueryError::MissingColumn(column.to_string()))?;
+
+        T::from_value(value).ok_or_else(|| QueryError::TypeMismatch {
+            column: column.to_string(),
+            value: value.clone(),
+        })
+    }
+}
+
+// ========================================================================================
+// TESTS
+// ========================================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_view_conversions() {
+        let columns = ["id".to_string(), "nickname".to_string(), "score".to_string()];
+        let values = [Value::Integer(7), Value::Null, Value::Integer(3)];
+        let row = RowView::new(&columns, &values);
+
+        assert_eq!(row.get::<i64>("id").unwrap(), 7);
+        assert_eq!(row.get::<Option<String>>("nickname").unwrap(), None);
+        assert_eq!(Value::Float(row.get("score").unwrap()), Value::Float(3.0));
+        assert!(matches!(row.get::<String>("id"), Err(QueryError::TypeMismatch { .. })));
+        assert!(matches!(row.get::<String>("nickname"), Err(QueryError::TypeMismatch { .. })));
+    }
+}
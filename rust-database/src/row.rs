@@ -1,16 +1,27 @@
 use crate::schema::{Schema};
 use crate::column::{DataType,Column};
-use crate::constraint_state::{ConstraintState};
+use crate::constraint_state::{Constraint, ConstraintKind, ConstraintState};
+use crate::executor::Executor;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
 use thiserror::Error;
 
 
 // ========================================================================================
 // ENUMS
 // ========================================================================================
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// A single stored value.
+///
+/// `f64` has no `Eq`/`Hash`/`Ord`, but values are used as keys in unique sets and
+/// B-tree indexes. Floats are therefore compared with `f64::total_cmp` and hashed by
+/// bit pattern: `NaN` equals itself and sorts after every number, and `-0.0` and
+/// `0.0` are distinct. Across variants the order is String < Integer < Float < Boolean < Null.
+#[derive(Clone, Debug)]
 pub enum Value {
     String(String),
     Integer(i64),
+    Float(f64),
+    Boolean(bool),
     Null,
 }
 
@@ -32,6 +43,33 @@ pub enum RowErrors {
 
     #[error("Unique constraint violated for column '{column}' with value {value:?}")]
     UniqueViolated { column: String, value: Value },
+
+    #[error("Column '{column}' does not exist")]
+    UnknownColumn { column: String },
+
+    #[error("Check constraint violated for column '{column}'")]
+    CheckViolated { column: String },
+
+    #[error("Unique constraint violated for columns {columns:?} with values {values:?}")]
+    CompositeUniqueViolated { columns: Vec<String>, values: Vec<Value> },
+}
+
+/// Why a JSON document couldn't be turned into values. `got` holds the offending
+/// JSON as text.
+#[cfg(feature = "serde")]
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("Invalid JSON: {0}")]
+    InvalidJson(String),
+
+    #[error("Expected a JSON object, got {got}")]
+    NotAnObject { got: String },
+
+    #[error("Column '{column}' does not exist")]
+    UnknownColumn { column: String },
+
+    #[error("Expected {expected:?}, but got JSON {got}")]
+    TypeMismatch { expected: DataType, got: String },
 }
 
 // ========================================================================================
@@ -42,10 +80,27 @@ pub struct Row {
     pub values: Vec<Value> 
 }
 
+/// Assembles a row's values by column name rather than position. Each `set` checks
+/// the value's type straight away; `build` fills unset columns through
+/// `Row::resolve_default`, as an insert would, and checks NOT NULL. Uniqueness
+/// depends on the table's contents, so it is left to `Table::add_row`, which the
+/// built values are meant for.
+#[derive(Debug)]
+pub struct RowBuilder<'a> {
+    schema: &'a Schema,
+    values: Vec<Option<Value>>,
+}
+
 // ========================================================================================
 // IMPLEMENTATIONS
 // ========================================================================================
 impl Row {
+    /// Validates `values` against the schema and claims them in `constraint_state`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `RowErrors` the values hit: count, type, NOT NULL, UNIQUE, CHECK
+    /// or composite unique. A rejected row claims nothing.
     pub fn new(
         schema: &Schema,
         constraint_state: &mut ConstraintState,
@@ -67,41 +122,111 @@ impl Row {
     }
 
     fn validate_and_apply_constraints(
-        values: &mut Vec<Value>,
+        values: &mut [Value],
         schema: &Schema,
         constraint_state: &mut ConstraintState,
     ) -> Result<(), RowErrors> {
         for (col, val) in schema.columns.iter().zip(values.iter_mut()) {
+            if *val == Value::Null {
+                *val = Self::resolve_default(col, constraint_state);
+            }
             Self::validate_type(val, &col.data_type, &col.name)?;
-            Self::apply_default_if_null(val, col, constraint_state);
             Self::check_not_null(val, col, constraint_state)?;
             Self::check_unique(val, col, constraint_state)?;
-            Self::check_if_indexed(val, col, constraint_state)?;
+        }
+        Self::check_predicates(values, schema)?;
+        let composite_keys = Self::check_composite_uniques(values, schema, constraint_state)?;
+
+        // Only claimed once every check has passed, so a rejected row leaves no trace.
+        for (col, val) in schema.columns.iter().zip(values.iter()) {
+            constraint_state.claim_unique(&col.name, val);
+        }
+        for (col, val) in schema.columns.iter().zip(values.iter()) {
+            if let Value::Integer(n) = val
+                && let Some(next) = constraint_state.next_auto_increment.get_mut(&col.name)
+            {
+                *next = (*next).max(n.saturating_add(1));
+            }
+        }
+        for (group, key) in schema.composite_uniques.iter().zip(composite_keys) {
+            if let Some(key) = key {
+                constraint_state.claim_composite(group, key);
+            }
         }
         Ok(())
     }
 
-    fn validate_type(val: &Value, expected_type: &DataType, col_name: &str) -> Result<(), RowErrors> {
-        if let Value::Null = val {
-            Ok(())
-        } else if val.get_data_type() != *expected_type {
-            Err(RowErrors::TypeMismatch {
+    /// Evaluates each column's CHECK predicate against the row, defaults applied.
+    fn check_predicates(values: &[Value], schema: &Schema) -> Result<(), RowErrors> {
+        let mut row = None;
+        for col in &schema.columns {
+            if let Some(Constraint::Check(predicate)) = col.constraints.get(&ConstraintKind::Check) {
+                let row = row.get_or_insert_with(|| Row { values: values.to_vec() });
+                if !Executor::satisfies_check(predicate, row, schema) {
+                    return Err(RowErrors::CheckViolated { column: col.name.clone() });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks every composite unique group, returning each group's key to claim, in
+    /// schema order. Groups with a NULL in the row have no key and are skipped.
+    fn check_composite_uniques(
+        values: &[Value],
+        schema: &Schema,
+        constraint_state: &ConstraintState,
+    ) -> Result<Vec<Option<Vec<Value>>>, RowErrors> {
+        let mut keys = Vec::with_capacity(schema.composite_uniques.len());
+        for group in &schema.composite_uniques {
+            let key = Self::composite_key(values, schema, group);
+            if let Some(key) = &key
+                && constraint_state.composite_uniques.get(group).is_some_and(|seen| seen.contains(key))
+            {
+                return Err(RowErrors::CompositeUniqueViolated { columns: group.clone(), values: key.clone() });
+            }
+            keys.push(key);
+        }
+        Ok(keys)
+    }
+
+    /// The row's values for `group`, or `None` if any is NULL.
+    pub(crate) fn composite_key(values: &[Value], schema: &Schema, group: &[String]) -> Option<Vec<Value>> {
+        group
+            .iter()
+            .map(|name| schema.get_column_index(name).map(|index| values[index].clone()))
+            .map(|value| value.filter(|value| *value != Value::Null))
+            .collect()
+    }
+
+    /// Checks the value fits the column, widening it to the column's type if needed.
+    fn validate_type(val: &mut Value, expected_type: &DataType, col_name: &str) -> Result<(), RowErrors> {
+        if !val.is_compatible_with(expected_type) {
+            return Err(RowErrors::TypeMismatch {
                 column: col_name.to_string(),
                 expected: expected_type.clone(),
                 got: val.clone(),
                 got_type: val.get_data_type(),
-            })
-        } else {
-            Ok(())
+            });
         }
+        val.widen_to(expected_type);
+        Ok(())
     }
 
-    fn apply_default_if_null(val: &mut Value, col: &Column, constraint_state: &ConstraintState) {
-        if *val == Value::Null {
-            if let Some(default_val) = constraint_state.default_values.get(&col.name) {
-                *val = default_val.clone();
-            }
+    /// The value an omitted (`Null`) column takes: its DEFAULT, else the next number
+    /// in its auto-increment sequence, else `Null`. Every insert resolves omitted
+    /// values here, including `DEFAULT` in `VALUES` (parsed as NULL), as do
+    /// `RowBuilder::build` and the revalidation that backfills stored rows when
+    /// `Table::add_constraint` adds a default.
+    #[must_use]
+    pub fn resolve_default(col: &Column, constraint_state: &ConstraintState) -> Value {
+        if let Some(default) = constraint_state.default_values.get(&col.name) {
+            return default.clone();
         }
+        constraint_state
+            .next_auto_increment
+            .get(&col.name)
+            .map_or(Value::Null, |next| Value::Integer(*next))
     }
 
     fn check_not_null(val: &Value, col: &Column, constraint_state: &ConstraintState) -> Result<(), RowErrors> {
@@ -117,30 +242,157 @@ impl Row {
     fn check_unique(
         val: &Value,
         col: &Column,
-        constraint_state: &mut ConstraintState,
+        constraint_state: &ConstraintState,
     ) -> Result<(), RowErrors> {
-        if *val != Value::Null {
-            if let Some(seen) = constraint_state.unique_values.get_mut(&col.name) {
-                if !seen.insert(val.clone()) {
-                    return Err(RowErrors::UniqueViolated {
-                        column: col.name.clone(),
-                        value: val.clone(),
-                    });
-                }
-            }
+        if *val != Value::Null
+            && let Some(seen) = constraint_state.unique_values.get(&col.name)
+            && seen.contains(val)
+        {
+            return Err(RowErrors::UniqueViolated {
+                column: col.name.clone(),
+                value: val.clone(),
+            });
         }
         Ok(())
     }
+}
 
-    fn check_if_indexed(
-        val: &Value,
-        col: &Column,
-        constraint_state: &mut ConstraintState,
-    ) -> Result<(), RowErrors> {
-        if let Some(index) = constraint_state.indexes.get_mut(&col.name) {
-            index.insert(val.clone());
+impl<'a> RowBuilder<'a> {
+    #[must_use]
+    pub fn new(schema: &'a Schema) -> Self {
+        Self { schema, values: vec![None; schema.columns.len()] }
+    }
+
+    /// Sets a column's value, replacing any earlier one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnknownColumn` or `TypeMismatch`.
+    pub fn set(mut self, column: &str, mut value: Value) -> Result<Self, RowErrors> {
+        let index = self
+            .schema
+            .get_column_index(column)
+            .ok_or_else(|| RowErrors::UnknownColumn { column: column.to_string() })?;
+        let col = &self.schema.columns[index];
+        Row::validate_type(&mut value, &col.data_type, &col.name)?;
+        self.values[index] = Some(value);
+        Ok(self)
+    }
+
+    /// Returns the values in schema order, resolving unset ones against the
+    /// table's `constraint_state`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotNullViolated` for a NOT NULL column left without a value.
+    pub fn build(self, constraint_state: &ConstraintState) -> Result<Vec<Value>, RowErrors> {
+        self.schema
+            .columns
+            .iter()
+            .zip(self.values)
+            .map(|(col, value)| {
+                let value = match value {
+                    Some(value) if value != Value::Null => value,
+                    _ => Row::resolve_default(col, constraint_state),
+                };
+                Row::check_not_null(&value, col, constraint_state)?;
+                Ok(value)
+            })
+            .collect()
+    }
+}
+
+impl Value {
+    fn variant_rank(&self) -> u8 {
+        match self {
+            Value::String(_) => 0,
+            Value::Integer(_) => 1,
+            Value::Float(_) => 2,
+            Value::Boolean(_) => 3,
+            Value::Null => 4,
+        }
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Integer(a), Value::Integer(b)) => a.cmp(b),
+            (Value::Float(a), Value::Float(b)) => a.total_cmp(b),
+            (Value::Boolean(a), Value::Boolean(b)) => a.cmp(b),
+            _ => self.variant_rank().cmp(&other.variant_rank()),
+        }
+    }
+}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.variant_rank().hash(state);
+        match self {
+            Value::String(s) => s.hash(state),
+            Value::Integer(i) => i.hash(state),
+            Value::Float(f) => f.to_bits().hash(state),
+            Value::Boolean(b) => b.hash(state),
+            Value::Null => {}
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Value {
+    pub fn to_json_value(&self) -> serde_json::Value {
+        match self {
+            Value::String(s) => serde_json::Value::String(s.clone()),
+            Value::Integer(i) => serde_json::Value::from(*i),
+            // JSON has no NaN/Infinity; those become null.
+            Value::Float(f) => serde_json::Number::from_f64(*f).map_or(serde_json::Value::Null, serde_json::Value::Number),
+            Value::Boolean(b) => serde_json::Value::Bool(*b),
+            Value::Null => serde_json::Value::Null,
         }
-        Ok(())
+    }
+
+    /// Reads a JSON scalar as a value of `target`. `null` is `Null` for any type;
+    /// an integral number fits an INTEGER or FLOAT column, any other number only a
+    /// FLOAT one. Arrays, objects and numbers outside `i64` are rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TypeMismatch` for a value that doesn't fit `target`.
+    pub fn from_json(v: &serde_json::Value, target: &DataType) -> Result<Value, ParseError> {
+        let mut value = match v {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Boolean(*b),
+            serde_json::Value::String(s) => Value::String(s.clone()),
+            serde_json::Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+                (Some(i), _) => Value::Integer(i),
+                (None, Some(f)) if n.is_f64() => Value::Float(f),
+                _ => return Err(ParseError::TypeMismatch { expected: target.clone(), got: v.to_string() }),
+            },
+            serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+                return Err(ParseError::TypeMismatch { expected: target.clone(), got: v.to_string() });
+            }
+        };
+
+        if !value.is_compatible_with(target) {
+            return Err(ParseError::TypeMismatch { expected: target.clone(), got: v.to_string() });
+        }
+        value.widen_to(target);
+        Ok(value)
     }
 }
 
@@ -156,11 +408,12 @@ mod tests {
     use crate::constraint_state::ConstraintState;
     use crate::schema::Schema;
     use crate::column::{DataType};
+    use crate::parser::{BinaryOperator, Expression, Literal};
 
 
     // Helper to create a simple schema for testing purposes.
     fn create_test_schema(columns: Vec<Column>) -> Schema {
-        return Schema::new(columns).unwrap();
+        Schema::new(columns).unwrap()
     }
 
     #[test]
@@ -177,6 +430,23 @@ mod tests {
         assert_eq!(values, vec![Value::Integer(0)]);
     }
     
+    #[test]
+    fn test_resolve_default() {
+        let schema = create_test_schema(vec![
+            ColumnBuilder::new("id", DataType::Integer).auto_increment().build(),
+            ColumnBuilder::new("status", DataType::String).default(Value::String("new".to_string())).unwrap().build(),
+            ColumnBuilder::new("note", DataType::String).build(),
+        ]);
+        let mut constraint_state = ConstraintState::new(&schema);
+        let resolve = |state: &ConstraintState| schema.columns.iter().map(|col| Row::resolve_default(col, state)).collect::<Vec<_>>();
+
+        assert_eq!(resolve(&constraint_state), vec![Value::Integer(1), Value::String("new".to_string()), Value::Null]);
+
+        // Sequences move past stored values; defaults don't change.
+        Row::new(&schema, &mut constraint_state, vec![Value::Integer(7), Value::Null, Value::Null]).unwrap();
+        assert_eq!(resolve(&constraint_state), vec![Value::Integer(8), Value::String("new".to_string()), Value::Null]);
+    }
+
     #[test]
     fn test_unique_constraint_works_correctly(){
         let column = ColumnBuilder::new("Status", DataType::String).unique().build();
@@ -390,26 +660,155 @@ mod tests {
 
 
     #[test]
-    fn test_indexed_column_populates_constraint_state() {
-        // Assuming ColumnBuilder has an `.indexed()` method
-        let column = ColumnBuilder::new("user_id", DataType::Integer).index().build();
+    fn test_float_values_work_as_unique_keys() {
+        let column = ColumnBuilder::new("price", DataType::Float).unique().build();
         let schema = create_test_schema(vec![column]);
         let mut constraint_state = ConstraintState::new(&schema);
 
-        // Initially, the index for the value should not exist
-        let value_to_insert = Value::Integer(12345);
-        let index = constraint_state.indexes.get("user_id").unwrap();
-        assert!(!index.contains(&value_to_insert));
+        Row::new(&schema, &mut constraint_state, vec![Value::Float(9.99)]).unwrap();
+        Row::new(&schema, &mut constraint_state, vec![Value::Float(1.5)]).unwrap();
+        let duplicate = Row::new(&schema, &mut constraint_state, vec![Value::Float(9.99)]);
 
-        // Create a new row, which should trigger `check_if_indexed`
-        Row::new(
-            &schema,
-            &mut constraint_state,
-            vec![value_to_insert.clone()],
-        ).unwrap();
+        assert!(matches!(duplicate, Err(RowErrors::UniqueViolated { .. })));
+    }
+
+    #[test]
+    fn test_float_ordering_is_total() {
+        assert_eq!(Value::Float(f64::NAN), Value::Float(f64::NAN));
+        assert!(Value::Float(f64::INFINITY) < Value::Float(f64::NAN));
+        assert!(Value::Float(-1.0) < Value::Float(0.5));
+        assert!(Value::Integer(100) < Value::Float(0.0));
+        assert!(Value::Float(0.0) < Value::Null);
+    }
+
+    #[test]
+    fn test_integer_is_widened_in_float_column() {
+        let schema = create_test_schema(vec![
+            ColumnBuilder::new("price", DataType::Float).build(),
+            ColumnBuilder::new("qty", DataType::Integer).build(),
+        ]);
+        let mut constraint_state = ConstraintState::new(&schema);
+
+        let row = Row::new(&schema, &mut constraint_state, vec![Value::Integer(3), Value::Integer(2)]).unwrap();
+        let narrowed = Row::new(&schema, &mut constraint_state, vec![Value::Float(3.0), Value::Float(2.0)]);
+
+        assert_eq!(row.values, vec![Value::Float(3.0), Value::Integer(2)]);
+        assert!(matches!(narrowed, Err(RowErrors::TypeMismatch { column, .. }) if column == "qty"));
+    }
+
+    fn builder_schema() -> Schema {
+        create_test_schema(vec![
+            ColumnBuilder::new("id", DataType::Integer).not_null().build(),
+            ColumnBuilder::new("name", DataType::String).build(),
+            ColumnBuilder::new("score", DataType::Float).default(Value::Float(1.5)).unwrap().build(),
+        ])
+    }
+
+    #[test]
+    fn row_builder_sets_columns_out_of_order() {
+        let schema = builder_schema();
+        let values = RowBuilder::new(&schema)
+            .set("score", Value::Integer(3)).unwrap()
+            .set("name", Value::String("Ada".to_string())).unwrap()
+            .set("id", Value::Integer(1)).unwrap()
+            .build(&ConstraintState::new(&schema))
+            .unwrap();
+
+        assert_eq!(values, vec![Value::Integer(1), Value::String("Ada".to_string()), Value::Float(3.0)]);
+    }
+
+    #[test]
+    fn row_builder_fills_defaults_and_nulls() {
+        let schema = builder_schema();
+        let mut state = ConstraintState::new(&schema);
+        let values = RowBuilder::new(&schema).set("id", Value::Integer(7)).unwrap().build(&state).unwrap();
+
+        assert_eq!(values, vec![Value::Integer(7), Value::Null, Value::Float(1.5)]);
+        assert!(Row::new(&schema, &mut state, values).is_ok());
+    }
 
-        // Now, the value should be present in the index
-        let index_after = constraint_state.indexes.get("user_id").unwrap();
-        assert!(index_after.contains(&value_to_insert));
+    #[test]
+    fn row_builder_reports_errors_by_column() {
+        let schema = builder_schema();
+
+        assert!(matches!(
+            RowBuilder::new(&schema).set("name", Value::Integer(1)),
+            Err(RowErrors::TypeMismatch { column, .. }) if column == "name"
+        ));
+        assert_eq!(
+            RowBuilder::new(&schema).set("nickname", Value::Null).unwrap_err(),
+            RowErrors::UnknownColumn { column: "nickname".to_string() }
+        );
+        assert_eq!(
+            RowBuilder::new(&schema).set("name", Value::String("Ada".to_string())).unwrap().build(&ConstraintState::new(&schema)),
+            Err(RowErrors::NotNullViolated { column: "id".to_string() })
+        );
+    }
+
+    #[test]
+    fn row_builder_takes_the_next_auto_increment_id() {
+        let schema = create_test_schema(vec![
+            ColumnBuilder::new("id", DataType::Integer).not_null().auto_increment().build(),
+            ColumnBuilder::new("name", DataType::String).build(),
+        ]);
+        let mut state = ConstraintState::new(&schema);
+        Row::new(&schema, &mut state, vec![Value::Integer(4), Value::Null]).unwrap();
+
+        let values = RowBuilder::new(&schema).set("name", Value::String("Ada".to_string())).unwrap().build(&state).unwrap();
+        assert_eq!(values, vec![Value::Integer(5), Value::String("Ada".to_string())]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_value_from_json() {
+        use serde_json::json;
+
+        assert_eq!(Value::from_json(&json!(null), &DataType::Integer), Ok(Value::Null));
+        assert_eq!(Value::from_json(&json!(7), &DataType::Integer), Ok(Value::Integer(7)));
+        assert_eq!(Value::from_json(&json!(7), &DataType::Float), Ok(Value::Float(7.0)));
+        assert_eq!(Value::from_json(&json!(1.5), &DataType::Float), Ok(Value::Float(1.5)));
+        assert_eq!(Value::from_json(&json!("x"), &DataType::String), Ok(Value::String("x".to_string())));
+        assert_eq!(Value::from_json(&json!(true), &DataType::Boolean), Ok(Value::Boolean(true)));
+
+        for (json, target) in [(json!(1.5), DataType::Integer), (json!("7"), DataType::Integer), (json!([1]), DataType::String), (json!(u64::MAX), DataType::Integer)] {
+            assert!(matches!(Value::from_json(&json, &target), Err(ParseError::TypeMismatch { .. })), "{json}");
+        }
+    }
+
+    fn check_schema(column: &str, data_type: DataType, predicate: Expression) -> Schema {
+        create_test_schema(vec![ColumnBuilder::new(column, data_type).check(predicate).build()])
+    }
+
+    fn column_compared(column: &str, op: BinaryOperator, literal: Literal) -> Expression {
+        Expression::Binary(
+            Box::new(Expression::Identifier(column.to_string(), None)),
+            op,
+            Box::new(Expression::Literal(literal, None)),
+        )
+    }
+
+    #[test]
+    fn test_check_constraint_on_integer_column() {
+        let schema = check_schema("age", DataType::Integer, column_compared("age", BinaryOperator::GreaterThanOrEquals, Literal::Integer(0)));
+        let mut constraint_state = ConstraintState::new(&schema);
+
+        assert!(Row::new(&schema, &mut constraint_state, vec![Value::Integer(30)]).is_ok());
+        assert!(Row::new(&schema, &mut constraint_state, vec![Value::Null]).is_ok());
+        assert_eq!(
+            Row::new(&schema, &mut constraint_state, vec![Value::Integer(-1)]),
+            Err(RowErrors::CheckViolated { column: "age".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_check_constraint_on_string_column() {
+        let schema = check_schema("status", DataType::String, column_compared("status", BinaryOperator::NotEquals, Literal::String(String::new())));
+        let mut constraint_state = ConstraintState::new(&schema);
+
+        assert!(Row::new(&schema, &mut constraint_state, vec![Value::String("open".to_string())]).is_ok());
+        assert_eq!(
+            Row::new(&schema, &mut constraint_state, vec![Value::String(String::new())]),
+            Err(RowErrors::CheckViolated { column: "status".to_string() })
+        );
     }
 }
@@ -1,5 +1,7 @@
 use crate::column::{Column, DataType};
-use crate::row::Value;
+use crate::constraint_state::{Constraint, ConstraintKind};
+use crate::parser::{BinaryOperator, Expression, Literal};
+use crate::row::{RowErrors, Value};
 use std::collections::HashMap;
 use thiserror::Error;
 
@@ -15,6 +17,33 @@ pub enum SchemaError {
     IndexOutOfBounds { name: String, index: usize, len: usize },
     #[error("Default value type mismatch for column '{column_name}'")]
     DefaultValueTypeMismatch { column_name: String },
+    #[error("Column '{0}' does not exist")]
+    ColumnNotFound(String),
+    #[error("Auto-increment column '{column_name}' must be INTEGER")]
+    AutoIncrementNotInteger { column_name: String },
+    #[error("CHECK on column '{column_name}' references unknown column '{reference}'")]
+    CheckReferencesUnknownColumn { column_name: String, reference: String },
+    #[error("Column '{column}' is referenced by the CHECK on column '{check_column}'")]
+    ColumnInCheck { column: String, check_column: String },
+}
+
+/// How table and column names in a query are matched against stored names.
+///
+/// Defaults to `CaseSensitive` so existing callers are unaffected; SQL treats
+/// unquoted identifiers case-insensitively, which `CaseInsensitive` opts into.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NameResolution {
+    #[default]
+    CaseSensitive,
+    CaseInsensitive,
+}
+
+impl NameResolution {
+    /// Lowercases a name for lookup in the normalized maps.
+    #[must_use]
+    pub fn normalize(name: &str) -> String {
+        name.to_lowercase()
+    }
 }
 
 // ========================================================================================
@@ -24,6 +53,8 @@ pub enum SchemaError {
 pub struct Schema {
     pub columns: Vec<Column>,
     pub name_to_index: HashMap<String, usize>, // fast lookup
+    pub normalized_name_to_index: HashMap<String, usize>, // case-insensitive lookup
+    pub composite_uniques: Vec<Vec<String>>, // column groups whose combined values must be unique
 }
 
 
@@ -31,25 +62,54 @@ pub struct Schema {
 // IMPLEMENTATIONS
 // ========================================================================================
 impl Schema {
+    /// Builds a schema from columns in declaration order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DuplicateColumnName` if two columns share a name,
+    /// `DefaultValueTypeMismatch` if a default doesn't fit its column, or
+    /// `CheckReferencesUnknownColumn` if a CHECK names a column the schema lacks.
     pub fn new(columns: Vec<Column>) -> Result<Self, SchemaError> {
         Self::validate_default_value_types(&columns)?;
         let name_to_index = Self::build_name_to_index_map(&columns)?;
-        Ok(Self { columns, name_to_index })
+        for col in &columns {
+            if let Some(Constraint::Check(predicate)) = col.constraints.get(&ConstraintKind::Check) {
+                Self::validate_check_references(&col.name, predicate, &name_to_index)?;
+            }
+        }
+        let normalized_name_to_index = Self::build_normalized_name_map(&columns);
+        Ok(Self { columns, name_to_index, normalized_name_to_index, composite_uniques: Vec::new() })
     }
 
     fn validate_default_value_types(columns: &[Column]) -> Result<(), SchemaError> {
-        for col in columns.iter() {
-            if let Some(constraint) = col.constraints.get(&crate::constraint_state::ConstraintKind::Default) {
-                if let crate::constraint_state::Constraint::WithValue(_, val) = constraint {
-                    if val.get_data_type() != col.data_type && val.get_data_type() != DataType::Null {
-                        return Err(SchemaError::DefaultValueTypeMismatch { column_name: col.name.clone() });
-                    }
-                }
+        for col in columns {
+            if let Some(Constraint::WithValue(_, val)) = col.constraints.get(&ConstraintKind::Default)
+                && !val.is_compatible_with(&col.data_type)
+            {
+                return Err(SchemaError::DefaultValueTypeMismatch { column_name: col.name.clone() });
+            }
+            if col.constraints.contains_key(&ConstraintKind::AutoIncrement) && col.data_type != DataType::Integer {
+                return Err(SchemaError::AutoIncrementNotInteger { column_name: col.name.clone() });
             }
         }
         Ok(())
     }
 
+    // CHECK predicates match column names exactly, like `satisfies_check`.
+    fn validate_check_references(
+        column_name: &str,
+        predicate: &Expression,
+        name_to_index: &HashMap<String, usize>,
+    ) -> Result<(), SchemaError> {
+        match predicate.column_references().into_iter().find(|name| !name_to_index.contains_key(*name)) {
+            Some(reference) => Err(SchemaError::CheckReferencesUnknownColumn {
+                column_name: column_name.to_string(),
+                reference: reference.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
     fn build_name_to_index_map(columns: &[Column]) -> Result<HashMap<String, usize>, SchemaError> {
         let mut name_to_index: HashMap<String, usize> = HashMap::with_capacity(columns.len());
 
@@ -62,32 +122,360 @@ impl Schema {
         Ok(name_to_index)
     }
 
+    // Columns differing only by case resolve to the first one declared.
+    fn build_normalized_name_map(columns: &[Column]) -> HashMap<String, usize> {
+        let mut normalized: HashMap<String, usize> = HashMap::with_capacity(columns.len());
+        for (i, col) in columns.iter().enumerate() {
+            normalized.entry(NameResolution::normalize(&col.name)).or_insert(i);
+        }
+        normalized
+    }
+
+    #[must_use]
     pub fn get_column_by_name(&self, name: &str) -> Option<&Column> {
         self.name_to_index.get(name).map(|&idx| &self.columns[idx])
     }
     
+    #[must_use]
     pub fn get_column_by_index(&self, index: usize) -> Option<&Column> {
         self.columns.get(index)
     }
 
+    #[must_use]
     pub fn get_column_index(&self, name: &str) -> Option<usize> {
         self.name_to_index.get(name).copied()
     }
 
-// get_column_index(&self, name: &str) -> Option<usize>
-// column_count(&self) -> usize
+    #[must_use]
+    pub fn resolve_column_index(&self, name: &str, resolution: NameResolution) -> Option<usize> {
+        match resolution {
+            NameResolution::CaseSensitive => self.get_column_index(name),
+            NameResolution::CaseInsensitive => self
+                .normalized_name_to_index
+                .get(&NameResolution::normalize(name))
+                .copied(),
+        }
+    }
+
+    /// Renders a `CREATE TABLE` statement for this schema. Identifiers are double-quoted,
+    /// so any name survives the round trip. Column constraints are written in a fixed
+    /// order (`NOT NULL`, `UNIQUE`, `DEFAULT`, `AUTO_INCREMENT`, `CHECK`) so the output is
+    /// stable.
+    ///
+    /// Indexes and composite uniques have no `CREATE TABLE` syntax and are not rendered;
+    /// re-parsing the output drops them.
+    #[must_use]
+    pub fn to_create_sql(&self, table_name: &str) -> String {
+        let column_defs: Vec<String> = self
+            .columns
+            .iter()
+            .map(|col| {
+                let mut def = format!("{} {}", quote_identifier(&col.name), col.data_type);
+                if col.constraints.contains_key(&ConstraintKind::NotNull) {
+                    def.push_str(" NOT NULL");
+                }
+                if col.constraints.contains_key(&ConstraintKind::Unique) {
+                    def.push_str(" UNIQUE");
+                }
+                if let Some(Constraint::WithValue(_, val)) = col.constraints.get(&ConstraintKind::Default) {
+                    def.push_str(" DEFAULT ");
+                    def.push_str(&val.to_sql_literal());
+                }
+                if col.constraints.contains_key(&ConstraintKind::AutoIncrement) {
+                    def.push_str(" AUTO_INCREMENT");
+                }
+                if let Some(Constraint::Check(predicate)) = col.constraints.get(&ConstraintKind::Check) {
+                    def.push_str(" CHECK ");
+                    def.push_str(&expression_to_sql(predicate));
+                }
+                def
+            })
+            .collect();
+
+        format!("CREATE TABLE {} ({});", quote_identifier(table_name), column_defs.join(", "))
+    }
+
+    /// Adds `constraint` to a column, replacing any constraint of the same kind.
+    /// Only the schema changes; use `Table::add_constraint` on a live table so the
+    /// existing rows are revalidated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ColumnNotFound` if the column doesn't exist, or the error for a constraint
+    /// that doesn't fit it, such as `CheckReferencesUnknownColumn` for a CHECK naming a
+    /// column the schema lacks.
+    pub fn add_constraint_to_column(&mut self, column: &str, constraint: Constraint) -> Result<(), SchemaError> {
+        let index = self.get_column_index(column).ok_or_else(|| SchemaError::ColumnNotFound(column.to_string()))?;
+        let col = &mut self.columns[index];
+
+        let constraint = match constraint {
+            Constraint::WithValue(ConstraintKind::Default, mut value) => {
+                if !value.is_compatible_with(&col.data_type) {
+                    return Err(SchemaError::DefaultValueTypeMismatch { column_name: col.name.clone() });
+                }
+                value.widen_to(&col.data_type);
+                Constraint::WithValue(ConstraintKind::Default, value)
+            }
+            Constraint::Unit(ConstraintKind::AutoIncrement) if col.data_type != DataType::Integer => {
+                return Err(SchemaError::AutoIncrementNotInteger { column_name: col.name.clone() });
+            }
+            Constraint::Check(predicate) => {
+                Self::validate_check_references(&col.name, &predicate, &self.name_to_index)?;
+                Constraint::Check(predicate)
+            }
+            other => other,
+        };
+        col.constraints.insert(constraint.kind(), constraint);
+        Ok(())
+    }
 
+    /// Requires the values of `columns`, taken together, to be unique. A row with
+    /// NULL in any of them is exempt, as with single-column UNIQUE. Registering the
+    /// same group twice is a no-op. Only the schema changes; use
+    /// `Table::add_composite_unique` on a live table.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ColumnNotFound` for the first of `columns` that doesn't exist.
+    pub fn add_composite_unique(&mut self, columns: &[String]) -> Result<(), SchemaError> {
+        if let Some(missing) = columns.iter().find(|name| !self.name_to_index.contains_key(*name)) {
+            return Err(SchemaError::ColumnNotFound(missing.clone()));
+        }
+        if !self.composite_uniques.iter().any(|group| group == columns) {
+            self.composite_uniques.push(columns.to_vec());
+        }
+        Ok(())
+    }
+
+    /// Removes a column and returns it, along with any composite unique covering it.
+    /// Later columns shift down one place, so both name maps are rebuilt. Only the
+    /// schema changes; use `Table::drop_column` on a live table so the rows lose the
+    /// value too.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ColumnNotFound` if the column doesn't exist, or `ColumnInCheck` if
+    /// another column's CHECK references it.
+    pub fn drop_column(&mut self, column: &str) -> Result<Column, SchemaError> {
+        let index = self.get_column_index(column).ok_or_else(|| SchemaError::ColumnNotFound(column.to_string()))?;
+        let dependent_check = self.columns.iter().enumerate().find(|(i, col)| {
+            *i != index
+                && matches!(col.constraints.get(&ConstraintKind::Check), Some(Constraint::Check(predicate)) if predicate.references_column(column))
+        });
+        if let Some((_, col)) = dependent_check {
+            return Err(SchemaError::ColumnInCheck { column: column.to_string(), check_column: col.name.clone() });
+        }
+        let removed = self.columns.remove(index);
+        self.composite_uniques.retain(|group| !group.contains(&removed.name));
+        self.name_to_index = Self::build_name_to_index_map(&self.columns)?;
+        self.normalized_name_to_index = Self::build_normalized_name_map(&self.columns);
+        Ok(removed)
+    }
+
+    /// Changes a column's type. A default is converted with `Value::cast_to`, and
+    /// rejected if it can't be; auto-increment columns must stay INTEGER. Only the
+    /// schema changes; use `Table::change_column_type` on a live table so the rows
+    /// are converted too.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ColumnNotFound`, `AutoIncrementNotInteger`, or `DefaultValueTypeMismatch`
+    /// if the default can't be converted.
+    pub fn change_column_type(&mut self, column: &str, data_type: DataType) -> Result<(), SchemaError> {
+        let index = self.get_column_index(column).ok_or_else(|| SchemaError::ColumnNotFound(column.to_string()))?;
+        let col = &mut self.columns[index];
+        if col.constraints.contains_key(&ConstraintKind::AutoIncrement) && data_type != DataType::Integer {
+            return Err(SchemaError::AutoIncrementNotInteger { column_name: col.name.clone() });
+        }
+        if let Some(Constraint::WithValue(_, value)) = col.constraints.get_mut(&ConstraintKind::Default) {
+            *value = value
+                .cast_to(&data_type)
+                .ok_or_else(|| SchemaError::DefaultValueTypeMismatch { column_name: col.name.clone() })?;
+        }
+        col.data_type = data_type;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Column names in declaration order.
+    #[must_use]
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns.iter().map(|col| col.name.as_str()).collect()
+    }
+
+    /// Exact-case lookup, like `get_column_index`.
+    #[must_use]
+    pub fn has_column(&self, name: &str) -> bool {
+        self.name_to_index.contains_key(name)
+    }
+
+    /// Cheap pre-check of a row: one value per column, each compatible with its
+    /// column's type. Needs no constraint state, so a batch can be screened before
+    /// the full validation in `Row::new`, which also fills defaults and enforces
+    /// constraints.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongValueCount` or `TypeMismatch` for the first problem found.
+    pub fn validate_row_shape(&self, values: &[Value]) -> Result<(), RowErrors> {
+        if values.len() != self.columns.len() {
+            return Err(RowErrors::WrongValueCount { expected: self.columns.len(), got: values.len() });
+        }
+        for (col, val) in self.columns.iter().zip(values) {
+            if !val.is_compatible_with(&col.data_type) {
+                return Err(RowErrors::TypeMismatch {
+                    column: col.name.clone(),
+                    expected: col.data_type.clone(),
+                    got: val.clone(),
+                    got_type: val.get_data_type(),
+                });
+            }
+        }
+        Ok(())
+    }
 }
 
 
 impl Value {
+    #[must_use]
     pub fn get_data_type(&self) -> DataType {
         match self {
             Value::String(_) => DataType::String,
             Value::Integer(_) => DataType::Integer,
+            Value::Float(_) => DataType::Float,
+            Value::Boolean(_) => DataType::Boolean,
             Value::Null => DataType::Null,
         }
    }
+
+    /// Whether the value can be stored in a column of `data_type`. `Null` fits any
+    /// column and integers widen into FLOAT columns; nothing narrows.
+    #[must_use]
+    pub fn is_compatible_with(&self, data_type: &DataType) -> bool {
+        match (self, data_type) {
+            (Value::Null, _) | (Value::Integer(_), DataType::Float) => true,
+            _ => self.get_data_type() == *data_type,
+        }
+    }
+
+    /// Converts a compatible value to `data_type`'s own variant, so an integer
+    /// stored in a FLOAT column becomes a `Float`. Other values are left alone.
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn widen_to(&mut self, data_type: &DataType) {
+        if let (Value::Integer(i), DataType::Float) = (&*self, data_type) {
+            *self = Value::Float(*i as f64);
+        }
+    }
+
+    /// Converts the value to `data_type`, or `None` if it has no equivalent there.
+    /// `Null` converts to anything. Numbers and booleans convert to their text;
+    /// text converts back if it parses (`true`/`false` in any case for BOOLEAN).
+    /// Integers widen to FLOAT, and a FLOAT narrows to INTEGER only when it's a
+    /// whole number in range. Booleans and integers convert as 1 and 0.
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    #[must_use]
+    pub fn cast_to(&self, data_type: &DataType) -> Option<Value> {
+        let value = match (self, data_type) {
+            (Value::Null, _) => Value::Null,
+            (value, data_type) if value.get_data_type() == *data_type => value.clone(),
+            (Value::Integer(i), DataType::String) => Value::String(i.to_string()),
+            (Value::Float(f), DataType::String) => Value::String(f.to_string()),
+            (Value::Boolean(b), DataType::String) => Value::String(b.to_string()),
+            (Value::String(s), DataType::Integer) => Value::Integer(s.trim().parse().ok()?),
+            (Value::String(s), DataType::Float) => Value::Float(s.trim().parse::<f64>().ok().filter(|f| f.is_finite())?),
+            (Value::String(s), DataType::Boolean) => Value::Boolean(match s.trim().to_lowercase().as_str() {
+                "true" => true,
+                "false" => false,
+                _ => return None,
+            }),
+            (Value::Integer(i), DataType::Float) => Value::Float(*i as f64),
+            // `i64::MAX as f64` rounds up to 2^63, so the upper bound is exclusive.
+            (Value::Float(f), DataType::Integer) if f.fract() == 0.0 && *f >= i64::MIN as f64 && *f < i64::MAX as f64 => {
+                Value::Integer(*f as i64)
+            }
+            (Value::Boolean(b), DataType::Integer) => Value::Integer(i64::from(*b)),
+            (Value::Integer(0), DataType::Boolean) => Value::Boolean(false),
+            (Value::Integer(1), DataType::Boolean) => Value::Boolean(true),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Renders the value as a SQL literal, e.g. for DEFAULT clauses.
+    #[must_use]
+    pub fn to_sql_literal(&self) -> String {
+        match self {
+            Value::String(s) => format!("'{}'", s.replace('\'', "''")),
+            Value::Integer(i) => i.to_string(),
+            Value::Float(f) => format!("{f:?}"), // Debug keeps the decimal point, e.g. 1.0
+            Value::Boolean(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
+            Value::Null => "NULL".to_string(),
+        }
+    }
+}
+
+// Wraps a name in double quotes, doubling any quote inside it.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// Renders a predicate the parser reads back as the same expression. Every compound
+// expression is parenthesized, so precedence never has to be reconstructed.
+fn expression_to_sql(expr: &Expression) -> String {
+    match expr {
+        Expression::Literal(literal, _) => literal_to_sql(literal),
+        Expression::Identifier(name, _) => quote_identifier(name),
+        Expression::Binary(left, op, right) => {
+            let op = match op {
+                BinaryOperator::Equals => "=",
+                BinaryOperator::NotEquals => "!=",
+                BinaryOperator::GreaterThan => ">",
+                BinaryOperator::LessThan => "<",
+                BinaryOperator::GreaterThanOrEquals => ">=",
+                BinaryOperator::LessThanOrEquals => "<=",
+                BinaryOperator::And => "AND",
+                BinaryOperator::Or => "OR",
+                BinaryOperator::Like => "LIKE",
+                BinaryOperator::NotLike => "NOT LIKE",
+            };
+            format!("({} {op} {})", expression_to_sql(left), expression_to_sql(right))
+        }
+        Expression::IsNull(inner, negated) => {
+            let not = if *negated { "NOT " } else { "" };
+            format!("({} IS {not}NULL)", expression_to_sql(inner))
+        }
+        Expression::InList(inner, literals, negated) => {
+            let not = if *negated { "NOT " } else { "" };
+            let list: Vec<String> = literals.iter().map(literal_to_sql).collect();
+            format!("({} {not}IN ({}))", expression_to_sql(inner), list.join(", "))
+        }
+        Expression::IsDistinctFrom(left, right, negated) => {
+            let not = if *negated { "NOT " } else { "" };
+            format!("({} IS {not}DISTINCT FROM {})", expression_to_sql(left), expression_to_sql(right))
+        }
+        Expression::Between { expr, low, high } => format!(
+            "({} BETWEEN {} AND {})",
+            expression_to_sql(expr),
+            expression_to_sql(low),
+            expression_to_sql(high)
+        ),
+        Expression::Not(inner) => format!("(NOT {})", expression_to_sql(inner)),
+    }
+}
+
+fn literal_to_sql(literal: &Literal) -> String {
+    let value = match literal {
+        Literal::String(s) => Value::String(s.clone()),
+        Literal::Integer(i) => Value::Integer(*i),
+        Literal::Float(f) => Value::Float(*f),
+        Literal::Boolean(b) => Value::Boolean(*b),
+        Literal::Null => Value::Null,
+    };
+    value.to_sql_literal()
 }
 
 // ========================================================================================
@@ -138,4 +526,231 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_resolve_column_index_by_case() {
+        let schema = SchemaBuilder::new()
+            .add_column(ColumnBuilder::new("id", DataType::Integer).build())
+            .add_column(ColumnBuilder::new("UserName", DataType::String).build())
+            .build()
+            .unwrap();
+
+        assert_eq!(schema.resolve_column_index("username", NameResolution::CaseSensitive), None);
+        assert_eq!(schema.resolve_column_index("UserName", NameResolution::CaseSensitive), Some(1));
+        assert_eq!(schema.resolve_column_index("username", NameResolution::CaseInsensitive), Some(1));
+        assert_eq!(schema.resolve_column_index("ID", NameResolution::CaseInsensitive), Some(0));
+    }
+
+    #[test]
+    fn test_to_create_sql() {
+        let schema = SchemaBuilder::new()
+            .add_column(ColumnBuilder::new("id", DataType::Integer).unique().not_null().build())
+            .add_column(ColumnBuilder::new("name", DataType::String).build())
+            .add_column(ColumnBuilder::new("role", DataType::String).default(Value::String("guest".to_string())).unwrap().build())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            schema.to_create_sql("users"),
+            r#"CREATE TABLE "users" ("id" INTEGER NOT NULL UNIQUE, "name" STRING, "role" STRING DEFAULT 'guest');"#
+        );
+        assert_eq!(Value::String("it's".to_string()).to_sql_literal(), "'it''s'");
+    }
+
+    #[test]
+    fn test_value_type_compatibility_matrix() {
+        let values = [
+            Value::String("a".to_string()),
+            Value::Integer(1),
+            Value::Float(1.5),
+            Value::Boolean(true),
+            Value::Null,
+        ];
+        let types = [DataType::String, DataType::Integer, DataType::Float, DataType::Boolean, DataType::Null];
+        let compatible = [
+            //  String Integer Float  Boolean Null
+            [true,  false, false, false, false], // String
+            [false, true,  true,  false, false], // Integer
+            [false, false, true,  false, false], // Float
+            [false, false, false, true,  false], // Boolean
+            [true,  true,  true,  true,  true ], // Null
+        ];
+
+        for (value, row) in values.iter().zip(compatible) {
+            for (data_type, expected) in types.iter().zip(row) {
+                assert_eq!(value.is_compatible_with(data_type), expected, "{value:?} into {data_type}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_constraint_to_column() {
+        let mut schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("score", DataType::Float).build(),
+        ])
+        .unwrap();
+
+        schema.add_constraint_to_column("id", Constraint::Unit(ConstraintKind::Unique)).unwrap();
+        assert!(schema.columns[0].constraints.contains_key(&ConstraintKind::Unique));
+
+        schema
+            .add_constraint_to_column("score", Constraint::WithValue(ConstraintKind::Default, Value::Integer(1)))
+            .unwrap();
+        assert_eq!(
+            schema.columns[1].constraints.get(&ConstraintKind::Default),
+            Some(&Constraint::WithValue(ConstraintKind::Default, Value::Float(1.0)))
+        );
+
+        assert_eq!(
+            schema.add_constraint_to_column("id", Constraint::WithValue(ConstraintKind::Default, Value::Boolean(true))),
+            Err(SchemaError::DefaultValueTypeMismatch { column_name: "id".to_string() })
+        );
+        assert_eq!(
+            schema.add_constraint_to_column("missing", Constraint::Unit(ConstraintKind::NotNull)),
+            Err(SchemaError::ColumnNotFound("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_drop_column_rebuilds_name_maps() {
+        let mut schema = SchemaBuilder::new()
+            .add_column(ColumnBuilder::new("id", DataType::Integer).build())
+            .add_column(ColumnBuilder::new("Name", DataType::String).build())
+            .add_column(ColumnBuilder::new("age", DataType::Integer).build())
+            .build()
+            .unwrap();
+
+        assert_eq!(schema.drop_column("Name").map(|col| col.name), Ok("Name".to_string()));
+        assert_eq!(schema.get_column_index("age"), Some(1));
+        assert_eq!(schema.resolve_column_index("name", NameResolution::CaseInsensitive), None);
+        assert_eq!(schema.drop_column("Name"), Err(SchemaError::ColumnNotFound("Name".to_string())));
+    }
+
+    #[test]
+    fn test_check_must_reference_existing_columns() {
+        let predicate = |name: &str| Expression::Binary(
+            Box::new(Expression::Identifier(name.to_string(), None)),
+            BinaryOperator::GreaterThan,
+            Box::new(Expression::Literal(Literal::Integer(0), None)),
+        );
+        let unknown = || SchemaError::CheckReferencesUnknownColumn { column_name: "qty".to_string(), reference: "quantity".to_string() };
+
+        let result = Schema::new(vec![ColumnBuilder::new("qty", DataType::Integer).check(predicate("quantity")).build()]);
+        assert_eq!(result, Err(unknown()));
+
+        let mut schema = SchemaBuilder::new()
+            .add_column(ColumnBuilder::new("qty", DataType::Integer).build())
+            .add_column(ColumnBuilder::new("price", DataType::Integer).build())
+            .build()
+            .unwrap();
+        assert_eq!(schema.add_constraint_to_column("qty", Constraint::Check(predicate("quantity"))), Err(unknown()));
+        schema.add_constraint_to_column("qty", Constraint::Check(predicate("price"))).unwrap();
+
+        assert_eq!(
+            schema.drop_column("price"),
+            Err(SchemaError::ColumnInCheck { column: "price".to_string(), check_column: "qty".to_string() })
+        );
+        assert!(schema.drop_column("qty").is_ok(), "a column's own CHECK goes with it");
+        assert!(schema.drop_column("price").is_ok());
+    }
+
+    #[test]
+    fn test_column_introspection() {
+        let empty = SchemaBuilder::new().build().unwrap();
+        assert_eq!(empty.column_count(), 0);
+        assert!(empty.column_names().is_empty());
+        assert!(!empty.has_column("id"));
+
+        let schema = SchemaBuilder::new()
+            .add_column(ColumnBuilder::new("id", DataType::Integer).build())
+            .add_column(ColumnBuilder::new("name", DataType::String).build())
+            .add_column(ColumnBuilder::new("age", DataType::Integer).build())
+            .build()
+            .unwrap();
+        assert_eq!(schema.column_count(), 3);
+        assert_eq!(schema.column_names(), vec!["id", "name", "age"]);
+        assert!(schema.has_column("name"));
+        assert!(!schema.has_column("NAME"));
+        assert!(!schema.has_column("email"));
+    }
+
+    #[test]
+    fn test_cast_to() {
+        let cases = [
+            (Value::Integer(42), DataType::String, Some(Value::String("42".to_string()))),
+            (Value::Float(1.5), DataType::String, Some(Value::String("1.5".to_string()))),
+            (Value::Boolean(true), DataType::String, Some(Value::String("true".to_string()))),
+            (Value::String(" 42 ".to_string()), DataType::Integer, Some(Value::Integer(42))),
+            (Value::String("4.2".to_string()), DataType::Integer, None),
+            (Value::String("123456789012345678901234567890".to_string()), DataType::Integer, None),
+            (Value::String("2.5".to_string()), DataType::Float, Some(Value::Float(2.5))),
+            (Value::String("inf".to_string()), DataType::Float, None),
+            (Value::String("FALSE".to_string()), DataType::Boolean, Some(Value::Boolean(false))),
+            (Value::String("yes".to_string()), DataType::Boolean, None),
+            (Value::Integer(3), DataType::Float, Some(Value::Float(3.0))),
+            (Value::Float(3.0), DataType::Integer, Some(Value::Integer(3))),
+            (Value::Float(3.5), DataType::Integer, None),
+            (Value::Float(1e19), DataType::Integer, None),
+            (Value::Boolean(true), DataType::Integer, Some(Value::Integer(1))),
+            (Value::Integer(0), DataType::Boolean, Some(Value::Boolean(false))),
+            (Value::Integer(2), DataType::Boolean, None),
+            (Value::Null, DataType::Integer, Some(Value::Null)),
+            (Value::Integer(1), DataType::Null, None),
+        ];
+        for (value, data_type, expected) in cases {
+            assert_eq!(value.cast_to(&data_type), expected, "{value:?} to {data_type}");
+        }
+    }
+
+    #[test]
+    fn test_change_column_type_converts_default() {
+        let mut schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).auto_increment().build(),
+            ColumnBuilder::new("code", DataType::String).default(Value::String("7".to_string())).unwrap().build(),
+            ColumnBuilder::new("label", DataType::String).default(Value::String("none".to_string())).unwrap().build(),
+        ])
+        .unwrap();
+
+        schema.change_column_type("code", DataType::Integer).unwrap();
+        assert_eq!(schema.columns[1].data_type, DataType::Integer);
+        assert_eq!(
+            schema.columns[1].constraints[&ConstraintKind::Default],
+            Constraint::WithValue(ConstraintKind::Default, Value::Integer(7))
+        );
+        assert_eq!(
+            schema.change_column_type("label", DataType::Integer),
+            Err(SchemaError::DefaultValueTypeMismatch { column_name: "label".to_string() })
+        );
+        assert_eq!(
+            schema.change_column_type("id", DataType::String),
+            Err(SchemaError::AutoIncrementNotInteger { column_name: "id".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_validate_row_shape() {
+        let schema = SchemaBuilder::new()
+            .add_column(ColumnBuilder::new("id", DataType::Integer).not_null().build())
+            .add_column(ColumnBuilder::new("score", DataType::Float).build())
+            .build()
+            .unwrap();
+
+        // NULLs and widenable integers pass; NOT NULL is left to full validation.
+        assert_eq!(schema.validate_row_shape(&[Value::Integer(1), Value::Integer(2)]), Ok(()));
+        assert_eq!(schema.validate_row_shape(&[Value::Null, Value::Null]), Ok(()));
+        assert_eq!(
+            schema.validate_row_shape(&[Value::Integer(1)]),
+            Err(RowErrors::WrongValueCount { expected: 2, got: 1 })
+        );
+        assert_eq!(
+            schema.validate_row_shape(&[Value::String("1".to_string()), Value::Float(2.0)]),
+            Err(RowErrors::TypeMismatch {
+                column: "id".to_string(),
+                expected: DataType::Integer,
+                got: Value::String("1".to_string()),
+                got_type: DataType::String,
+            })
+        );
+    }
 }
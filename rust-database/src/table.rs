@@ -1,70 +1,748 @@
-use std::collections::HashMap;
-use crate::constraint_state::{ConstraintState};
-use crate::schema::Schema;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime};
+use crate::column::DataType;
+use crate::constraint_state::{Constraint, ConstraintState};
+use crate::schema::{Schema, SchemaError};
 use crate::row::{Row, Value, RowErrors}; 
+#[cfg(feature = "serde")]
+use crate::row::ParseError;
+use crate::parser::TruncateIdentity;
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+#[derive(Debug, PartialEq, Error)]
 pub enum TableErrors {
     #[error("Row construction failed: {0}")]
     RowConstructionError(#[from] RowErrors),
 
     #[error("Row with index {0} does not exist")]
     RowNotFound(u64),
+
+    #[error("Batch insert rejected at row {index}: {source}")]
+    BatchRowRejected { index: usize, source: RowErrors },
+
+    #[error("Column '{0}' does not exist")]
+    ColumnNotFound(String),
+
+    #[error("Schema change rejected: {0}")]
+    SchemaChangeRejected(#[from] SchemaError),
+
+    #[error("Cannot convert {value:?} in column '{column}' to {to}")]
+    ConversionFailed { column: String, value: Value, to: DataType },
+
+    #[error("Foreign key violated: {column} = {value:?} has no match in {references}")]
+    ForeignKeyViolation { column: String, value: Value, references: String },
+
+    /// The column is one end of a foreign key, so it can't be dropped or change type.
+    #[error("Column '{column}' is used by foreign key {foreign_key}")]
+    ColumnInForeignKey { column: String, foreign_key: String },
+
+    /// Deleting or changing a row would leave another row's foreign key without a match.
+    #[error("{value:?} is still referenced through foreign key {foreign_key}")]
+    RowStillReferenced { value: Value, foreign_key: String },
+
+    /// A stored foreign key names a column that no longer exists, so it can't be checked.
+    #[error("Foreign key {foreign_key} refers to a missing column")]
+    UnresolvedForeignKey { foreign_key: String },
+
+    /// Line `line` (one-based) of a JSON-lines import couldn't be read.
+    #[cfg(feature = "serde")]
+    #[error("JSON import failed on line {line}: {source}")]
+    JsonImport { line: usize, source: ParseError },
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Table {
     pub schema: Schema,
-    pub rows: HashMap<u64, Row>,
+    pub rows: BTreeMap<u64, Row>, // keyed by row id, so iteration follows insertion order
     pub constraint_state: ConstraintState,
+    next_row_id: u64, // never reused, even after deletes
+    composite_indexes: HashMap<Vec<String>, CompositeIndex>, // keyed by the indexed column names, in key order
+    expires_at: BTreeMap<u64, SystemTime>, // row id → expiry, for rows added with a TTL
+}
+
+/// Multi-column index: the values of `positions`, in order, map to the ids of the
+/// rows holding them. Keys sort lexicographically, so rows sharing a leading
+/// prefix are contiguous.
+#[derive(Clone, Debug)]
+struct CompositeIndex {
+    positions: Vec<usize>,
+    entries: BTreeMap<Vec<Value>, BTreeSet<u64>>,
+}
+
+/// Summary of an indexed column, read straight off its index. `cardinality`
+/// counts distinct non-NULL values; `min` and `max` are `None` when the column
+/// holds no non-NULL values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnStats {
+    pub cardinality: usize,
+    pub min: Option<Value>,
+    pub max: Option<Value>,
 }
 
 impl Table {
+    #[must_use]
     pub fn new(schema: Schema) -> Self {
         let constraint_state = ConstraintState::new(&schema);
         Table {
             schema,
-            rows: HashMap::new(),
+            rows: BTreeMap::new(),
             constraint_state,
+            next_row_id: 0,
+            composite_indexes: HashMap::new(),
+            expires_at: BTreeMap::new(),
         }
     }
 
+    /// Validates a row and stores it, returning its row id.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RowConstructionError` if the row doesn't fit the schema or breaks a
+    /// constraint.
     pub fn add_row(&mut self, row_values: Vec<Value>) -> Result<u64, TableErrors> {
         let row = Row::new(&self.schema, &mut self.constraint_state, row_values)?; // Validate row
-        let row_id = self.rows.len() as u64;
-        self.rows.insert(row_id, row);
+        Ok(self.insert_validated(row))
+    }
+
+    /// Like `add_row`, but the row expires `ttl_secs` seconds from now and is removed
+    /// by the next `evict_expired` after that. A TTL too large to represent never
+    /// expires.
+    ///
+    /// # Errors
+    ///
+    /// Fails as `add_row` does.
+    pub fn add_row_with_ttl(&mut self, row_values: Vec<Value>, ttl_secs: u64) -> Result<u64, TableErrors> {
+        let row_id = self.add_row(row_values)?;
+        if let Some(expiry) = SystemTime::now().checked_add(Duration::from_secs(ttl_secs)) {
+            self.expires_at.insert(row_id, expiry);
+        }
         Ok(row_id)
     }
 
+    /// When a row added with a TTL expires; `None` for rows that never do.
+    #[must_use]
+    pub fn expires_at(&self, row_id: u64) -> Option<SystemTime> {
+        self.expires_at.get(&row_id).copied()
+    }
+
+    /// Deletes every row whose expiry is at or before `now`, releasing its unique and
+    /// index entries like `delete_row`, and returns how many were removed. Until then
+    /// expired rows are still stored and still seen by queries.
+    pub fn evict_expired(&mut self, now: SystemTime) -> usize {
+        let expired: Vec<u64> = self.expires_at.iter().filter(|(_, expiry)| **expiry <= now).map(|(id, _)| *id).collect();
+        expired.into_iter().filter(|id| self.delete_row(*id).is_ok()).count()
+    }
+
+    /// Inserts every row or none of them. Every row's shape is checked first, before
+    /// copying the constraint state; rows are then validated against that scratch
+    /// copy, so duplicates within the batch are caught and a rejected batch leaves
+    /// the unique sets untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BatchRowRejected` with the index of the first row that doesn't fit.
+    pub fn add_rows(&mut self, batch: Vec<Vec<Value>>) -> Result<Vec<u64>, TableErrors> {
+        for (index, row_values) in batch.iter().enumerate() {
+            self.schema
+                .validate_row_shape(row_values)
+                .map_err(|source| TableErrors::BatchRowRejected { index, source })?;
+        }
+
+        let mut scratch_state = self.constraint_state.clone();
+        let mut validated = Vec::with_capacity(batch.len());
+
+        for (index, row_values) in batch.into_iter().enumerate() {
+            let row = Row::new(&self.schema, &mut scratch_state, row_values)
+                .map_err(|source| TableErrors::BatchRowRejected { index, source })?;
+            validated.push(row);
+        }
+
+        self.constraint_state = scratch_state;
+        Ok(validated.into_iter().map(|row| self.insert_validated(row)).collect())
+    }
+
+    /// Removes a row, releasing its unique and index entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RowNotFound` if no row has that id.
     pub fn delete_row(&mut self, index: u64) -> Result<(), TableErrors> {
-        if self.rows.remove(&index).is_none() {
+        let Some(row) = self.rows.remove(&index) else {
             return Err(TableErrors::RowNotFound(index));
-        }
+        };
+        self.expires_at.remove(&index);
+        self.release_constraints(index, &row);
+        self.unindex_composite(index, &row);
         Ok(())
     }
 
+    /// Replaces a row's values. The old row's unique/index entries are released before
+    /// validating, so a row can keep its own unique value; if validation fails the
+    /// old row and its entries are put back unchanged. The new values go through
+    /// the same validation as `add_row`, so a `Null` in a column with a default is
+    /// replaced by that default.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RowNotFound` for an unknown id, or `RowConstructionError` if the new values
+    /// are rejected.
     pub fn edit_row(&mut self, index: u64, row_values: Vec<Value>) -> Result<(), TableErrors> {
-        if !self.rows.contains_key(&index) {
+        self.replace_row(index, row_values).map(drop)
+    }
+
+    /// Applies several `edit_row`s as one: if any edit is rejected, the rows edited
+    /// before it are put back and the constraint state is rolled back to where it
+    /// started, so the table is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error an edit hits, as `edit_row` would.
+    pub fn edit_rows(&mut self, edits: Vec<(u64, Vec<Value>)>) -> Result<(), TableErrors> {
+        let snapshot = self.constraint_state.snapshot();
+        let mut replaced = Vec::with_capacity(edits.len());
+        for (index, row_values) in edits {
+            match self.replace_row(index, row_values) {
+                Ok(old_row) => replaced.push((index, old_row)),
+                Err(err) => {
+                    for (index, old_row) in replaced.into_iter().rev() {
+                        if let Some(new_row) = self.rows.remove(&index) {
+                            self.unindex_composite(index, &new_row);
+                        }
+                        self.index_composite(index, &old_row);
+                        self.rows.insert(index, old_row);
+                    }
+                    self.constraint_state.restore(snapshot);
+                    return Err(err);
+                }
+            }
+        }
+        self.constraint_state.commit(snapshot);
+        Ok(())
+    }
+
+    /// `edit_row`, returning the row that was replaced.
+    fn replace_row(&mut self, index: u64, row_values: Vec<Value>) -> Result<Row, TableErrors> {
+        let Some(old_row) = self.rows.remove(&index) else {
             return Err(TableErrors::RowNotFound(index));
+        };
+        self.release_constraints(index, &old_row);
+
+        // A rejected row claims nothing, so only the release needs undoing.
+        match Row::new(&self.schema, &mut self.constraint_state, row_values) {
+            Ok(row) => {
+                self.index_columns(index, &row);
+                self.unindex_composite(index, &old_row);
+                self.index_composite(index, &row);
+                self.rows.insert(index, row);
+                Ok(old_row)
+            }
+            Err(err) => {
+                self.reclaim_constraints(index, &old_row);
+                self.rows.insert(index, old_row);
+                Err(err.into())
+            }
         }
+    }
 
-        let row = Row::new(&self.schema, &mut self.constraint_state, row_values)?;
-        self.rows.insert(index, row);
-        Ok(())
+    /// Removes every row and resets the constraint state. `Restart` also rewinds the
+    /// row id and auto-increment sequences; `Continue` keeps them going.
+    pub fn truncate(&mut self, identity: TruncateIdentity) {
+        self.rows.clear();
+        self.expires_at.clear();
+        let next_auto_increment = std::mem::take(&mut self.constraint_state.next_auto_increment);
+        self.constraint_state = ConstraintState::new(&self.schema);
+        if identity == TruncateIdentity::Continue {
+            self.constraint_state.next_auto_increment = next_auto_increment;
+        }
+        for index in self.composite_indexes.values_mut() {
+            index.entries.clear();
+        }
+        if identity == TruncateIdentity::Restart {
+            self.next_row_id = 0;
+        }
     }
 
+    #[must_use]
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    #[must_use]
     pub fn get_row(&self, index: u64) -> Option<&Row> {
         self.rows.get(&index)
     }
+
+    /// The row with the lowest id, i.e. the oldest one still stored.
+    #[must_use]
+    pub fn first_row(&self) -> Option<(u64, &Row)> {
+        self.rows.first_key_value().map(|(&id, row)| (id, row))
+    }
+
+    /// The row with the highest id, i.e. the most recently inserted one still stored.
+    #[must_use]
+    pub fn last_row(&self) -> Option<(u64, &Row)> {
+        self.rows.last_key_value().map(|(&id, row)| (id, row))
+    }
+
+    /// Picks up to `n` rows, reproducibly for a given `seed`. Uses selection sampling
+    /// (Knuth's Algorithm S), so the sample comes back in row-id order.
+    #[must_use]
+    pub fn sample(&self, n: usize, seed: u64) -> Vec<&Row> {
+        let sample_size = n.min(self.rows.len());
+        let mut rng = SplitMix64::new(seed);
+        let mut needed = sample_size as u64;
+        let mut remaining = self.rows.len() as u64;
+        let mut sample = Vec::with_capacity(sample_size);
+
+        for row in self.rows.values() {
+            if needed == 0 {
+                break;
+            }
+            if rng.next_u64() % remaining < needed {
+                sample.push(row);
+                needed -= 1;
+            }
+            remaining -= 1;
+        }
+        sample
+    }
+
+    /// Writes the table as RFC 4180 CSV: a header of column names, then one record
+    /// per row in row-id order, each ending in CRLF. Strings are always quoted with
+    /// embedded quotes doubled; NULL is an empty field.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to `writer`.
+    pub fn export_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let header: Vec<String> = self.schema.columns.iter().map(|col| csv_field(&col.name)).collect();
+        write!(writer, "{}\r\n", header.join(","))?;
+
+        for row in self.rows.values() {
+            let fields: Vec<String> = row
+                .values
+                .iter()
+                .map(|value| match value {
+                    Value::String(s) => format!("\"{}\"", s.replace('"', "\"\"")),
+                    Value::Integer(i) => i.to_string(),
+                    Value::Float(f) => format!("{f:?}"),
+                    Value::Boolean(b) => b.to_string(),
+                    Value::Null => String::new(),
+                })
+                .collect();
+            write!(writer, "{}\r\n", fields.join(","))?;
+        }
+        Ok(())
+    }
+    /// Loads newline-delimited JSON objects keyed by column name, all or nothing,
+    /// through the same validation as `add_rows`. Missing keys are `Null` (so they
+    /// take the column's default); blank lines are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `JsonImport` with the line number of a line that isn't a valid row object,
+    /// or the error `add_rows` gives for a rejected row.
+    #[cfg(feature = "serde")]
+    pub fn import_json_lines(&mut self, input: &str) -> Result<Vec<u64>, TableErrors> {
+        let mut batch = Vec::new();
+        for (index, text) in input.lines().enumerate().filter(|(_, text)| !text.trim().is_empty()) {
+            let row_values = self
+                .json_row_values(text)
+                .map_err(|source| TableErrors::JsonImport { line: index + 1, source })?;
+            batch.push(row_values);
+        }
+        self.add_rows(batch)
+    }
+
+    #[cfg(feature = "serde")]
+    fn json_row_values(&self, text: &str) -> Result<Vec<Value>, ParseError> {
+        let json: serde_json::Value = serde_json::from_str(text).map_err(|err| ParseError::InvalidJson(err.to_string()))?;
+        let serde_json::Value::Object(object) = json else {
+            return Err(ParseError::NotAnObject { got: json.to_string() });
+        };
+
+        let mut values = vec![Value::Null; self.schema.columns.len()];
+        for (key, field) in &object {
+            let index = self.schema.get_column_index(key).ok_or_else(|| ParseError::UnknownColumn { column: key.clone() })?;
+            values[index] = Value::from_json(field, &self.schema.columns[index].data_type)?;
+        }
+        Ok(values)
+    }
+
+    /// Adds a constraint to a column and revalidates every stored row against the
+    /// new schema, rebuilding the constraint state from scratch. Like `add_row`,
+    /// a `Null` in a column that gains a default takes that default. If any row
+    /// fails, the table is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SchemaChangeRejected` if the schema refuses the constraint, or the row
+    /// error of the first stored row that breaks it.
+    pub fn add_constraint(&mut self, column: &str, constraint: Constraint) -> Result<(), TableErrors> {
+        let mut schema = self.schema.clone();
+        schema.add_constraint_to_column(column, constraint)?;
+
+        let row_values = self.rows.iter().map(|(&row_id, row)| (row_id, row.values.clone())).collect();
+        self.replace_schema(schema, row_values)
+    }
+
+    /// Requires `columns` to be unique together, checking every stored row first.
+    /// If two rows already share a combination, the table is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SchemaChangeRejected` for an unknown column, or the violation if stored
+    /// rows already share a combination.
+    pub fn add_composite_unique(&mut self, columns: &[String]) -> Result<(), TableErrors> {
+        let mut schema = self.schema.clone();
+        schema.add_composite_unique(columns)?;
+
+        let row_values = self.rows.iter().map(|(&row_id, row)| (row_id, row.values.clone())).collect();
+        self.replace_schema(schema, row_values)
+    }
+
+    /// Removes a column and its value from every row. The constraint state is
+    /// rebuilt for the remaining columns, and composite indexes covering the
+    /// column are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SchemaChangeRejected` if the column doesn't exist.
+    pub fn drop_column(&mut self, column: &str) -> Result<(), TableErrors> {
+        let mut schema = self.schema.clone();
+        schema.drop_column(column)?;
+        let position = self.schema.get_column_index(column).ok_or_else(|| TableErrors::ColumnNotFound(column.to_string()))?;
+
+        let row_values = self
+            .rows
+            .iter()
+            .map(|(&row_id, row)| {
+                let mut values = row.values.clone();
+                values.remove(position);
+                (row_id, values)
+            })
+            .collect();
+        self.replace_schema(schema, row_values)
+    }
+
+    /// Changes a column's type, converting every stored value with `Value::cast_to`.
+    /// If any value can't be converted, or a converted row breaks a constraint, the
+    /// table is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SchemaChangeRejected` if the schema refuses the type, `ConversionFailed`
+    /// for a value that can't be cast, or the row error of a converted row that breaks a
+    /// constraint.
+    pub fn change_column_type(&mut self, column: &str, data_type: DataType) -> Result<(), TableErrors> {
+        let mut schema = self.schema.clone();
+        schema.change_column_type(column, data_type)?;
+        let position = self.schema.get_column_index(column).ok_or_else(|| TableErrors::ColumnNotFound(column.to_string()))?;
+        let data_type = &schema.columns[position].data_type;
+
+        let row_values = self
+            .rows
+            .iter()
+            .map(|(&row_id, row)| {
+                let mut values = row.values.clone();
+                values[position] = values[position].cast_to(data_type).ok_or_else(|| TableErrors::ConversionFailed {
+                    column: column.to_string(),
+                    value: values[position].clone(),
+                    to: data_type.clone(),
+                })?;
+                Ok((row_id, values))
+            })
+            .collect::<Result<_, TableErrors>>()?;
+        self.replace_schema(schema, row_values)
+    }
+
+    /// Consistency check for debugging and tests: the constraint state matches the
+    /// schema (see `ConstraintState::validate_against_schema`) and every stored row
+    /// has the schema's shape.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first inconsistency found.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        self.constraint_state.validate_against_schema(&self.schema)?;
+        for (row_id, row) in &self.rows {
+            self.schema.validate_row_shape(&row.values).map_err(|err| format!("row {row_id}: {err}"))?;
+        }
+        Ok(())
+    }
+
+    /// Whether any stored row holds `value` in `column`. Reads the column's index or
+    /// unique set when it has one, and scans otherwise.
+    #[must_use]
+    pub fn contains_value(&self, column: &str, value: &Value) -> bool {
+        if let Some(index) = self.constraint_state.indexes.get(column) {
+            return index.contains_key(value);
+        }
+        if *value != Value::Null
+            && let Some(seen) = self.constraint_state.unique_values.get(column)
+        {
+            return seen.contains(value);
+        }
+        self.schema
+            .get_column_index(column)
+            .is_some_and(|pos| self.rows.values().any(|row| row.values[pos] == *value))
+    }
+
+    /// Every value of one column, NULLs included, in row-id order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ColumnNotFound` if the column doesn't exist.
+    pub fn column_values(&self, column: &str) -> Result<Vec<&Value>, TableErrors> {
+        let position = self.schema.get_column_index(column).ok_or_else(|| TableErrors::ColumnNotFound(column.to_string()))?;
+        Ok(self.rows.values().map(|row| &row.values[position]).collect())
+    }
+
+    /// The distinct non-NULL values of a column, in ascending order. Read from the
+    /// column's index or unique set when it has one, otherwise by scanning.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ColumnNotFound` if the column doesn't exist.
+    pub fn distinct_values(&self, column: &str) -> Result<Vec<Value>, TableErrors> {
+        let position = self.schema.get_column_index(column).ok_or_else(|| TableErrors::ColumnNotFound(column.to_string()))?;
+
+        if let Some(index) = self.constraint_state.indexes.get(column) {
+            return Ok(index.keys().filter(|value| **value != Value::Null).cloned().collect());
+        }
+        let values: BTreeSet<&Value> = match self.constraint_state.unique_values.get(column) {
+            Some(seen) => seen.iter().collect(),
+            None => self.rows.values().map(|row| &row.values[position]).filter(|value| **value != Value::Null).collect(),
+        };
+        Ok(values.into_iter().cloned().collect())
+    }
+
+    /// Number of distinct non-NULL values in an indexed column, or `None` if the
+    /// column has no index.
+    #[must_use]
+    pub fn index_cardinality(&self, column: &str) -> Option<usize> {
+        let index = self.constraint_state.indexes.get(column)?;
+        Some(index.len() - usize::from(index.contains_key(&Value::Null)))
+    }
+
+    /// Cardinality and value range of an indexed column, or `None` if the column
+    /// has no index. NULL sorts after every other value, so it is skipped when
+    /// reading the ends of the index.
+    #[must_use]
+    pub fn column_stats(&self, column: &str) -> Option<ColumnStats> {
+        let index = self.constraint_state.indexes.get(column)?;
+        let mut values = index.keys().filter(|value| **value != Value::Null);
+        Some(ColumnStats {
+            cardinality: self.index_cardinality(column)?,
+            min: values.next().cloned(),
+            max: values.next_back().cloned(),
+        })
+    }
+
+    /// Indexes `columns` together, in the given order, covering existing and future rows.
+    /// Creating an index that already exists is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ColumnNotFound` if any of `columns` doesn't exist.
+    pub fn create_composite_index(&mut self, columns: &[String]) -> Result<(), TableErrors> {
+        if self.composite_indexes.contains_key(columns) {
+            return Ok(());
+        }
+
+        let positions = columns
+            .iter()
+            .map(|name| self.schema.get_column_index(name).ok_or_else(|| TableErrors::ColumnNotFound(name.clone())))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut index = CompositeIndex { positions, entries: BTreeMap::new() };
+        for (&row_id, row) in &self.rows {
+            index.entries.entry(index.key(row)).or_default().insert(row_id);
+        }
+
+        self.composite_indexes.insert(columns.to_vec(), index);
+        Ok(())
+    }
+
+    /// Returns the rows whose leading index columns equal `prefix`, in index-key order
+    /// (ties by row id). A prefix shorter than the index key leaves the remaining
+    /// columns unconstrained, so an empty prefix returns every row; a prefix longer
+    /// than the key matches nothing. Without a composite index on `columns` this
+    /// falls back to a scan, returning rows in row-id order.
+    #[must_use]
+    pub fn rows_matching_index_prefix(&self, columns: &[String], prefix: &[Value]) -> Vec<&Row> {
+        if prefix.len() > columns.len() {
+            return Vec::new();
+        }
+
+        if let Some(index) = self.composite_indexes.get(columns) {
+            // Every key extending `prefix` sorts at or after it, and they are contiguous.
+            return index
+                .entries
+                .range(prefix.to_vec()..)
+                .take_while(|(key, _)| key.starts_with(prefix))
+                .flat_map(|(_, row_ids)| row_ids.iter().filter_map(|id| self.rows.get(id)))
+                .collect();
+        }
+
+        let Some(positions) = columns[..prefix.len()]
+            .iter()
+            .map(|name| self.schema.get_column_index(name))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Vec::new();
+        };
+        self.rows
+            .values()
+            .filter(|row| positions.iter().zip(prefix).all(|(&pos, value)| row.values[pos] == *value))
+            .collect()
+    }
+
+    fn index_composite(&mut self, row_id: u64, row: &Row) {
+        for index in self.composite_indexes.values_mut() {
+            index.entries.entry(index.key(row)).or_default().insert(row_id);
+        }
+    }
+
+    fn unindex_composite(&mut self, row_id: u64, row: &Row) {
+        for index in self.composite_indexes.values_mut() {
+            let key = index.key(row);
+            if let Some(row_ids) = index.entries.get_mut(&key) {
+                row_ids.remove(&row_id);
+                if row_ids.is_empty() {
+                    index.entries.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// Frees the unique and index entries held by a row that is no longer stored.
+    fn release_constraints(&mut self, row_id: u64, row: &Row) {
+        for group in &self.schema.composite_uniques {
+            if let Some(key) = Row::composite_key(&row.values, &self.schema, group) {
+                self.constraint_state.release_composite(group, &key);
+            }
+        }
+        for (col, val) in self.schema.columns.iter().zip(&row.values) {
+            self.constraint_state.release_unique(&col.name, val);
+            self.constraint_state.unindex_value(&col.name, val, row_id);
+        }
+    }
+
+    /// The inverse of `release_constraints`: takes back the entries of a row that
+    /// is stored again.
+    fn reclaim_constraints(&mut self, row_id: u64, row: &Row) {
+        for group in &self.schema.composite_uniques {
+            if let Some(key) = Row::composite_key(&row.values, &self.schema, group) {
+                self.constraint_state.claim_composite(group, key);
+            }
+        }
+        for (col, val) in self.schema.columns.iter().zip(&row.values) {
+            self.constraint_state.claim_unique(&col.name, val);
+        }
+        self.index_columns(row_id, row);
+    }
+
+    fn index_columns(&mut self, row_id: u64, row: &Row) {
+        for (col, val) in self.schema.columns.iter().zip(&row.values) {
+            self.constraint_state.index_value(&col.name, val, row_id);
+        }
+    }
+
+    /// Revalidates `row_values` against `schema` with a fresh constraint state and,
+    /// only if every row passes, swaps them in. Composite indexes are re-resolved
+    /// against the new schema by column name (dropping any whose columns are gone)
+    /// and refilled.
+    fn replace_schema(&mut self, schema: Schema, row_values: Vec<(u64, Vec<Value>)>) -> Result<(), TableErrors> {
+        let mut constraint_state = ConstraintState::new(&schema);
+        let mut rows = BTreeMap::new();
+        for (row_id, values) in row_values {
+            rows.insert(row_id, Row::new(&schema, &mut constraint_state, values)?);
+        }
+
+        self.composite_indexes.retain(|columns, index| {
+            let Some(positions) = columns.iter().map(|name| schema.get_column_index(name)).collect() else {
+                return false;
+            };
+            index.positions = positions;
+            index.entries.clear();
+            true
+        });
+        // Sequences never go backwards, even past values whose rows are gone.
+        for (column, next) in &mut constraint_state.next_auto_increment {
+            if let Some(&previous) = self.constraint_state.next_auto_increment.get(column) {
+                *next = (*next).max(previous);
+            }
+        }
+        self.schema = schema;
+        self.constraint_state = constraint_state;
+        self.rows = BTreeMap::new();
+        for (row_id, row) in rows {
+            self.index_columns(row_id, &row);
+            self.index_composite(row_id, &row);
+            self.rows.insert(row_id, row);
+        }
+        Ok(())
+    }
+
+    fn insert_validated(&mut self, row: Row) -> u64 {
+        let row_id = self.next_row_id;
+        self.next_row_id += 1;
+        self.index_columns(row_id, &row);
+        self.index_composite(row_id, &row);
+        self.rows.insert(row_id, row);
+        row_id
+    }
+}
+
+impl CompositeIndex {
+    fn key(&self, row: &Row) -> Vec<Value> {
+        self.positions.iter().map(|&pos| row.values[pos].clone()).collect()
+    }
+}
+
+
+/// Quotes a header field only when it needs it.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+// Small seeded PRNG so sampling is reproducible without pulling in a crate.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
 }
 
 
 #[cfg(test)]
 mod table_tests {
     use super::*;
-    use crate::column::{Column, DataType};
+    use std::collections::{HashMap, HashSet};
+    use crate::column::{Column, ColumnBuilder, DataType};
     use crate::schema::{Schema};
+    use crate::constraint_state::ConstraintKind;
 
     // ---------- Helpers ----------
     fn make_schema() -> Schema {
@@ -82,7 +760,7 @@ mod table_tests {
         vec![Value::Integer(id), Value::String(name.to_string())]
     }
 
-    fn assert_row_eq(table: &Table, key: u64, expected: Vec<Value>) {
+    fn assert_row_eq(table: &Table, key: u64, expected: &[Value]) {
         let stored_row = table.rows.get(&key).unwrap();
         assert_eq!(stored_row.values, expected);
     }
@@ -95,7 +773,7 @@ mod table_tests {
         table.add_row(row_int_str(1, "Alice")).unwrap();
 
         assert_eq!(table.rows.len(), 1);
-        assert_row_eq(&table, 0, row_int_str(1, "Alice"));
+        assert_row_eq(&table, 0, &row_int_str(1, "Alice"));
     }
 
     #[test]
@@ -123,8 +801,8 @@ mod table_tests {
         table.delete_row(0).unwrap();
 
         assert_eq!(table.rows.len(), 1);
-        assert!(table.rows.get(&0).is_none());
-        assert!(table.rows.get(&1).is_some());
+        assert!(!table.rows.contains_key(&0));
+        assert!(table.rows.contains_key(&1));
     }
 
     #[test]
@@ -136,7 +814,7 @@ mod table_tests {
         let new_row = row_int_str(1, "Bob");
         table.edit_row(0, new_row.clone()).unwrap();
 
-        assert_row_eq(&table, 0, new_row);
+        assert_row_eq(&table, 0, &new_row);
     }
 
     #[test]
@@ -153,4 +831,845 @@ mod table_tests {
         let result = table.edit_row(0, invalid_row);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn add_rows_success() {
+        let mut table = make_table();
+
+        let ids = table.add_rows(vec![row_int_str(1, "Alice"), row_int_str(2, "Bob")]).unwrap();
+
+        assert_eq!(ids, vec![0, 1]);
+        assert_row_eq(&table, 1, &row_int_str(2, "Bob"));
+    }
+
+    #[test]
+    fn add_rows_rejects_duplicates_within_batch() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).unique().build(),
+            ColumnBuilder::new("name", DataType::String).build(),
+        ]).unwrap();
+        let mut table = Table::new(schema);
+
+        let result = table.add_rows(vec![
+            row_int_str(1, "Alice"),
+            row_int_str(2, "Bob"),
+            row_int_str(1, "Carol"),
+        ]);
+
+        match result {
+            Err(TableErrors::BatchRowRejected { index, source }) => {
+                assert_eq!(index, 2);
+                assert_eq!(source, RowErrors::UniqueViolated { column: "id".to_string(), value: Value::Integer(1) });
+            }
+            other => panic!("expected batch rejection, got {other:?}"),
+        }
+
+        // Nothing from the rejected batch was kept, so its values are still free.
+        assert!(table.rows.is_empty());
+        assert!(table.constraint_state.unique_values["id"].is_empty());
+        table.add_row(row_int_str(1, "Alice")).unwrap();
+    }
+
+    #[test]
+    fn add_rows_rejects_bad_shape_before_constraints() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).unique().build(),
+            ColumnBuilder::new("name", DataType::String).build(),
+        ]).unwrap();
+        let mut table = Table::new(schema);
+
+        // The duplicate at index 1 would fail later; the short row at index 2 is caught first.
+        let result = table.add_rows(vec![row_int_str(1, "Alice"), row_int_str(1, "Bob"), vec![Value::Integer(3)]]);
+        assert_eq!(
+            result,
+            Err(TableErrors::BatchRowRejected { index: 2, source: RowErrors::WrongValueCount { expected: 2, got: 1 } })
+        );
+        assert!(table.rows.is_empty());
+    }
+
+    #[test]
+    fn truncate_clears_rows_and_unique_values() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).unique().build(),
+            ColumnBuilder::new("name", DataType::String).build(),
+        ]).unwrap();
+        let mut table = Table::new(schema);
+        table.add_row(row_int_str(1, "Alice")).unwrap();
+        table.add_row(row_int_str(2, "Bob")).unwrap();
+
+        table.truncate(TruncateIdentity::Continue);
+
+        assert!(table.rows.is_empty());
+        table.add_row(row_int_str(1, "Alice")).unwrap();
+    }
+
+    #[test]
+    fn add_row_after_delete_does_not_reuse_ids() {
+        let mut table = make_table();
+        table.add_row(row_int_str(1, "Alice")).unwrap();
+        table.add_row(row_int_str(2, "Bob")).unwrap();
+        table.add_row(row_int_str(3, "Charlie")).unwrap();
+
+        table.delete_row(1).unwrap();
+        let new_id = table.add_row(row_int_str(4, "Dan")).unwrap();
+
+        assert_eq!(new_id, 3);
+        assert_eq!(table.rows.len(), 3);
+        assert_row_eq(&table, 0, &row_int_str(1, "Alice"));
+        assert_row_eq(&table, 2, &row_int_str(3, "Charlie"));
+        assert_row_eq(&table, 3, &row_int_str(4, "Dan"));
+    }
+
+    #[test]
+    fn truncate_restart_identity_resets_row_ids() {
+        let mut table = make_table();
+        table.add_row(row_int_str(1, "Alice")).unwrap();
+        table.add_row(row_int_str(2, "Bob")).unwrap();
+
+        table.truncate(TruncateIdentity::Restart);
+
+        assert_eq!(table.add_row(row_int_str(3, "Charlie")).unwrap(), 0);
+    }
+
+    #[test]
+    fn truncate_continue_identity_keeps_row_ids() {
+        let mut table = make_table();
+        table.add_row(row_int_str(1, "Alice")).unwrap();
+        table.add_row(row_int_str(2, "Bob")).unwrap();
+
+        table.truncate(TruncateIdentity::Continue);
+
+        assert_eq!(table.add_row(row_int_str(3, "Charlie")).unwrap(), 2);
+    }
+
+    #[test]
+    fn delete_row_releases_unique_value() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("email", DataType::String).unique().build(),
+        ]).unwrap();
+        let mut table = Table::new(schema);
+        let row_id = table.add_row(row_int_str(1, "x@example.com")).unwrap();
+
+        table.delete_row(row_id).unwrap();
+
+        assert!(table.add_row(row_int_str(2, "x@example.com")).is_ok());
+    }
+
+    #[test]
+    fn delete_row_keeps_index_value_shared_by_other_rows() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("city", DataType::String).index().build(),
+        ]).unwrap();
+        let mut table = Table::new(schema);
+        let first = table.add_row(row_int_str(1, "Paris")).unwrap();
+        let second = table.add_row(row_int_str(2, "Paris")).unwrap();
+        let city = Value::String("Paris".to_string());
+
+        table.delete_row(first).unwrap();
+        assert_eq!(table.constraint_state.indexes["city"][&city], BTreeSet::from([second]));
+
+        table.delete_row(second).unwrap();
+        assert!(!table.constraint_state.indexes["city"].contains_key(&city));
+    }
+
+    #[test]
+    fn index_maps_values_to_row_ids() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("city", DataType::String).index().build(),
+        ]).unwrap();
+        let mut table = Table::new(schema);
+        let paris = table.add_row(row_int_str(1, "Paris")).unwrap();
+        let batch = table.add_rows(vec![row_int_str(2, "Lyon"), row_int_str(3, "Paris")]).unwrap();
+
+        let index = &table.constraint_state.indexes["city"];
+        assert_eq!(index[&Value::String("Paris".to_string())], BTreeSet::from([paris, batch[1]]));
+        assert_eq!(index[&Value::String("Lyon".to_string())], BTreeSet::from([batch[0]]));
+    }
+
+    #[test]
+    fn edit_row_moves_index_entry() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).unique().build(),
+            ColumnBuilder::new("city", DataType::String).index().build(),
+        ]).unwrap();
+        let mut table = Table::new(schema);
+        let row_id = table.add_row(row_int_str(1, "Paris")).unwrap();
+        table.add_row(row_int_str(2, "Lyon")).unwrap();
+
+        table.edit_row(row_id, row_int_str(1, "Nice")).unwrap();
+        // A rejected edit leaves the index as it was.
+        assert!(table.edit_row(row_id, row_int_str(2, "Metz")).is_err());
+
+        let index = &table.constraint_state.indexes["city"];
+        assert!(!index.contains_key(&Value::String("Paris".to_string())));
+        assert!(!index.contains_key(&Value::String("Metz".to_string())));
+        assert_eq!(index[&Value::String("Nice".to_string())], BTreeSet::from([row_id]));
+    }
+
+    #[test]
+    fn sample_is_reproducible_for_a_seed() {
+        let mut table = make_table();
+        for id in 0..50 {
+            table.add_row(row_int_str(id, "user")).unwrap();
+        }
+
+        let first = table.sample(5, 42);
+        let again = table.sample(5, 42);
+        let other_seeds: Vec<Vec<&Row>> = (1..=5).map(|seed| table.sample(5, seed)).collect();
+
+        assert_eq!(first.len(), 5);
+        assert_eq!(first, again);
+        assert!(other_seeds.iter().any(|sample| *sample != first));
+    }
+
+    #[test]
+    fn sample_larger_than_table_returns_all_rows() {
+        let mut table = make_table();
+        table.add_row(row_int_str(1, "Alice")).unwrap();
+        table.add_row(row_int_str(2, "Bob")).unwrap();
+
+        let sample = table.sample(10, 7);
+
+        assert_eq!(sample, table.rows.values().collect::<Vec<_>>());
+        assert!(make_table().sample(3, 7).is_empty());
+    }
+
+    fn make_unique_name_table() -> Table {
+        Table::new(Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("name", DataType::String).unique().build(),
+        ]).unwrap())
+    }
+
+    #[test]
+    fn edit_row_to_new_unique_value_frees_old_value() {
+        let mut table = make_unique_name_table();
+        let row_id = table.add_row(row_int_str(1, "Alice")).unwrap();
+
+        table.edit_row(row_id, row_int_str(1, "Bob")).unwrap();
+
+        assert_row_eq(&table, row_id, &row_int_str(1, "Bob"));
+        assert!(table.add_row(row_int_str(2, "Alice")).is_ok());
+        assert!(table.add_row(row_int_str(3, "Bob")).is_err());
+    }
+
+    #[test]
+    fn edit_row_to_its_own_unique_value() {
+        let mut table = make_unique_name_table();
+        let row_id = table.add_row(row_int_str(1, "Alice")).unwrap();
+
+        table.edit_row(row_id, row_int_str(2, "Alice")).unwrap();
+
+        assert_row_eq(&table, row_id, &row_int_str(2, "Alice"));
+    }
+
+    #[test]
+    fn failed_edit_row_rolls_back() {
+        let mut table = make_unique_name_table();
+        let alice = table.add_row(row_int_str(1, "Alice")).unwrap();
+        table.add_row(row_int_str(2, "Bob")).unwrap();
+
+        let result = table.edit_row(alice, row_int_str(1, "Bob"));
+
+        assert!(matches!(result, Err(TableErrors::RowConstructionError(RowErrors::UniqueViolated { .. }))));
+        assert_row_eq(&table, alice, &row_int_str(1, "Alice"));
+        // "Alice" is still taken and "Carol" was never claimed.
+        assert!(table.add_row(row_int_str(3, "Alice")).is_err());
+        assert!(table.add_row(row_int_str(3, "Carol")).is_ok());
+    }
+
+    #[test]
+    fn failed_edit_rows_leaves_table_unchanged() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).unique().build(),
+            ColumnBuilder::new("name", DataType::String).index().build(),
+        ])
+        .unwrap();
+        let mut table = Table::new(schema);
+        table.create_composite_index(&["name".to_string(), "id".to_string()]).unwrap();
+        let alice = table.add_row(row_int_str(1, "Alice")).unwrap();
+        let bob = table.add_row(row_int_str(2, "Bob")).unwrap();
+        let before = table.rows.clone();
+        let indexes_before = table.constraint_state.indexes.clone();
+
+        // The first edit succeeds and frees 1; the second then collides with it.
+        let result = table.edit_rows(vec![(alice, row_int_str(3, "Carol")), (bob, row_int_str(3, "Bob"))]);
+
+        assert!(matches!(result, Err(TableErrors::RowConstructionError(RowErrors::UniqueViolated { .. }))));
+        assert_eq!(table.rows, before);
+        assert_eq!(table.constraint_state.unique_values["id"], HashSet::from([Value::Integer(1), Value::Integer(2)]));
+        assert_eq!(table.constraint_state.indexes, indexes_before);
+        let by_name = table.rows_matching_index_prefix(&["name".to_string(), "id".to_string()], &[Value::String("Alice".to_string())]);
+        assert_eq!(by_name.len(), 1);
+        assert!(table.rows_matching_index_prefix(&["name".to_string(), "id".to_string()], &[Value::String("Carol".to_string())]).is_empty());
+        assert_eq!(table.check_invariants(), Ok(()));
+
+        table.edit_rows(vec![(alice, row_int_str(3, "Carol")), (bob, row_int_str(1, "Bob"))]).unwrap();
+        assert_row_eq(&table, alice, &row_int_str(3, "Carol"));
+        assert_row_eq(&table, bob, &row_int_str(1, "Bob"));
+    }
+
+    #[test]
+    fn edit_row_reapplies_default_for_null() {
+        let mut table = Table::new(Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("name", DataType::String).default(Value::String("anonymous".to_string())).unwrap().build(),
+        ]).unwrap());
+        let row_id = table.add_row(row_int_str(1, "Alice")).unwrap();
+
+        table.edit_row(row_id, vec![Value::Integer(1), Value::Null]).unwrap();
+
+        assert_row_eq(&table, row_id, &row_int_str(1, "anonymous"));
+    }
+
+    fn make_city_table() -> Table {
+        let mut table = Table::new(Schema::new(vec![
+            ColumnBuilder::new("country", DataType::String).build(),
+            ColumnBuilder::new("city", DataType::String).build(),
+        ]).unwrap());
+        for (country, city) in [("UK", "York"), ("FR", "Paris"), ("UK", "Bath"), ("FR", "Lyon")] {
+            table.add_row(vec![Value::String(country.to_string()), Value::String(city.to_string())]).unwrap();
+        }
+        table
+    }
+
+    fn cities(rows: &[&Row]) -> Vec<Value> {
+        rows.iter().map(|row| row.values[1].clone()).collect()
+    }
+
+    #[test]
+    fn composite_index_prefix_scan() {
+        let mut table = make_city_table();
+        let columns = vec!["country".to_string(), "city".to_string()];
+        table.create_composite_index(&columns).unwrap();
+        let uk = Value::String("UK".to_string());
+
+        // A one-value prefix matches on "country" alone, in index-key order.
+        let rows = table.rows_matching_index_prefix(&columns, std::slice::from_ref(&uk));
+        assert_eq!(cities(&rows), vec![Value::String("Bath".to_string()), Value::String("York".to_string())]);
+
+        // The full key narrows to one row; an empty prefix matches every row.
+        let rows = table.rows_matching_index_prefix(&columns, &[uk.clone(), Value::String("York".to_string())]);
+        assert_eq!(cities(&rows), vec![Value::String("York".to_string())]);
+        assert_eq!(table.rows_matching_index_prefix(&columns, &[]).len(), 4);
+        let too_long = [uk.clone(), Value::String("York".to_string()), Value::Null];
+        assert!(table.rows_matching_index_prefix(&columns, &too_long).is_empty());
+    }
+
+    #[test]
+    fn composite_index_tracks_row_changes() {
+        let mut table = make_city_table();
+        let columns = vec!["country".to_string(), "city".to_string()];
+        table.create_composite_index(&columns).unwrap();
+        let uk = [Value::String("UK".to_string())];
+
+        table.delete_row(0).unwrap();
+        table.edit_row(1, vec![Value::String("UK".to_string()), Value::String("Leeds".to_string())]).unwrap();
+        table.add_row(vec![Value::String("UK".to_string()), Value::String("Ayr".to_string())]).unwrap();
+
+        let expected = ["Ayr", "Bath", "Leeds"].map(|city| Value::String(city.to_string())).to_vec();
+        assert_eq!(cities(&table.rows_matching_index_prefix(&columns, &uk)), expected);
+
+        table.truncate(TruncateIdentity::Continue);
+        assert!(table.rows_matching_index_prefix(&columns, &uk).is_empty());
+    }
+
+    #[test]
+    fn prefix_scan_without_index_falls_back_to_scan() {
+        let table = make_city_table();
+        let columns = vec!["country".to_string(), "city".to_string()];
+
+        let rows = table.rows_matching_index_prefix(&columns, &[Value::String("UK".to_string())]);
+
+        assert_eq!(cities(&rows), vec![Value::String("York".to_string()), Value::String("Bath".to_string())]);
+    }
+
+    #[test]
+    fn composite_index_on_unknown_column() {
+        let mut table = make_city_table();
+
+        let result = table.create_composite_index(&["country".to_string(), "zip".to_string()]);
+
+        assert_eq!(result, Err(TableErrors::ColumnNotFound("zip".to_string())));
+    }
+
+    #[test]
+    fn first_and_last_row_follow_ids() {
+        let mut table = make_table();
+        assert!(table.first_row().is_none() && table.last_row().is_none());
+
+        let ids: Vec<u64> = [(7, "Grace"), (2, "Bob"), (9, "Ivan"), (4, "Dan")]
+            .into_iter()
+            .map(|(id, name)| table.add_row(row_int_str(id, name)).unwrap())
+            .collect();
+
+        assert_eq!(table.first_row(), Some((ids[0], &Row { values: row_int_str(7, "Grace") })));
+        assert_eq!(table.last_row(), Some((ids[3], &Row { values: row_int_str(4, "Dan") })));
+
+        table.delete_row(ids[0]).unwrap();
+        table.delete_row(ids[3]).unwrap();
+        assert_eq!(table.first_row().map(|(id, _)| id), Some(ids[1]));
+        assert_eq!(table.last_row().map(|(id, _)| id), Some(ids[2]));
+    }
+
+    #[test]
+    fn export_csv_writes_header_and_rows() {
+        let mut table = Table::new(Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("name", DataType::String).build(),
+            ColumnBuilder::new("age", DataType::Integer).build(),
+        ]).unwrap());
+        for (id, name, age) in [(1, "Alice", 30), (2, "Bob", 25), (3, "Charlie", 30)] {
+            table.add_row(vec![Value::Integer(id), Value::String(name.to_string()), Value::Integer(age)]).unwrap();
+        }
+
+        let mut csv = Vec::new();
+        table.export_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "id,name,age\r\n1,\"Alice\",30\r\n2,\"Bob\",25\r\n3,\"Charlie\",30\r\n"
+        );
+    }
+
+    #[test]
+    fn export_csv_escapes_strings_and_blanks_nulls() {
+        let mut table = Table::new(Schema::new(vec![
+            ColumnBuilder::new("quote, \"unquoted\"", DataType::String).build(),
+            ColumnBuilder::new("price", DataType::Float).build(),
+            ColumnBuilder::new("active", DataType::Boolean).build(),
+        ]).unwrap());
+        table.add_row(vec![Value::String("say \"hi\", then\nleave".to_string()), Value::Float(2.0), Value::Boolean(true)]).unwrap();
+        table.add_row(vec![Value::Null, Value::Null, Value::Null]).unwrap();
+
+        let mut csv = Vec::new();
+        table.export_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "\"quote, \"\"unquoted\"\"\",price,active\r\n\"say \"\"hi\"\", then\nleave\",2.0,true\r\n,,\r\n"
+        );
+    }
+
+    #[test]
+    fn adding_a_default_backfills_null_values() {
+        let mut table = make_table();
+        table.add_row(row_int_str(1, "Alice")).unwrap();
+        table.add_row(vec![Value::Integer(2), Value::Null]).unwrap();
+
+        table.add_constraint("name", Constraint::WithValue(ConstraintKind::Default, Value::String("anon".to_string()))).unwrap();
+
+        assert_row_eq(&table, 0, &row_int_str(1, "Alice"));
+        assert_row_eq(&table, 1, &row_int_str(2, "anon"));
+        assert_eq!(table.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn add_unique_constraint_to_existing_column() {
+        let mut table = make_table();
+        table.add_row(row_int_str(1, "Alice")).unwrap();
+        table.add_row(row_int_str(2, "Bob")).unwrap();
+
+        table.add_constraint("name", Constraint::Unit(ConstraintKind::Unique)).unwrap();
+
+        assert!(table.schema.columns[1].constraints.contains_key(&ConstraintKind::Unique));
+        assert!(matches!(
+            table.add_row(row_int_str(3, "Alice")),
+            Err(TableErrors::RowConstructionError(RowErrors::UniqueViolated { .. }))
+        ));
+        table.add_row(row_int_str(3, "Carol")).unwrap();
+        assert_eq!(table.rows.len(), 3);
+    }
+
+    #[test]
+    fn add_constraint_violated_by_existing_rows_leaves_table_unchanged() {
+        let mut table = make_table();
+        table.add_row(row_int_str(1, "Alice")).unwrap();
+        table.add_row(row_int_str(2, "Alice")).unwrap();
+
+        assert!(table.add_constraint("name", Constraint::Unit(ConstraintKind::Unique)).is_err());
+        assert!(!table.schema.columns[1].constraints.contains_key(&ConstraintKind::Unique));
+        table.add_row(row_int_str(3, "Alice")).unwrap();
+
+        assert_eq!(
+            table.add_constraint("nickname", Constraint::Unit(ConstraintKind::NotNull)),
+            Err(TableErrors::SchemaChangeRejected(SchemaError::ColumnNotFound("nickname".to_string())))
+        );
+    }
+
+    fn make_indexed_age_table() -> Table {
+        Table::new(Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("age", DataType::Integer).index().build(),
+        ]).unwrap())
+    }
+
+    #[test]
+    fn index_cardinality_counts_distinct_values() {
+        let mut table = make_indexed_age_table();
+        assert_eq!(table.index_cardinality("age"), Some(0));
+        assert_eq!(table.index_cardinality("id"), None);
+
+        for (id, age) in [(1, Value::Integer(30)), (2, Value::Integer(25)), (3, Value::Integer(30)), (4, Value::Null)] {
+            table.add_row(vec![Value::Integer(id), age]).unwrap();
+        }
+        assert_eq!(table.index_cardinality("age"), Some(2));
+
+        // Row 2 still holds 30, so dropping row 0 keeps both values.
+        table.delete_row(0).unwrap();
+        assert_eq!(table.index_cardinality("age"), Some(2));
+        table.delete_row(1).unwrap();
+        assert_eq!(table.index_cardinality("age"), Some(1));
+    }
+
+    #[test]
+    fn column_stats_report_min_and_max() {
+        let mut table = make_indexed_age_table();
+        assert_eq!(table.column_stats("age"), Some(ColumnStats { cardinality: 0, min: None, max: None }));
+
+        for (id, age) in [(1, Value::Integer(30)), (2, Value::Null), (3, Value::Integer(18)), (4, Value::Integer(65))] {
+            table.add_row(vec![Value::Integer(id), age]).unwrap();
+        }
+        assert_eq!(
+            table.column_stats("age"),
+            Some(ColumnStats { cardinality: 3, min: Some(Value::Integer(18)), max: Some(Value::Integer(65)) })
+        );
+        assert_eq!(table.column_stats("id"), None);
+    }
+
+    #[test]
+    fn drop_column_keeps_remaining_data_and_indexes() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).unique().build(),
+            ColumnBuilder::new("name", DataType::String).build(),
+            ColumnBuilder::new("age", DataType::Integer).index().build(),
+        ])
+        .unwrap();
+        let mut table = Table::new(schema);
+        table.create_composite_index(&["name".to_string(), "age".to_string()]).unwrap();
+        table.create_composite_index(&["age".to_string(), "id".to_string()]).unwrap();
+        table.add_row(vec![Value::Integer(1), Value::String("a".into()), Value::Integer(30)]).unwrap();
+        table.add_row(vec![Value::Integer(2), Value::String("b".into()), Value::Integer(25)]).unwrap();
+
+        table.drop_column("name").unwrap();
+
+        assert_eq!(table.schema.get_column_index("age"), Some(1));
+        assert_row_eq(&table, 0, &[Value::Integer(1), Value::Integer(30)]);
+        assert_row_eq(&table, 1, &[Value::Integer(2), Value::Integer(25)]);
+        assert_eq!(table.constraint_state.indexes["age"][&Value::Integer(25)], BTreeSet::from([1]));
+        assert_eq!(table.composite_indexes.len(), 1);
+        let by_age = table.rows_matching_index_prefix(&["age".to_string(), "id".to_string()], &[Value::Integer(30)]);
+        assert_eq!(by_age.iter().map(|row| row.values.clone()).collect::<Vec<_>>(), vec![vec![Value::Integer(1), Value::Integer(30)]]);
+        assert!(matches!(
+            table.add_row(vec![Value::Integer(1), Value::Integer(40)]),
+            Err(TableErrors::RowConstructionError(RowErrors::UniqueViolated { .. }))
+        ));
+    }
+
+    #[test]
+    fn change_column_type_converts_every_row() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("age", DataType::Integer).index().default(Value::Integer(18)).unwrap().build(),
+        ])
+        .unwrap();
+        let mut table = Table::new(schema);
+        table.add_row(vec![Value::Integer(1), Value::Integer(30)]).unwrap();
+        table.add_row(vec![Value::Integer(2), Value::Null]).unwrap();
+
+        table.change_column_type("age", DataType::String).unwrap();
+
+        assert_eq!(table.schema.columns[1].data_type, DataType::String);
+        assert_row_eq(&table, 0, &[Value::Integer(1), Value::String("30".to_string())]);
+        assert_row_eq(&table, 1, &[Value::Integer(2), Value::String("18".to_string())]);
+        assert_eq!(table.constraint_state.indexes["age"][&Value::String("30".to_string())], BTreeSet::from([0]));
+        table.add_row(vec![Value::Integer(3), Value::Null]).unwrap();
+        assert_row_eq(&table, 2, &[Value::Integer(3), Value::String("18".to_string())]);
+    }
+
+    #[test]
+    fn failed_column_type_change_leaves_table_intact() {
+        let mut table = make_table();
+        table.add_row(row_int_str(1, "42")).unwrap();
+        table.add_row(row_int_str(2, "Alice")).unwrap();
+        let before = table.rows.clone();
+
+        assert_eq!(
+            table.change_column_type("name", DataType::Integer),
+            Err(TableErrors::ConversionFailed {
+                column: "name".to_string(),
+                value: Value::String("Alice".to_string()),
+                to: DataType::Integer,
+            })
+        );
+        assert_eq!(table.schema, make_schema());
+        assert_eq!(table.rows, before);
+        assert_eq!(
+            table.change_column_type("missing", DataType::String),
+            Err(TableErrors::SchemaChangeRejected(SchemaError::ColumnNotFound("missing".to_string())))
+        );
+    }
+
+    #[test]
+    fn schema_changes_keep_invariants() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).unique().build(),
+            ColumnBuilder::new("name", DataType::String).index().build(),
+            ColumnBuilder::new("age", DataType::Integer).default(Value::Integer(18)).unwrap().build(),
+        ])
+        .unwrap();
+        let mut table = Table::new(schema);
+        table.add_row(vec![Value::Integer(1), Value::String("a".into()), Value::Integer(30)]).unwrap();
+        assert_eq!(table.check_invariants(), Ok(()));
+
+        table.drop_column("name").unwrap();
+        assert_eq!(table.check_invariants(), Ok(()));
+        table.change_column_type("age", DataType::String).unwrap();
+        assert_eq!(table.check_invariants(), Ok(()));
+
+        // Editing the schema behind the table's back is caught.
+        table.schema.drop_column("id").unwrap();
+        assert!(table.check_invariants().unwrap_err().contains("'id'"));
+    }
+
+    #[test]
+    fn drop_missing_column_is_schema_error() {
+        let mut table = make_table();
+        assert_eq!(
+            table.drop_column("missing"),
+            Err(TableErrors::SchemaChangeRejected(SchemaError::ColumnNotFound("missing".to_string())))
+        );
+    }
+
+    #[test]
+    fn rejected_row_does_not_claim_unique_values() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).unique().build(),
+            ColumnBuilder::new("email", DataType::String).unique().build(),
+        ])
+        .unwrap();
+        let mut table = Table::new(schema);
+        table.add_row(vec![Value::Integer(1), Value::String("a".into())]).unwrap();
+
+        assert!(table.add_row(vec![Value::Integer(2), Value::String("a".into())]).is_err());
+        assert_eq!(table.add_row(vec![Value::Integer(2), Value::String("b".into())]), Ok(1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn import_json_lines_loads_records_by_column_name() {
+        let mut table = make_table();
+        let input = "{\"name\": \"Alice\", \"id\": 1}\n\n{\"id\": 2, \"name\": null}\n";
+
+        assert_eq!(table.import_json_lines(input), Ok(vec![0, 1]));
+        assert_row_eq(&table, 0, &row_int_str(1, "Alice"));
+        assert_row_eq(&table, 1, &[Value::Integer(2), Value::Null]);
+
+        let bad = "{\"id\": 3}\n{\"id\": \"four\"}";
+        assert!(matches!(
+            table.import_json_lines(bad),
+            Err(TableErrors::JsonImport { line: 2, source: ParseError::TypeMismatch { .. } })
+        ));
+        assert!(matches!(table.import_json_lines("[1, 2]"), Err(TableErrors::JsonImport { line: 1, source: ParseError::NotAnObject { .. } })));
+        assert!(matches!(table.import_json_lines("{\"age\": 1}"), Err(TableErrors::JsonImport { source: ParseError::UnknownColumn { .. }, .. })));
+        assert_eq!(table.rows.len(), 2);
+    }
+
+    fn make_people_table() -> Table {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("first_name", DataType::String).build(),
+            ColumnBuilder::new("last_name", DataType::String).build(),
+        ])
+        .unwrap();
+        let mut table = Table::new(schema);
+        table.add_composite_unique(&["first_name".to_string(), "last_name".to_string()]).unwrap();
+        table
+    }
+
+    fn person(first: &str, last: &str) -> Vec<Value> {
+        vec![Value::String(first.to_string()), Value::String(last.to_string())]
+    }
+
+    #[test]
+    fn composite_unique_allows_repeats_in_each_column() {
+        let mut table = make_people_table();
+        for (first, last) in [("Ada", "Lovelace"), ("Ada", "Byron"), ("Alan", "Lovelace")] {
+            table.add_row(person(first, last)).unwrap();
+        }
+        table.add_row(vec![Value::String("Ada".to_string()), Value::Null]).unwrap();
+        table.add_row(vec![Value::String("Ada".to_string()), Value::Null]).unwrap();
+        assert_eq!(table.rows.len(), 5);
+    }
+
+    #[test]
+    fn composite_unique_rejects_colliding_pair_until_released() {
+        let mut table = make_people_table();
+        let id = table.add_row(person("Ada", "Lovelace")).unwrap();
+
+        assert_eq!(
+            table.add_row(person("Ada", "Lovelace")),
+            Err(TableErrors::RowConstructionError(RowErrors::CompositeUniqueViolated {
+                columns: vec!["first_name".to_string(), "last_name".to_string()],
+                values: person("Ada", "Lovelace"),
+            }))
+        );
+        table.edit_row(id, person("Ada", "Lovelace")).unwrap();
+
+        table.delete_row(id).unwrap();
+        assert!(table.add_row(person("Ada", "Lovelace")).is_ok());
+    }
+
+    #[test]
+    fn add_composite_unique_rejects_existing_duplicates() {
+        let mut table = Table::new(Schema::new(vec![
+            ColumnBuilder::new("first_name", DataType::String).build(),
+            ColumnBuilder::new("last_name", DataType::String).build(),
+        ]).unwrap());
+        table.add_row(person("Ada", "Lovelace")).unwrap();
+        table.add_row(person("Ada", "Lovelace")).unwrap();
+
+        let group = ["first_name".to_string(), "last_name".to_string()];
+        assert!(table.add_composite_unique(&group).is_err());
+        assert!(table.schema.composite_uniques.is_empty());
+        assert_eq!(
+            table.add_composite_unique(&["first_name".to_string(), "age".to_string()]),
+            Err(TableErrors::SchemaChangeRejected(SchemaError::ColumnNotFound("age".to_string())))
+        );
+    }
+
+    #[test]
+    fn distinct_values_are_sorted_and_deduplicated() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("plain", DataType::Integer).build(),
+            ColumnBuilder::new("indexed", DataType::Integer).index().build(),
+            ColumnBuilder::new("unique", DataType::Integer).unique().build(),
+        ])
+        .unwrap();
+        let mut table = Table::new(schema);
+        for (n, unique) in [(3, 30), (1, 10), (3, 20), (2, 40)] {
+            table.add_row(vec![Value::Integer(n), Value::Integer(n), Value::Integer(unique)]).unwrap();
+        }
+        table.add_row(vec![Value::Null, Value::Null, Value::Null]).unwrap();
+
+        let expected = vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)];
+        assert_eq!(table.distinct_values("plain"), Ok(expected.clone()));
+        assert_eq!(table.distinct_values("indexed"), Ok(expected));
+        assert_eq!(table.distinct_values("unique"), Ok([10, 20, 30, 40].map(Value::Integer).to_vec()));
+        assert_eq!(table.distinct_values("missing"), Err(TableErrors::ColumnNotFound("missing".to_string())));
+    }
+
+    #[test]
+    fn column_values_follow_row_id_order() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).build(),
+            ColumnBuilder::new("age", DataType::Integer).build(),
+        ])
+        .unwrap();
+        let mut table = Table::new(schema);
+        for (id, age) in [(1, Value::Integer(30)), (2, Value::Null), (3, Value::Integer(25))] {
+            table.add_row(vec![Value::Integer(id), age]).unwrap();
+        }
+        table.delete_row(0).unwrap();
+
+        assert_eq!(table.column_values("age"), Ok(vec![&Value::Null, &Value::Integer(25)]));
+        assert_eq!(table.column_values("missing"), Err(TableErrors::ColumnNotFound("missing".to_string())));
+    }
+
+    #[test]
+    fn evict_expired_removes_only_expired_rows() {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).unique().build(),
+            ColumnBuilder::new("name", DataType::String).build(),
+        ])
+        .unwrap();
+        let mut table = Table::new(schema);
+        let short = table.add_row_with_ttl(row_int_str(1, "short"), 10).unwrap();
+        let long = table.add_row_with_ttl(row_int_str(2, "long"), 3600).unwrap();
+        let forever = table.add_row(row_int_str(3, "forever")).unwrap();
+        assert!(table.expires_at(short) < table.expires_at(long));
+        assert_eq!(table.expires_at(forever), None);
+
+        let now = SystemTime::now();
+        assert_eq!(table.evict_expired(now), 0);
+        assert_eq!(table.evict_expired(now + Duration::from_mins(1)), 1);
+        assert_eq!(table.rows.keys().copied().collect::<Vec<_>>(), vec![long, forever]);
+        assert_eq!(table.expires_at(short), None);
+
+        // The evicted row's unique value is free again.
+        table.add_row(row_int_str(1, "again")).unwrap();
+        assert_eq!(table.evict_expired(now + Duration::from_hours(2)), 1);
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.evict_expired(now + Duration::from_hours(2)), 0);
+    }
+
+    fn make_auto_increment_table() -> Table {
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).auto_increment().not_null().unique().build(),
+            ColumnBuilder::new("name", DataType::String).build(),
+        ])
+        .unwrap();
+        Table::new(schema)
+    }
+
+    fn auto_ids(table: &Table) -> Vec<Value> {
+        table.rows.values().map(|row| row.values[0].clone()).collect()
+    }
+
+    #[test]
+    fn auto_increment_fills_omitted_ids_and_skips_explicit_ones() {
+        let mut table = make_auto_increment_table();
+        table.add_row(vec![Value::Null, Value::String("a".into())]).unwrap();
+        table.add_row(vec![Value::Null, Value::String("b".into())]).unwrap();
+        table.add_row(vec![Value::Integer(10), Value::String("c".into())]).unwrap();
+        table.add_row(vec![Value::Null, Value::String("d".into())]).unwrap();
+        table.add_row(vec![Value::Integer(5), Value::String("e".into())]).unwrap();
+        table.add_row(vec![Value::Null, Value::String("f".into())]).unwrap();
+
+        assert_eq!(auto_ids(&table), [1, 2, 10, 11, 5, 12].map(Value::Integer).to_vec());
+        // A rejected row doesn't use up a number.
+        assert!(table.add_row(vec![Value::Integer(5), Value::String("g".into())]).is_err());
+        assert!(table.add_row(vec![Value::Null, Value::Integer(1)]).is_err());
+        table.add_row(vec![Value::Null, Value::String("h".into())]).unwrap();
+        assert_eq!(auto_ids(&table).last(), Some(&Value::Integer(13)));
+    }
+
+    #[test]
+    fn auto_increment_sequence_follows_truncate_identity() {
+        let mut table = make_auto_increment_table();
+        table.add_row(vec![Value::Null, Value::String("a".into())]).unwrap();
+
+        table.truncate(TruncateIdentity::Continue);
+        table.add_row(vec![Value::Null, Value::String("b".into())]).unwrap();
+        assert_eq!(auto_ids(&table), vec![Value::Integer(2)]);
+
+        table.truncate(TruncateIdentity::Restart);
+        table.add_row(vec![Value::Null, Value::String("c".into())]).unwrap();
+        assert_eq!(auto_ids(&table), vec![Value::Integer(1)]);
+    }
+
+    #[test]
+    fn auto_increment_requires_integer_column() {
+        let result = Schema::new(vec![ColumnBuilder::new("id", DataType::String).auto_increment().build()]);
+        assert_eq!(result, Err(SchemaError::AutoIncrementNotInteger { column_name: "id".to_string() }));
+    }
+
+    #[test]
+    fn row_count_and_is_empty() {
+        let mut table = make_table();
+        assert_eq!(table.row_count(), 0);
+        assert!(table.is_empty());
+
+        table.add_row(row_int_str(1, "Alice")).unwrap();
+        let bob = table.add_row(row_int_str(2, "Bob")).unwrap();
+        assert_eq!(table.row_count(), 2);
+        assert!(!table.is_empty());
+
+        table.delete_row(bob).unwrap();
+        assert_eq!(table.row_count(), 1);
+        table.truncate(TruncateIdentity::Continue);
+        assert!(table.is_empty());
+    }
 }
@@ -11,6 +11,12 @@ pub enum TokenizerError {
     #[error("Unterminated string literal starting at position {0}")]
     UnterminatedString(usize),
 
+    #[error("Unterminated block comment starting at position {0}")]
+    UnterminatedComment(usize),
+
+    #[error("Unterminated quoted identifier starting at position {0}")]
+    UnterminatedIdentifier(usize),
+
     #[error("Invalid numeric literal '{0}' at position {1}")]
     InvalidNumeric(String, usize),
 
@@ -27,17 +33,31 @@ pub enum TokenizerError {
 #[derive(Debug, PartialEq, Clone)]
 pub enum Token {
     Select,
+    Distinct,
     From,
     Where,
     Insert,
     Delete,
+    Update,
     Into,
     Values,
+    Order,
+    By,
+    Asc,
+    Desc,
+    Limit,
+    Offset,
+    Fetch,
+    Is,
+    Not,
+    Null,
 
     // Identifiers and Literals
     Identifier(String),
     StringLiteral(String),
     NumericLiteral(String),
+    FloatLiteral(String),
+    BooleanLiteral(bool),
     
 
     // Symbols
@@ -60,12 +80,21 @@ pub enum Token {
     LessThanOrEquals,
     And,
     Or,
+    Like,
+    In,
+    Between,
 
-    // DDL for Table and Constituents
-    CreateTable,
+    // DDL for Table and Constituents.
+    // Multi-word commands are emitted one keyword at a time (`CREATE` then `TABLE`)
+    // and composed by the parser, so `TABLE` can be reused by DROP/ALTER.
     Create,
     Drop,
     Alter,
+    Truncate,
+    Unique,
+    Default,
+    Union,
+    Describe,
 
     // End of Input
     Eof,
@@ -75,29 +104,97 @@ pub enum Token {
 // STRUCT
 // ========================================================================================
 
+/// Byte range `start..end` of a token in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedToken {
+    pub token: Token,
+    pub span: Span,
+}
+
 pub struct Tokenizer<'a> {
     input: &'a str,
     position: usize,
     ch: u8,
+    finished: bool, // set once the iterator has yielded `Eof` or an error
 }
 
 // ========================================================================================
 // IMPLEMENTATION
 // ========================================================================================
 impl<'a> Tokenizer<'a> {
+    #[must_use]
     pub fn new(input: &'a str) -> Self {
         let mut tokenizer = Self {
             input,
             position: 0,
             ch: 0,
+            finished: false,
         };
         // Get first to ensure correct pos. 
         tokenizer.read_char();
         tokenizer 
     }
     
+    /// Reads every remaining token, ending with (and including) `Token::Eof`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `TokenizerError` in the input.
+    pub fn tokenize(&mut self) -> Result<Vec<Token>, TokenizerError> {
+        Ok(self.tokenize_spanned()?.into_iter().map(|spanned| spanned.token).collect())
+    }
+
+    /// Like `tokenize`, keeping each token's span.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `TokenizerError` in the input.
+    pub fn tokenize_spanned(&mut self) -> Result<Vec<SpannedToken>, TokenizerError> {
+        let mut tokens = Vec::new();
+        loop {
+            let spanned = self.next_spanned_token()?;
+            let is_eof = spanned.token == Token::Eof;
+            tokens.push(spanned);
+            if is_eof {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    /// Reads the next token.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TokenizerError` if the input there isn't a valid token.
     pub fn get_next_token(&mut self) -> Result<Token, TokenizerError> {
-        self.skip_whitespace();
+        self.next_spanned_token().map(|spanned| spanned.token)
+    }
+
+    /// Reads the next token along with the bytes it covers. `Eof` gets an empty
+    /// span at the end of the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TokenizerError` if the input there isn't a valid token.
+    pub fn next_spanned_token(&mut self) -> Result<SpannedToken, TokenizerError> {
+        self.skip_whitespace_and_comments()?;
+        let start = self.offset();
+        let token = self.read_token()?;
+        Ok(SpannedToken { token, span: Span { start, end: self.offset() } })
+    }
+
+    /// Byte offset of the current character, `self.ch`.
+    fn offset(&self) -> usize {
+        (self.position - 1).min(self.input.len())
+    }
+
+    fn read_token(&mut self) -> Result<Token, TokenizerError> {
 
         let token = match self.ch {
             // Dont forget teh b is a byte literal
@@ -108,6 +205,7 @@ impl<'a> Tokenizer<'a> {
             b')' => Ok(Token::CloseBracket),
             b',' => Ok(Token::Comma),
             b'\'' => self.read_string_literal(),
+            b'"' => self.read_quoted_identifier(),
             // This is the end of the input string.
             0 => Ok(Token::Eof),
 
@@ -144,9 +242,13 @@ impl<'a> Tokenizer<'a> {
                 return Ok(Self::lookup_ident(&literal));
             }
             
-            // If it's a digit, it's a number.
-            b'0'..=b'9' => {
+            // If it's a digit, it's a number. With no arithmetic operators, a '-'
+            // directly before a digit can only be a sign, so it's part of the literal.
+            b'0'..=b'9' | b'-' if self.ch != b'-' || self.input.as_bytes().get(self.position).is_some_and(u8::is_ascii_digit) => {
                 let literal = self.read_numeric_literal();
+                if literal.contains('.') {
+                    return Ok(Token::FloatLiteral(literal));
+                }
                 return Ok(Token::NumericLiteral(literal));
             },
             _ => Err(TokenizerError::UnexpectedCharacter(self.ch as char, self.position)),
@@ -173,40 +275,103 @@ impl<'a> Tokenizer<'a> {
         self.input[start_pos..self.position - 1].to_string()
     }
     
+    // Reads an optional '-' and digits, plus a fractional part if a '.' is followed
+    // by another digit.
     fn read_numeric_literal(&mut self) -> String {
         let start_pos = self.position - 1;
+        if self.ch == b'-' {
+            self.read_char();
+        }
         while self.ch.is_ascii_digit() {
             self.read_char();
         }
+        if self.ch == b'.' && self.input.as_bytes().get(self.position).is_some_and(u8::is_ascii_digit) {
+            self.read_char(); // Consume the '.'
+            while self.ch.is_ascii_digit() {
+                self.read_char();
+            }
+        }
         self.input[start_pos..self.position - 1].to_string()
     }
 
     fn read_string_literal(&mut self) -> Result<Token, TokenizerError> {
         let start_pos = self.position;
+        let literal = self.read_quoted(b'\'').ok_or(TokenizerError::UnterminatedString(start_pos))?;
+        Ok(Token::StringLiteral(literal))
+    }
+
+    // A quoted identifier is taken verbatim: case is kept and keywords aren't looked up.
+    fn read_quoted_identifier(&mut self) -> Result<Token, TokenizerError> {
+        let start_pos = self.position;
+        let ident = self.read_quoted(b'"').ok_or(TokenizerError::UnterminatedIdentifier(start_pos))?;
+        if ident.is_empty() {
+            return Err(TokenizerError::InvalidIdentifier("\"\"".to_string(), start_pos));
+        }
+        Ok(Token::Identifier(ident))
+    }
+
+    // Reads up to the closing `quote`, leaving it as the current character. A doubled
+    // quote inside ('it''s') stands for a single one. `None` if the input ends first.
+    fn read_quoted(&mut self, quote: u8) -> Option<String> {
         self.read_char(); // Consume the opening quote
-        
-        while self.ch != b'\'' {
-            if self.ch == 0 { // Reached end of input without closing quote
-                return Err(TokenizerError::UnterminatedString(start_pos));
+
+        let mut text = String::new();
+        let mut segment_start = self.position - 1;
+        loop {
+            match self.ch {
+                0 => return None,
+                ch if ch == quote && self.input.as_bytes().get(self.position) == Some(&quote) => {
+                    text.push_str(&self.input[segment_start..self.position]); // Keeps one quote
+                    self.read_char();
+                    self.read_char();
+                    segment_start = self.position - 1;
+                }
+                ch if ch == quote => break,
+                _ => self.read_char(),
             }
-            self.read_char();
         }
 
-        let literal = self.input[start_pos..self.position-1].to_string();
-        Ok(Token::StringLiteral(literal))
+        text.push_str(&self.input[segment_start..self.position - 1]);
+        Some(text)
     }
 
     fn lookup_ident(ident: &str) -> Token {
         match ident.to_uppercase().as_str() {
             "SELECT" => Token::Select,
+            "DISTINCT" => Token::Distinct,
             "FROM" => Token::From,
             "INTO" => Token::Into,
             "WHERE" => Token::Where,
             "INSERT" => Token::Insert,
             "DELETE" => Token::Delete,
+            "UPDATE" => Token::Update,
             "AND" => Token::And,
             "OR" => Token::Or,
+            "LIKE" => Token::Like,
+            "IN" => Token::In,
+            "BETWEEN" => Token::Between,
             "VALUES" => Token::Values,
+            "ORDER" => Token::Order,
+            "BY" => Token::By,
+            "ASC" => Token::Asc,
+            "DESC" => Token::Desc,
+            "LIMIT" => Token::Limit,
+            "OFFSET" => Token::Offset,
+            "FETCH" => Token::Fetch,
+            "IS" => Token::Is,
+            "NOT" => Token::Not,
+            "NULL" => Token::Null,
+            "TRUE" => Token::BooleanLiteral(true),
+            "FALSE" => Token::BooleanLiteral(false),
+            "CREATE" => Token::Create,
+            "TABLE" => Token::Table,
+            "TRUNCATE" => Token::Truncate,
+            "DROP" => Token::Drop,
+            "ALTER" => Token::Alter,
+            "UNIQUE" => Token::Unique,
+            "DEFAULT" => Token::Default,
+            "UNION" => Token::Union,
+            "DESCRIBE" => Token::Describe,
             _ => Token::Identifier(ident.to_string()),
         }
     }
@@ -216,6 +381,52 @@ impl<'a> Tokenizer<'a> {
             self.read_char();
         }
     }
+
+    /// Skips whitespace along with `-- ...` comments (to the end of the line) and
+    /// `/* ... */` comments, which don't nest.
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), TokenizerError> {
+        loop {
+            self.skip_whitespace();
+            let next = self.input.as_bytes().get(self.position).copied();
+            match (self.ch, next) {
+                (b'-', Some(b'-')) => {
+                    while self.ch != b'\n' && self.ch != 0 {
+                        self.read_char();
+                    }
+                }
+                (b'/', Some(b'*')) => {
+                    let start_pos = self.position;
+                    self.read_char(); // Consume the '/'
+                    self.read_char(); // Consume the '*'
+                    loop {
+                        match (self.ch, self.input.as_bytes().get(self.position)) {
+                            (0, _) => return Err(TokenizerError::UnterminatedComment(start_pos)),
+                            (b'*', Some(b'/')) => break,
+                            _ => self.read_char(),
+                        }
+                    }
+                    self.read_char(); // Consume the '*'
+                    self.read_char(); // Consume the '/'
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+}
+
+/// Yields each token in turn, ending with `Token::Eof`; after `Eof` or the first
+/// error the iterator is exhausted.
+impl Iterator for Tokenizer<'_> {
+    type Item = Result<Token, TokenizerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let token = self.get_next_token();
+        self.finished = !matches!(token, Ok(ref token) if *token != Token::Eof);
+        Some(token)
+    }
 }
 
 
@@ -229,14 +440,14 @@ mod tokenizer_tests {
 
     #[test]
     fn test_select_with_wildcard_token() -> Result<(), TokenizerError> {
-        let select_query = "SELECT * FROM table WHERE name = 'PHILIP';";
+        let select_query = "SELECT * FROM my_table WHERE name = 'PHILIP';";
         let mut tokenizer = Tokenizer::new(select_query);
 
         let expected_tokens = vec![
             Token::Select,
             Token::Asterisk,
             Token::From,
-            Token::Identifier("table".to_string()),
+            Token::Identifier("my_table".to_string()),
             Token::Where,
             Token::Identifier("name".to_string()),
             Token::Equals,
@@ -245,15 +456,7 @@ mod tokenizer_tests {
             Token::Eof,
         ];
         
-        let mut generated_tokens = Vec::new();
-        loop {
-            let token = tokenizer.get_next_token()?;
-            let is_eof = token == Token::Eof;
-            generated_tokens.push(token);
-            if is_eof {
-                break;
-            }
-        }
+        let generated_tokens = tokenizer.tokenize()?;
 
         assert_eq!(expected_tokens, generated_tokens);
         Ok(())
@@ -265,8 +468,8 @@ mod tokenizer_tests {
         let mut tokenizer = Tokenizer::new(create_query);
 
         let expected_tokens = vec![
-            Token::Identifier("CREATE".to_string()),
-            Token::Identifier("TABLE".to_string()),
+            Token::Create,
+            Token::Table,
             Token::Identifier("new_table".to_string()),
             Token::OpenBracket,
             Token::Identifier("column1".to_string()),
@@ -282,15 +485,7 @@ mod tokenizer_tests {
             Token::Eof,
         ];
         
-        let mut generated_tokens = Vec::new();
-        loop {
-            let token = tokenizer.get_next_token()?;
-            let is_eof = token == Token::Eof;
-            generated_tokens.push(token);
-            if is_eof {
-                break;
-            }
-        }
+        let generated_tokens = tokenizer.tokenize()?;
 
         assert_eq!(expected_tokens, generated_tokens);
         Ok(())
@@ -310,15 +505,7 @@ mod tokenizer_tests {
             Token::Eof,
         ];
 
-        let mut generated_tokens = Vec::new();
-        loop {
-            let token = tokenizer.get_next_token()?;
-            let is_eof = token == Token::Eof;
-            generated_tokens.push(token);
-            if is_eof {
-                break;
-            }
-        }
+        let generated_tokens = tokenizer.tokenize()?;
 
         assert_eq!(expected_tokens, generated_tokens);
         Ok(())
@@ -326,14 +513,14 @@ mod tokenizer_tests {
 
     #[test]
     fn test_binary_operators_and_keywords() -> Result<(), TokenizerError> {
-        let query = "SELECT column1 FROM table WHERE value1 >= 10 AND value2 <= 20 OR value3 != 'test';";
+        let query = "SELECT column1 FROM my_table WHERE value1 >= 10 AND value2 <= 20 OR value3 != 'test';";
         let mut tokenizer = Tokenizer::new(query);
 
         let expected_tokens = vec![
             Token::Select,
             Token::Identifier("column1".to_string()),
             Token::From,
-            Token::Identifier("table".to_string()),
+            Token::Identifier("my_table".to_string()),
             Token::Where,
             Token::Identifier("value1".to_string()),
             Token::GreaterThanOrEquals,
@@ -350,15 +537,7 @@ mod tokenizer_tests {
             Token::Eof,
         ];
 
-        let mut generated_tokens = Vec::new();
-        loop {
-            let token = tokenizer.get_next_token()?;
-            let is_eof = token == Token::Eof;
-            generated_tokens.push(token);
-            if is_eof {
-                break;
-            }
-        }
+        let generated_tokens = tokenizer.tokenize()?;
 
         assert_eq!(expected_tokens, generated_tokens);
         Ok(())
@@ -366,14 +545,14 @@ mod tokenizer_tests {
 
     #[test]
     fn test_insert_statement() -> Result<(), TokenizerError> {
-        let query = "INSERT INTO table VALUES ('first','second', '3', '4th');";
+        let query = "INSERT INTO my_table VALUES ('first','second', '3', '4th');";
         let mut tokenizer = Tokenizer::new(query);
 
         let expected_tokens = vec![
             Token::Insert,
             Token::Into,
-            Token::Identifier("table".to_string()),
-            Token::Identifier("VALUES".to_string()),
+            Token::Identifier("my_table".to_string()),
+            Token::Values,
             Token::OpenBracket,
             Token::StringLiteral("first".to_string()),
             Token::Comma,
@@ -386,16 +565,7 @@ mod tokenizer_tests {
             Token::Semicolon,
             Token::Eof,
         ];
-
-        let mut generated_tokens = Vec::new();
-        loop {
-            let token = tokenizer.get_next_token()?;
-            let is_eof = token == Token::Eof;
-            generated_tokens.push(token);
-            if is_eof {
-            break;
-            }
-        }
+        let generated_tokens = tokenizer.tokenize()?;
 
         assert_eq!(expected_tokens, generated_tokens);
         Ok(())
@@ -403,13 +573,13 @@ mod tokenizer_tests {
 
     #[test]
     fn test_insert_statement_with_columns() -> Result<(), TokenizerError> {
-        let query = "INSERT INTO table (firstColumn, secondColumn, thirdColumn, fourthColumn) VALUES ('first','second', '3', '4th');";
+        let query = "INSERT INTO my_table (firstColumn, secondColumn, thirdColumn, fourthColumn) VALUES ('first','second', '3', '4th');";
         let mut tokenizer = Tokenizer::new(query);
 
         let expected_tokens = vec![
             Token::Insert,
             Token::Into,
-            Token::Identifier("table".to_string()),
+            Token::Identifier("my_table".to_string()),
             Token::OpenBracket,
             Token::Identifier("firstColumn".to_string()),
             Token::Comma,
@@ -433,18 +603,369 @@ mod tokenizer_tests {
             Token::Eof,
         ];
 
+        let generated_tokens = tokenizer.tokenize()?;
 
-        let mut generated_tokens = Vec::new();
-        loop {
-            let token = tokenizer.get_next_token()?;
-            let is_eof = token == Token::Eof;
-            generated_tokens.push(token);
-            if is_eof {
-            break;
+        assert_eq!(expected_tokens, generated_tokens);
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncate_statement() -> Result<(), TokenizerError> {
+        let query = "TRUNCATE TABLE users RESTART IDENTITY;";
+        let mut tokenizer = Tokenizer::new(query);
+
+        let expected_tokens = vec![
+            Token::Truncate,
+            Token::Table,
+            Token::Identifier("users".to_string()),
+            Token::Identifier("RESTART".to_string()),
+            Token::Identifier("IDENTITY".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let generated_tokens = tokenizer.tokenize()?;
+
+        assert_eq!(expected_tokens, generated_tokens);
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_statement() -> Result<(), TokenizerError> {
+        let query = "UPDATE users SET age = 31 WHERE id = 1;";
+        let mut tokenizer = Tokenizer::new(query);
+
+        let expected_tokens = vec![
+            Token::Update,
+            Token::Identifier("users".to_string()),
+            Token::Identifier("SET".to_string()),
+            Token::Identifier("age".to_string()),
+            Token::Equals,
+            Token::NumericLiteral("31".to_string()),
+            Token::Where,
+            Token::Identifier("id".to_string()),
+            Token::Equals,
+            Token::NumericLiteral("1".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let generated_tokens = tokenizer.tokenize()?;
+
+        assert_eq!(expected_tokens, generated_tokens);
+        Ok(())
+    }
+
+    #[test]
+    fn test_order_by_clause() -> Result<(), TokenizerError> {
+        let query = "SELECT * FROM users ORDER BY age DESC, name asc;";
+        let mut tokenizer = Tokenizer::new(query);
+
+        let expected_tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Order,
+            Token::By,
+            Token::Identifier("age".to_string()),
+            Token::Desc,
+            Token::Comma,
+            Token::Identifier("name".to_string()),
+            Token::Asc,
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let generated_tokens = tokenizer.tokenize()?;
+
+        assert_eq!(expected_tokens, generated_tokens);
+        Ok(())
+    }
+
+    #[test]
+    fn test_limit_offset_clause() -> Result<(), TokenizerError> {
+        let query = "SELECT * FROM users LIMIT 10 offset 20;";
+        let mut tokenizer = Tokenizer::new(query);
+
+        let expected_tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Limit,
+            Token::NumericLiteral("10".to_string()),
+            Token::Offset,
+            Token::NumericLiteral("20".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let generated_tokens = tokenizer.tokenize()?;
+
+        assert_eq!(expected_tokens, generated_tokens);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_first_clause() -> Result<(), TokenizerError> {
+        let query = "SELECT * FROM users OFFSET 20 ROWS FETCH FIRST 10 ROWS ONLY;";
+        let mut tokenizer = Tokenizer::new(query);
+
+        let expected_tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Offset,
+            Token::NumericLiteral("20".to_string()),
+            Token::Identifier("ROWS".to_string()),
+            Token::Fetch,
+            Token::Identifier("FIRST".to_string()),
+            Token::NumericLiteral("10".to_string()),
+            Token::Identifier("ROWS".to_string()),
+            Token::Identifier("ONLY".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let generated_tokens = tokenizer.tokenize()?;
+
+        assert_eq!(expected_tokens, generated_tokens);
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_distinct() -> Result<(), TokenizerError> {
+        let query = "SELECT DISTINCT age FROM users;";
+        let mut tokenizer = Tokenizer::new(query);
+
+        let expected_tokens = vec![
+            Token::Select,
+            Token::Distinct,
+            Token::Identifier("age".to_string()),
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let generated_tokens = tokenizer.tokenize()?;
+
+        assert_eq!(expected_tokens, generated_tokens);
+        Ok(())
+    }
+
+    #[test]
+    fn test_null_predicates() -> Result<(), TokenizerError> {
+        let tokens = Tokenizer::new("WHERE email is not NULL").tokenize()?;
+        assert_eq!(tokens, vec![
+            Token::Where,
+            Token::Identifier("email".to_string()),
+            Token::Is,
+            Token::Not,
+            Token::Null,
+            Token::Eof,
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_token_spans() -> Result<(), TokenizerError> {
+        let query = "SELECT name FROM  users WHERE age >= 30 AND nick = 'it''s';";
+        let tokens = Tokenizer::new(query).tokenize_spanned()?;
+
+        let users = &tokens[3];
+        assert_eq!(users.token, Token::Identifier("users".to_string()));
+        assert_eq!(users.span, Span { start: 18, end: 23 });
+        assert_eq!(&query[users.span.start..users.span.end], "users");
+
+        for spanned in &tokens {
+            let text = &query[spanned.span.start..spanned.span.end];
+            match &spanned.token {
+                Token::GreaterThanOrEquals => assert_eq!(text, ">="),
+                Token::NumericLiteral(n) => assert_eq!(text, n),
+                Token::Eof => assert_eq!(spanned.span, Span { start: query.len(), end: query.len() }),
+                _ => assert!(!text.is_empty() && !text.contains(' ') || text.starts_with('\'')),
             }
         }
+        Ok(())
+    }
+
+    #[test]
+    fn test_float_literals() -> Result<(), TokenizerError> {
+        let query = "SELECT * FROM products WHERE price >= 9.99 AND stock = 3;";
+        let mut tokenizer = Tokenizer::new(query);
+
+        let expected_tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("products".to_string()),
+            Token::Where,
+            Token::Identifier("price".to_string()),
+            Token::GreaterThanOrEquals,
+            Token::FloatLiteral("9.99".to_string()),
+            Token::And,
+            Token::Identifier("stock".to_string()),
+            Token::Equals,
+            Token::NumericLiteral("3".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let generated_tokens = tokenizer.tokenize()?;
 
         assert_eq!(expected_tokens, generated_tokens);
         Ok(())
     }
+
+    #[test]
+    fn test_boolean_literals() -> Result<(), TokenizerError> {
+        let query = "SELECT * FROM users WHERE active = TRUE OR banned = false;";
+        let mut tokenizer = Tokenizer::new(query);
+
+        let expected_tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Where,
+            Token::Identifier("active".to_string()),
+            Token::Equals,
+            Token::BooleanLiteral(true),
+            Token::Or,
+            Token::Identifier("banned".to_string()),
+            Token::Equals,
+            Token::BooleanLiteral(false),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        let generated_tokens = tokenizer.tokenize()?;
+
+        assert_eq!(expected_tokens, generated_tokens);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tokenize_and_iterate() -> Result<(), TokenizerError> {
+        let query = "SELECT id FROM users;";
+        let expected_tokens = vec![
+            Token::Select,
+            Token::Identifier("id".to_string()),
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        assert_eq!(Tokenizer::new(query).tokenize()?, expected_tokens);
+        assert_eq!(Tokenizer::new(query).collect::<Result<Vec<_>, _>>()?, expected_tokens);
+
+        let mut tokenizer = Tokenizer::new("SELECT @");
+        assert!(matches!(tokenizer.next(), Some(Ok(Token::Select))));
+        assert!(matches!(tokenizer.next(), Some(Err(TokenizerError::UnexpectedCharacter('@', _)))));
+        assert!(tokenizer.next().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_comments_are_skipped() -> Result<(), TokenizerError> {
+        let query = "-- leading comment
+            SELECT /* the id */ id -- trailing comment
+            FROM users/**/WHERE id = 1; /* done */ -- end";
+        let expected_tokens = vec![
+            Token::Select,
+            Token::Identifier("id".to_string()),
+            Token::From,
+            Token::Identifier("users".to_string()),
+            Token::Where,
+            Token::Identifier("id".to_string()),
+            Token::Equals,
+            Token::NumericLiteral("1".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+        assert_eq!(Tokenizer::new(query).tokenize()?, expected_tokens);
+
+        // Comment markers inside a string are just text.
+        assert_eq!(
+            Tokenizer::new("'a -- b /* c */'").tokenize()?,
+            vec![Token::StringLiteral("a -- b /* c */".to_string()), Token::Eof]
+        );
+        assert!(matches!(
+            Tokenizer::new("SELECT /* never closed *").tokenize(),
+            Err(TokenizerError::UnterminatedComment(8))
+        ));
+        assert!(matches!(Tokenizer::new("SELECT - 1").tokenize(), Err(TokenizerError::UnexpectedCharacter('-', _))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_doubled_quotes_in_string_literals() -> Result<(), TokenizerError> {
+        let string = |sql: &str| -> Result<Vec<Token>, TokenizerError> { Tokenizer::new(sql).tokenize() };
+
+        assert_eq!(string("'it''s'")?, vec![Token::StringLiteral("it's".to_string()), Token::Eof]);
+        assert_eq!(
+            string("'''quoted'' and ''again'''")?,
+            vec![Token::StringLiteral("'quoted' and 'again'".to_string()), Token::Eof]
+        );
+        assert_eq!(string("''''")?, vec![Token::StringLiteral("'".to_string()), Token::Eof]);
+        assert_eq!(
+            string("'' = 'naïve'")?,
+            vec![Token::StringLiteral(String::new()), Token::Equals, Token::StringLiteral("naïve".to_string()), Token::Eof]
+        );
+        assert!(matches!(string("'it''s"), Err(TokenizerError::UnterminatedString(1))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_double_quoted_identifiers() -> Result<(), TokenizerError> {
+        let query = r#"SELECT "order", "Full Name", "a""b" FROM "select";"#;
+        let expected_tokens = vec![
+            Token::Select,
+            Token::Identifier("order".to_string()),
+            Token::Comma,
+            Token::Identifier("Full Name".to_string()),
+            Token::Comma,
+            Token::Identifier("a\"b".to_string()),
+            Token::From,
+            Token::Identifier("select".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+        assert_eq!(Tokenizer::new(query).tokenize()?, expected_tokens);
+
+        assert!(matches!(Tokenizer::new(r#"SELECT "order"#).tokenize(), Err(TokenizerError::UnterminatedIdentifier(8))));
+        assert!(matches!(Tokenizer::new(r#"SELECT """#).tokenize(), Err(TokenizerError::InvalidIdentifier(_, 8))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_negative_numeric_literals() -> Result<(), TokenizerError> {
+        let query = "SELECT * FROM accounts WHERE balance = -50 OR rate < -0.5;";
+        let expected_tokens = vec![
+            Token::Select,
+            Token::Asterisk,
+            Token::From,
+            Token::Identifier("accounts".to_string()),
+            Token::Where,
+            Token::Identifier("balance".to_string()),
+            Token::Equals,
+            Token::NumericLiteral("-50".to_string()),
+            Token::Or,
+            Token::Identifier("rate".to_string()),
+            Token::LessThan,
+            Token::FloatLiteral("-0.5".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+        assert_eq!(Tokenizer::new(query).tokenize()?, expected_tokens);
+
+        // A '-' not directly followed by a digit is still an error, and '--' a comment.
+        assert!(matches!(Tokenizer::new("- 5").tokenize(), Err(TokenizerError::UnexpectedCharacter('-', _))));
+        assert_eq!(Tokenizer::new("--5").tokenize()?, vec![Token::Eof]);
+        Ok(())
+    }
 }
@@ -0,0 +1,285 @@
+use rust_database::column::{ColumnBuilder, DataType};
+use rust_database::database::Database;
+use rust_database::executor::Executor;
+use rust_database::parser::{BinaryOperator, Expression, Literal, Parser, Statements};
+use rust_database::row::Value;
+use rust_database::schema::{NameResolution, Schema};
+use rust_database::tokenizer::Tokenizer;
+
+// ---------- Helpers ----------
+fn parse(sql: &str) -> Statements {
+    let tokens = Tokenizer::new(sql).tokenize().unwrap();
+    Parser::new(tokens).parse_statement().unwrap()
+}
+
+// ---------- Tests ----------
+#[test]
+fn create_sql_round_trips_through_the_parser() {
+    let schema = Schema::new(vec![
+        ColumnBuilder::new("id", DataType::Integer).build(),
+        ColumnBuilder::new("name", DataType::String).build(),
+        ColumnBuilder::new("age", DataType::Integer).build(),
+        ColumnBuilder::new("role", DataType::String).not_null().default(Value::String("guest".to_string())).unwrap().build(),
+    ])
+    .unwrap();
+
+    let sql = schema.to_create_sql("users");
+    let mut db = Database::new();
+    Executor {}.execute(&parse(&sql), &mut db).unwrap();
+
+    assert_eq!(db.get_table("users".to_string()).unwrap().schema, schema);
+}
+
+#[test]
+fn create_sql_round_trips_every_constraint_kind_and_awkward_names() {
+    let column = |name: &str| Box::new(Expression::Identifier(name.to_string(), None));
+    let literal = |literal: Literal| Box::new(Expression::Literal(literal, None));
+    let predicate = Expression::Binary(
+        Box::new(Expression::Binary(
+            Box::new(Expression::Between { expr: column("key"), low: literal(Literal::Integer(1)), high: literal(Literal::Integer(99)) }),
+            BinaryOperator::And,
+            Box::new(Expression::Not(Box::new(Expression::InList(column("say \"hi\""), vec![Literal::String("it's".to_string())], false)))),
+        )),
+        BinaryOperator::Or,
+        Box::new(Expression::Binary(
+            Box::new(Expression::IsNull(column("score"), true)),
+            BinaryOperator::And,
+            Box::new(Expression::IsDistinctFrom(column("score"), literal(Literal::Float(0.5)), false)),
+        )),
+    );
+    let schema = Schema::new(vec![
+        ColumnBuilder::new("key", DataType::Integer).not_null().unique().auto_increment().check(predicate).build(),
+        ColumnBuilder::new("say \"hi\"", DataType::String).default(Value::String("o'clock".to_string())).unwrap().build(),
+        ColumnBuilder::new("score", DataType::Float).build(),
+        ColumnBuilder::new("Active", DataType::Boolean).not_null().default(Value::Boolean(true)).unwrap().build(),
+    ])
+    .unwrap();
+
+    let sql = schema.to_create_sql("order items");
+    let mut db = Database::new();
+    Executor {}.execute(&parse(&sql), &mut db).unwrap();
+
+    assert_eq!(db.get_table("order items".to_string()).unwrap().schema, schema);
+}
+
+#[test]
+fn insert_with_scrambled_column_list_stores_schema_order() {
+    let mut db = Database::new();
+    Executor {}.execute(&parse("CREATE TABLE t (id INTEGER, name STRING);"), &mut db).unwrap();
+
+    Executor {}.execute(&parse("INSERT INTO t (name, id) VALUES ('A', 1);"), &mut db).unwrap();
+
+    let table = db.get_table("t".to_string()).unwrap();
+    let stored: Vec<&Vec<Value>> = table.rows.values().map(|row| &row.values).collect();
+    assert_eq!(stored, vec![&vec![Value::Integer(1), Value::String("A".to_string())]]);
+}
+
+#[test]
+fn create_table_constraints_are_enforced() {
+    let mut db = Database::new();
+    let sql = "CREATE TABLE users (id INTEGER PRIMARY KEY, email STRING NOT NULL UNIQUE, age INTEGER);";
+    Executor {}.execute(&parse(sql), &mut db).unwrap();
+
+    let expected = Schema::new(vec![
+        ColumnBuilder::new("id", DataType::Integer).not_null().unique().build(),
+        ColumnBuilder::new("email", DataType::String).not_null().unique().build(),
+        ColumnBuilder::new("age", DataType::Integer).build(),
+    ])
+    .unwrap();
+    assert_eq!(db.get_table("users".to_string()).unwrap().schema, expected);
+    assert_eq!(expected.to_create_sql("users"), r#"CREATE TABLE "users" ("id" INTEGER NOT NULL UNIQUE, "email" STRING NOT NULL UNIQUE, "age" INTEGER);"#);
+
+    Executor {}.execute(&parse("INSERT INTO users VALUES (1, 'a@example.com', 30);"), &mut db).unwrap();
+    for sql in [
+        "INSERT INTO users VALUES (1, 'b@example.com', 30);",
+        "INSERT INTO users VALUES (2, 'a@example.com', 30);",
+        "INSERT INTO users (id, age) VALUES (3, 30);",
+    ] {
+        assert!(Executor {}.execute(&parse(sql), &mut db).is_err(), "{sql}");
+    }
+    Executor {}.execute(&parse("INSERT INTO users (id, email) VALUES (2, 'b@example.com');"), &mut db).unwrap();
+    assert_eq!(db.get_table("users".to_string()).unwrap().row_count(), 2);
+}
+
+#[test]
+fn keyword_column_is_usable_when_double_quoted() {
+    let mut db = Database::new();
+    let executor = Executor {};
+    executor.execute(&parse(r#"CREATE TABLE orders (id INTEGER, "order" INTEGER);"#), &mut db).unwrap();
+    executor.execute(&parse(r#"INSERT INTO orders (id, "order") VALUES (1, 42);"#), &mut db).unwrap();
+
+    let result = executor.execute(&parse(r#"SELECT "order" FROM orders WHERE "order" = 42;"#), &mut db).unwrap();
+    assert_eq!(result.columns, vec!["order".to_string()]);
+    assert_eq!(result.rows[0].values, vec![Value::Integer(42)]);
+
+    // Unquoted, `order` is the ORDER keyword and the statement doesn't parse.
+    let tokens = Tokenizer::new("SELECT order FROM orders;").tokenize().unwrap();
+    assert!(Parser::new(tokens).parse_statement().is_err());
+}
+
+#[test]
+fn negative_literals_round_trip_through_the_executor() {
+    let mut db = Database::new();
+    let executor = Executor {};
+    executor.execute(&parse("CREATE TABLE accounts (id INTEGER, balance INTEGER);"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO accounts VALUES (1, -50);"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO accounts VALUES (2, 50);"), &mut db).unwrap();
+
+    let result = executor.execute(&parse("SELECT id, balance FROM accounts WHERE balance = -50;"), &mut db).unwrap();
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(result.rows[0].values, vec![Value::Integer(1), Value::Integer(-50)]);
+}
+
+#[test]
+fn mixed_case_identifiers_resolve_when_case_insensitive() {
+    let statements = [
+        "CREATE TABLE users (id INTEGER, name STRING);",
+        "INSERT INTO USERS (ID, Name) VALUES (1, 'Alice');",
+        "INSERT INTO Users (Id, NAME) VALUES (2, 'Bob');",
+        "UPDATE users SET NAME = 'Robert' WHERE Id = 2;",
+    ];
+
+    // Case-sensitive by default: the first mixed-case statement fails.
+    let mut db = Database::new();
+    let executor = Executor {};
+    executor.execute(&parse(statements[0]), &mut db).unwrap();
+    assert!(executor.execute(&parse(statements[1]), &mut db).is_err());
+
+    let mut db = Database::with_name_resolution(NameResolution::CaseInsensitive);
+    for sql in statements {
+        executor.execute(&parse(sql), &mut db).unwrap();
+    }
+    let result = executor.execute(&parse("SELECT NAME FROM USERS WHERE ID > 0 ORDER BY Id DESC;"), &mut db).unwrap();
+    assert_eq!(
+        result.rows.iter().map(|row| row.values.clone()).collect::<Vec<_>>(),
+        vec![vec![Value::String("Robert".to_string())], vec![Value::String("Alice".to_string())]]
+    );
+    assert_eq!(executor.execute(&parse("DESCRIBE Users;"), &mut db).unwrap().rows.len(), 2);
+}
+
+#[test]
+fn default_in_values_takes_the_column_default() {
+    let mut db = Database::new();
+    let executor = Executor {};
+    executor.execute(&parse("CREATE TABLE tasks (id INTEGER, status STRING DEFAULT 'open');"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO tasks VALUES (1, DEFAULT);"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO tasks (status, id) VALUES (DEFAULT, 2);"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO tasks (id) VALUES (3);"), &mut db).unwrap();
+
+    let result = executor.execute(&parse("SELECT status FROM tasks;"), &mut db).unwrap();
+    assert!(result.rows.iter().all(|row| row.values == vec![Value::String("open".to_string())]));
+    assert_eq!(result.rows.len(), 3);
+}
+
+#[test]
+fn truncate_restart_identity_rewinds_the_sequence() {
+    let next_id_after_truncate = |identity: &str| {
+        let mut db = Database::new();
+        let schema = Schema::new(vec![
+            ColumnBuilder::new("id", DataType::Integer).auto_increment().build(),
+            ColumnBuilder::new("name", DataType::String).build(),
+        ])
+        .unwrap();
+        db.create_table("users".to_string(), schema).unwrap();
+        let executor = Executor {};
+        executor.execute(&parse("INSERT INTO users (name) VALUES ('Alice');"), &mut db).unwrap();
+        executor.execute(&parse("INSERT INTO users (name) VALUES ('Bob');"), &mut db).unwrap();
+
+        let truncated = executor.execute(&parse(&format!("TRUNCATE TABLE users {identity};")), &mut db).unwrap();
+        assert_eq!(truncated.rows_affected, 2);
+        assert_eq!(db.get_table("users".to_string()).unwrap().row_count(), 0);
+
+        executor.execute(&parse("INSERT INTO users (name) VALUES ('Carol');"), &mut db).unwrap();
+        executor.execute(&parse("SELECT id FROM users;"), &mut db).unwrap().rows[0].values[0].clone()
+    };
+
+    assert_eq!(next_id_after_truncate("RESTART IDENTITY"), Value::Integer(1));
+    assert_eq!(next_id_after_truncate("CONTINUE IDENTITY"), Value::Integer(3));
+    assert_eq!(next_id_after_truncate(""), Value::Integer(3));
+}
+
+#[test]
+fn non_reserved_keywords_work_as_column_names() {
+    let mut db = Database::new();
+    let executor = Executor {};
+    executor
+        .execute(&parse("CREATE TABLE people (id INTEGER PRIMARY KEY, key STRING, first STRING, last STRING, nulls STRING, next STRING, rows INTEGER, only BOOLEAN);"), &mut db)
+        .unwrap();
+    executor.execute(&parse("INSERT INTO people (id, key, first, last, nulls, next, rows, only) VALUES (1, 'a', 'Ada', 'Lee', NULL, 'Bob', 3, TRUE);"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO people VALUES (2, 'c', 'Cy', 'Ng', 'x', 'Di', 1, FALSE);"), &mut db).unwrap();
+    assert!(executor.execute(&parse("INSERT INTO people VALUES (2, 'e', 'Ed', 'Ro', NULL, 'Fi', 0, FALSE);"), &mut db).is_err());
+
+    let result = executor.execute(&parse("SELECT key FROM people WHERE key = 'a';"), &mut db).unwrap();
+    assert_eq!(result.rows[0].values, vec![Value::String("a".to_string())]);
+
+    let result = executor
+        .execute(&parse("SELECT first, rows FROM people WHERE only = FALSE OR rows > 0 ORDER BY rows FETCH FIRST 1 ROWS ONLY;"), &mut db)
+        .unwrap();
+    assert_eq!(result.columns, vec!["first".to_string(), "rows".to_string()]);
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(result.rows[0].values, vec![Value::String("Cy".to_string()), Value::Integer(1)]);
+
+    let result = executor.execute(&parse("SELECT last FROM people ORDER BY nulls ASC NULLS LAST;"), &mut db).unwrap();
+    let lasts: Vec<&Value> = result.rows.iter().map(|row| &row.values[0]).collect();
+    assert_eq!(lasts, vec![&Value::String("Ng".to_string()), &Value::String("Lee".to_string())]);
+
+    executor.execute(&parse("CREATE TABLE flags (all BOOLEAN);"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO flags (all) VALUES (TRUE);"), &mut db).unwrap();
+    let result = executor.execute(&parse("SELECT all FROM flags UNION ALL SELECT all FROM flags;"), &mut db).unwrap();
+    assert_eq!(result.rows.len(), 2);
+
+    executor.execute(&parse("CREATE TABLE settings (set STRING);"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO settings VALUES ('a');"), &mut db).unwrap();
+    executor.execute(&parse("UPDATE settings SET set = 'b' WHERE set = 'a';"), &mut db).unwrap();
+    let result = executor.execute(&parse("SELECT set FROM settings;"), &mut db).unwrap();
+    assert_eq!(result.rows[0].values, vec![Value::String("b".to_string())]);
+
+    executor.execute(&parse("CREATE TABLE moves (rename STRING, to STRING);"), &mut db).unwrap();
+    executor.execute(&parse("ALTER TABLE moves RENAME TO transfers;"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO transfers (rename, to) VALUES ('x', 'y');"), &mut db).unwrap();
+    let result = executor.execute(&parse("SELECT to FROM transfers WHERE rename = 'x';"), &mut db).unwrap();
+    assert_eq!(result.rows[0].values, vec![Value::String("y".to_string())]);
+
+    executor.execute(&parse("CREATE TABLE if (exists BOOLEAN);"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO if (exists) VALUES (TRUE);"), &mut db).unwrap();
+    assert_eq!(executor.execute(&parse("SELECT exists FROM if;"), &mut db).unwrap().rows.len(), 1);
+    executor.execute(&parse("DROP TABLE IF EXISTS if;"), &mut db).unwrap();
+    executor.execute(&parse("DROP TABLE IF EXISTS if;"), &mut db).unwrap();
+    assert!(db.get_table("if".to_string()).is_err());
+
+    executor.execute(&parse("CREATE TABLE identity (restart INTEGER, continue INTEGER);"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO identity (restart, continue) VALUES (1, 2);"), &mut db).unwrap();
+    assert_eq!(executor.execute(&parse("SELECT restart, continue FROM identity;"), &mut db).unwrap().rows.len(), 1);
+    executor.execute(&parse("TRUNCATE identity RESTART IDENTITY;"), &mut db).unwrap();
+    assert!(db.get_table("identity".to_string()).unwrap().is_empty());
+
+    executor.execute(&parse("CREATE TABLE cells (row INTEGER, column INTEGER, value STRING);"), &mut db).unwrap();
+    executor.execute(&parse("ALTER TABLE cells ALTER COLUMN column TYPE STRING;"), &mut db).unwrap();
+    executor.execute(&parse("ALTER TABLE cells DROP COLUMN row;"), &mut db).unwrap();
+    let columns = db.get_table("cells".to_string()).unwrap().schema.column_names().join(",");
+    assert_eq!(columns, "column,value");
+
+    executor.execute(&parse("CREATE TABLE tables (show STRING);"), &mut db).unwrap();
+    executor.execute(&parse("INSERT INTO tables (show) VALUES ('on');"), &mut db).unwrap();
+    assert_eq!(executor.execute(&parse("SELECT show FROM tables;"), &mut db).unwrap().rows.len(), 1);
+    let listed = executor.execute(&parse("SHOW TABLES;"), &mut db).unwrap();
+    assert!(listed.rows.iter().any(|row| row.values == vec![Value::String("tables".to_string())]));
+}
+
+#[test]
+fn union_order_by_and_limit_apply_to_the_combined_rows() {
+    let mut db = Database::new();
+    let executor = Executor {};
+    executor.execute(&parse("CREATE TABLE n (v INTEGER);"), &mut db).unwrap();
+    for v in [3, 1, 2] {
+        executor.execute(&parse(&format!("INSERT INTO n VALUES ({v});")), &mut db).unwrap();
+    }
+
+    let result = executor.execute(&parse("SELECT v FROM n UNION ALL SELECT v FROM n ORDER BY v LIMIT 2;"), &mut db).unwrap();
+    let values: Vec<&Value> = result.rows.iter().map(|row| &row.values[0]).collect();
+    assert_eq!(values, vec![&Value::Integer(1), &Value::Integer(1)]);
+
+    let result = executor.execute(&parse("SELECT v FROM n UNION SELECT v FROM n ORDER BY v DESC OFFSET 1;"), &mut db).unwrap();
+    let values: Vec<&Value> = result.rows.iter().map(|row| &row.values[0]).collect();
+    assert_eq!(values, vec![&Value::Integer(2), &Value::Integer(1)]);
+}